@@ -0,0 +1,330 @@
+//! Library entry points
+//!
+//! Side-effect-free functions that return data instead of printing it. The
+//! CLI commands are thin wrappers over these.
+//!
+//! ```no_run
+//! use proc_cli::{Filter, KillOptions};
+//!
+//! let mut filter = Filter::default();
+//! filter.name = Some("node".to_string());
+//! filter.min_cpu = Some(5.0);
+//! for proc in proc_cli::list(&filter)? {
+//!     println!("{} {}", proc.pid, proc.name);
+//! }
+//!
+//! let report = proc_cli::kill(&[":3000".to_string()], &KillOptions::default())?;
+//! println!("killed {}", report.killed.len());
+//! # Ok::<(), proc_cli::ProcError>(())
+//! ```
+
+use crate::core::process::{split_sort_spec, wait_for_exits};
+use crate::core::{
+    diff_samples, group_sockets, resolve_targets, sort_deltas, terminate_with_escalation,
+    EscalationTiming, Filter, PortFilter, PortInfo, PortSortKey, Process, ProcessDelta,
+    ProcessSockets,
+};
+use crate::error::{ProcError, Result};
+use crate::ui::format_wait;
+use std::collections::HashSet;
+use std::time::Duration;
+
+/// List processes matching a filter, sorted and limited as the filter requests
+///
+/// Returns an empty list when nothing matches.
+pub fn list(filter: &Filter) -> Result<Vec<Process>> {
+    Ok(filter.apply(candidates(filter)?))
+}
+
+/// Like [`list`], also counting processes left out only because their working
+/// directory or executable path could not be read (see
+/// [`Filter::count_hidden_by_permissions`])
+pub fn list_counting_hidden(filter: &Filter) -> Result<(Vec<Process>, usize)> {
+    let processes = candidates(filter)?;
+    let hidden = filter.count_hidden_by_permissions(&processes);
+    Ok((filter.apply(processes), hidden))
+}
+
+/// Like [`list_counting_hidden`], matching any of several name patterns
+///
+/// Each pattern is tried with the rest of the filter, a process matching
+/// more than one is listed once, and the filter's sort and limit apply to the
+/// combined result. The filter's own `name` is ignored.
+pub fn list_matching_any(filter: &Filter, names: &[String]) -> Result<(Vec<Process>, usize)> {
+    let with_name = |name: &String| Filter {
+        name: Some(name.clone()),
+        ..filter.clone()
+    };
+    if let [name] = names {
+        return list_counting_hidden(&with_name(name));
+    }
+
+    let per_name: Vec<Filter> = names
+        .iter()
+        .map(|name| Filter {
+            sort: None,
+            limit: None,
+            ..with_name(name)
+        })
+        .collect();
+    let snapshot = Process::find_all()?;
+    let hidden = snapshot
+        .iter()
+        .filter(|p| {
+            per_name
+                .iter()
+                .any(|f| f.count_hidden_by_permissions(std::slice::from_ref(p)) > 0)
+        })
+        .count();
+
+    let mut seen = HashSet::new();
+    let mut matched = Vec::new();
+    for f in &per_name {
+        matched.extend(
+            f.apply(snapshot.clone())
+                .into_iter()
+                .filter(|p| seen.insert(p.pid)),
+        );
+    }
+    let order = Filter {
+        sort: filter.sort.clone(),
+        limit: filter.limit,
+        ..Filter::default()
+    };
+    Ok((order.apply(matched), hidden))
+}
+
+/// The smallest snapshot a filter can be applied to
+fn candidates(filter: &Filter) -> Result<Vec<Process>> {
+    match filter.name {
+        // Subtree filtering needs every process, not just the name matches
+        Some(ref name) if !filter.glob && !filter.parent_recursive => Process::find_by_name(name),
+        _ => Process::find_all(),
+    }
+}
+
+/// Sample processes twice, `interval` apart, and report how each one changed
+///
+/// A process is included when it matches the filter in either sample. The
+/// filter's sort applies to the change (`cpu` and `mem` order by the size of
+/// the change), and its limit caps the result.
+pub fn list_delta(filter: &Filter, interval: Duration) -> Result<Vec<ProcessDelta>> {
+    let (before, after) = Process::sample_pair(interval)?;
+
+    let selector = Filter {
+        sort: None,
+        limit: None,
+        ..filter.clone()
+    };
+    let keep: HashSet<u32> = selector
+        .apply(before.clone())
+        .into_iter()
+        .chain(selector.apply(after.clone()))
+        .map(|p| p.pid)
+        .collect();
+    let retain = |mut sample: Vec<Process>| {
+        sample.retain(|p| keep.contains(&p.pid));
+        sample
+    };
+
+    let mut deltas = diff_samples(retain(before), retain(after));
+    if let Some(ref sort) = filter.sort {
+        sort_deltas(&mut deltas, sort);
+    }
+    if let Some(limit) = filter.limit {
+        deltas.truncate(limit);
+    }
+    Ok(deltas)
+}
+
+/// Like [`list`], with disk I/O rates (and CPU usage) measured over `interval`
+pub fn list_io(filter: &Filter, interval: Duration) -> Result<Vec<Process>> {
+    Ok(list_io_counting_hidden(filter, interval)?.0)
+}
+
+/// Like [`list_io`], also counting processes hidden by permissions as
+/// [`list_counting_hidden`] does
+pub fn list_io_counting_hidden(
+    filter: &Filter,
+    interval: Duration,
+) -> Result<(Vec<Process>, usize)> {
+    let processes = Process::find_all_io_sampled(interval)?;
+    let hidden = filter.count_hidden_by_permissions(&processes);
+    Ok((filter.apply(processes), hidden))
+}
+
+/// List listening ports matching a filter
+pub fn ports(filter: &PortFilter) -> Result<Vec<PortInfo>> {
+    Ok(filter.apply(PortInfo::get_all_listening()?))
+}
+
+/// List, per process, the ports it listens on and its established connections
+///
+/// Listening ports go through the whole filter; connections only have to
+/// match its name pattern and PIDs. Ordered by the filter's sort key: `port` (lowest
+/// listening port, processes that only hold connections last), `pid`, or `name`.
+pub fn sockets(filter: &PortFilter) -> Result<Vec<ProcessSockets>> {
+    let listening = filter.apply(PortInfo::get_all_listening()?);
+    let mut connections = PortInfo::established_connections()?;
+    if let Some(ref name) = filter.name {
+        let name = name.to_lowercase();
+        connections.retain(|c| c.process_name.to_lowercase().contains(&name));
+    }
+    if !filter.pids.is_empty() {
+        connections.retain(|c| filter.pids.contains(&c.pid));
+    }
+
+    let mut grouped = group_sockets(listening, connections);
+    let (key, descending) = split_sort_spec(filter.sort.as_deref().unwrap_or("port"));
+    match PortSortKey::parse(key) {
+        Some(PortSortKey::Pid) => {}
+        Some(PortSortKey::Name) => grouped.sort_by_key(|s| s.process_name.to_lowercase()),
+        _ => grouped.sort_by_key(|s| {
+            let lowest = s.listening.iter().map(|p| p.port).min();
+            (lowest.is_none(), lowest)
+        }),
+    }
+    if descending == Some(true) {
+        grouped.reverse();
+    }
+    Ok(grouped)
+}
+
+/// Options for [`kill`]
+///
+/// Fields may be added in minor releases, so start from
+/// `KillOptions::default()` and set the fields you need.
+#[derive(Debug, Clone, Default)]
+#[non_exhaustive]
+pub struct KillOptions {
+    /// Send SIGTERM instead of SIGKILL
+    pub graceful: bool,
+    /// Send this signal number instead (takes precedence over `graceful`)
+    pub signal: Option<i32>,
+    /// Skip processes whose name or command contains any of these patterns
+    pub exclude: Vec<String>,
+    /// After signaling, wait up to this long for each process to disappear
+    pub wait: Option<Duration>,
+    /// With `graceful`, SIGKILL processes still running this long after SIGTERM
+    pub escalate_after: Option<Duration>,
+    /// Longest gap between checks while waiting (defaults to
+    /// [`EscalationTiming::DEFAULT_POLL_INTERVAL`])
+    pub poll_interval: Option<Duration>,
+    /// With `escalate_after`, stop every process under one shared grace
+    /// period instead of one process at a time
+    pub parallel: bool,
+}
+
+impl KillOptions {
+    /// Check whether a process is excluded from killing
+    pub fn excludes(&self, proc: &Process) -> bool {
+        self.exclude.iter().any(|pat| proc.matches_pattern(pat))
+    }
+}
+
+/// Outcome of a kill operation
+#[derive(Debug, Default)]
+#[non_exhaustive]
+pub struct KillReport {
+    /// Processes that were signaled successfully but not waited on
+    pub killed: Vec<Process>,
+    /// Processes confirmed to have exited (only populated when waiting or escalating)
+    pub confirmed: Vec<Process>,
+    /// Processes that ignored SIGTERM and needed SIGKILL (only when escalating)
+    pub escalated: Vec<Process>,
+    /// Processes that could not be signaled or survived the wait, with the reason
+    pub failed: Vec<(Process, ProcError)>,
+    /// Processes skipped because their PID now belongs to a different process
+    pub reused: Vec<Process>,
+    /// Processes not signaled because they are already zombies or dead
+    pub defunct: Vec<Process>,
+    /// Targets that did not resolve to any process
+    pub not_found: Vec<String>,
+    /// Processes a dry run would have signaled; nothing was sent to them
+    pub would_kill: Vec<Process>,
+}
+
+/// Resolve targets (`:port`, PID, or name) and kill every matching process
+///
+/// Returns `ProcError::ProcessNotFound` when no target resolves to a process.
+pub fn kill(targets: &[String], opts: &KillOptions) -> Result<KillReport> {
+    let (mut processes, not_found) = resolve_targets(targets);
+    processes.retain(|p| !opts.excludes(p));
+
+    if processes.is_empty() {
+        return Err(ProcError::ProcessNotFound(targets.join(",")));
+    }
+
+    let mut report = kill_processes(processes, opts);
+    report.not_found = not_found;
+    Ok(report)
+}
+
+/// Kill already-resolved processes
+pub fn kill_processes(processes: Vec<Process>, opts: &KillOptions) -> KillReport {
+    let mut report = KillReport::default();
+    let mut live = Vec::new();
+
+    for proc in processes {
+        // Guard against the PID having been recycled since resolution
+        if !proc.is_same_process() {
+            report.reused.push(proc);
+        // Signals can't touch a process that already exited
+        } else if proc.is_defunct() {
+            report.defunct.push(proc);
+        } else {
+            live.push(proc);
+        }
+    }
+
+    let poll_interval = opts
+        .poll_interval
+        .unwrap_or(EscalationTiming::DEFAULT_POLL_INTERVAL);
+    if let (None, true, Some(grace_period)) = (opts.signal, opts.graceful, opts.escalate_after) {
+        let timing = EscalationTiming {
+            grace_period,
+            poll_interval,
+        };
+        let batches = if opts.parallel {
+            vec![live]
+        } else {
+            live.into_iter().map(|proc| vec![proc]).collect()
+        };
+        for batch in batches {
+            let outcome = terminate_with_escalation(batch, timing);
+            report.confirmed.extend(outcome.terminated);
+            report.escalated.extend(outcome.escalated);
+            report.failed.extend(outcome.failed);
+        }
+        return report;
+    }
+
+    for proc in live {
+        let result = match opts.signal {
+            Some(signal) => proc.send_signal(signal),
+            None if opts.graceful => proc.terminate(),
+            None => proc.kill(),
+        };
+
+        match result {
+            Ok(()) => report.killed.push(proc),
+            Err(e) => report.failed.push((proc, e)),
+        }
+    }
+
+    if let Some(timeout) = opts.wait {
+        let (exited, survivors) =
+            wait_for_exits(std::mem::take(&mut report.killed), timeout, poll_interval);
+        report.confirmed = exited;
+        for proc in survivors {
+            let err = ProcError::Timeout(format!(
+                "PID {} still running {} after signal",
+                proc.pid,
+                format_wait(timeout)
+            ));
+            report.failed.push((proc, err));
+        }
+    }
+
+    report
+}
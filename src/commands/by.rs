@@ -2,20 +2,31 @@
 //!
 //! Examples:
 //!   proc by node               # Processes named 'node'
+//!   proc by node,python        # Either name (commas always split the pattern)
 //!   proc by node --in .        # Node processes in current directory
 //!   proc by node --min-cpu 5   # Node processes using >5% CPU
+//!   proc by node -x daemon    # Node processes, excluding 'daemon'
+//!   proc by worker --count   # Just the number of matches
+//!   proc by worker --expect 4  # Exit non-zero unless 4 workers are up
 //!   proc by "my app"           # Processes with spaces in name
-
-use crate::core::{Process, ProcessStatus};
-use crate::error::Result;
+//!   proc by 'worker-?' --glob  # Glob anchored to the whole name
+//!   proc by server --name-only # Ignore matches in arguments like /etc/server.conf
+//!   proc by server -v          # Show whether each process matched on name or command line
+//!   proc by server --json --with-matches  # Add matched_on and match offsets to each process
+
+use crate::api;
+use crate::commands::{process_sort, resolve_dir, warn_if_missing_dir, ThresholdArgs};
+use crate::core::{parse_targets, Filter, MatchField, NameMatch, Process, StatusFilter};
+use crate::error::{ProcError, Result};
 use crate::ui::{OutputFormat, Printer};
 use clap::Args;
-use std::path::PathBuf;
+use colored::*;
+use std::collections::HashMap;
 
 /// Filter processes by name
 #[derive(Args, Debug)]
 pub struct ByCommand {
-    /// Process name or pattern to match
+    /// Process name or pattern to match (comma-separated for several; commas always split)
     pub name: String,
 
     /// Filter by directory (defaults to current directory if no path given)
@@ -34,155 +45,211 @@ pub struct ByCommand {
     #[arg(long)]
     pub min_mem: Option<f64>,
 
-    /// Filter by status: running, sleeping, stopped, zombie
-    #[arg(long)]
-    pub status: Option<String>,
+    /// Only show processes in this state
+    #[arg(long, value_enum, ignore_case = true)]
+    pub status: Option<StatusFilter>,
 
     /// Output as JSON
     #[arg(long, short = 'j')]
     pub json: bool,
 
+    /// Output format: human, json, or plain (tab-separated, no color or header)
+    #[arg(long, value_enum, default_value_t = OutputFormat::Human)]
+    pub format: OutputFormat,
+
+    /// Leave out the table's column header and separator line
+    #[arg(long)]
+    pub no_header: bool,
+
     /// Show verbose output with command line, cwd, and parent PID
     #[arg(long, short = 'v')]
     pub verbose: bool,
 
+    /// Print only the number of matching processes
+    #[arg(long)]
+    pub count: bool,
+
+    /// Exit non-zero unless exactly this many processes match
+    #[arg(long)]
+    pub expect: Option<usize>,
+
+    /// Exit non-zero unless at least this many processes match
+    #[arg(long)]
+    pub expect_min: Option<usize>,
+
+    /// Exit non-zero unless at most this many processes match
+    #[arg(long)]
+    pub expect_max: Option<usize>,
+
     /// Limit the number of results
     #[arg(long, short = 'n')]
     pub limit: Option<usize>,
 
-    /// Sort by: cpu, mem, pid, name
-    #[arg(long, short = 's', default_value = "cpu")]
+    /// Exclude processes whose name or command contains this pattern (repeatable)
+    #[arg(long, short = 'x')]
+    pub exclude: Vec<String>,
+
+    /// Sort by: cpu, cputime, mem, vmem, io, nice, pid, name (add :asc or :desc to flip, e.g. cpu:asc)
+    #[arg(long, short = 's', default_value = "cpu", value_parser = process_sort)]
     pub sort: String,
+
+    /// Only show direct children of this PID
+    #[arg(long, value_name = "PID")]
+    pub parent: Option<u32>,
+
+    /// Only show descendants of this PID (children, grandchildren, ...)
+    #[arg(long, value_name = "PID", conflicts_with = "parent")]
+    pub parent_recursive: Option<u32>,
+
+    /// Match only the executable name, not the command line
+    #[arg(long, conflicts_with_all = ["cmd", "glob"])]
+    pub name_only: bool,
+
+    /// Match only the command line, not the executable name
+    #[arg(long, conflicts_with = "glob")]
+    pub cmd: bool,
+
+    /// Match the name as a shell-style glob against the whole process name (e.g. 'python*')
+    #[arg(long)]
+    pub glob: bool,
+
+    /// Exit non-zero when no processes match
+    #[arg(long, conflicts_with_all = ["count", "expect", "expect_min", "expect_max"])]
+    pub fail_empty: bool,
+
+    /// In JSON, add where the pattern matched each process: `matched_on`
+    /// (name, raw_name, or command) and character offsets `match_start`/`match_end`
+    #[arg(long, conflicts_with = "glob")]
+    pub with_matches: bool,
+
+    /// Levels at which CPU and memory cells turn yellow, then red
+    #[command(flatten)]
+    pub thresholds: ThresholdArgs,
 }
 
 impl ByCommand {
     /// Executes the by command, listing processes matching the name filter.
     pub fn execute(&self) -> Result<()> {
+        self.thresholds.apply()?;
         let format = if self.json {
             OutputFormat::Json
         } else {
-            OutputFormat::Human
+            self.format
         };
-        let printer = Printer::new(format, self.verbose);
-
-        // Get processes by name
-        let mut processes = Process::find_by_name(&self.name)?;
+        let mut printer = Printer::new(format, self.verbose).with_header(!self.no_header);
 
         // Resolve --in filter path
-        let in_dir_filter: Option<PathBuf> = self.in_dir.as_ref().map(|p| {
-            if p == "." {
-                std::env::current_dir().unwrap_or_else(|_| PathBuf::from("."))
-            } else {
-                let path = PathBuf::from(p);
-                if path.is_relative() {
-                    std::env::current_dir()
-                        .unwrap_or_else(|_| PathBuf::from("."))
-                        .join(path)
-                } else {
-                    path
-                }
-            }
-        });
+        let in_dir_filter = self.in_dir.as_deref().map(|p| resolve_dir(Some(p)));
+        if let Some(ref dir) = in_dir_filter {
+            warn_if_missing_dir(&printer, dir);
+        }
 
         // Resolve path filter
-        let path_filter: Option<PathBuf> = self.path.as_ref().map(|p| {
-            let path = PathBuf::from(p);
-            if path.is_relative() {
-                std::env::current_dir()
-                    .unwrap_or_else(|_| PathBuf::from("."))
-                    .join(path)
-            } else {
-                path
-            }
-        });
-
-        // Apply filters
-        processes.retain(|p| {
-            // Directory filter (--in)
-            if let Some(ref dir_path) = in_dir_filter {
-                if let Some(ref proc_cwd) = p.cwd {
-                    let proc_path = PathBuf::from(proc_cwd);
-                    if !proc_path.starts_with(dir_path) {
-                        return false;
-                    }
-                } else {
-                    return false;
-                }
-            }
+        let path_filter = self.path.as_deref().map(|p| resolve_dir(Some(p)));
 
-            // Path filter (executable path)
-            if let Some(ref exe_path) = path_filter {
-                if let Some(ref proc_exe) = p.exe_path {
-                    let proc_path = PathBuf::from(proc_exe);
-                    if !proc_path.starts_with(exe_path) {
-                        return false;
-                    }
-                } else {
-                    return false;
-                }
-            }
-
-            // CPU filter
-            if let Some(min_cpu) = self.min_cpu {
-                if p.cpu_percent < min_cpu {
-                    return false;
-                }
-            }
+        let names = parse_targets(&self.name);
+        if names.is_empty() {
+            return Err(ProcError::InvalidInput("no name pattern given".to_string()));
+        }
 
-            // Memory filter
-            if let Some(min_mem) = self.min_mem {
-                if p.memory_mb < min_mem {
-                    return false;
-                }
-            }
+        let filter = Filter {
+            glob: self.glob,
+            in_dir: in_dir_filter.clone(),
+            exe_path: path_filter,
+            min_cpu: self.min_cpu,
+            min_mem: self.min_mem,
+            status: self.status,
+            exclude: self.exclude.clone(),
+            match_field: if self.name_only {
+                MatchField::Name
+            } else if self.cmd {
+                MatchField::Command
+            } else {
+                MatchField::Both
+            },
+            parent: self.parent.or(self.parent_recursive),
+            parent_recursive: self.parent_recursive.is_some(),
+            sort: Some(self.sort.clone()),
+            limit: self.limit,
+            ..Filter::default()
+        };
 
-            // Status filter
-            if let Some(ref status) = self.status {
-                let status_match = match status.to_lowercase().as_str() {
-                    "running" => matches!(p.status, ProcessStatus::Running),
-                    "sleeping" | "sleep" => matches!(p.status, ProcessStatus::Sleeping),
-                    "stopped" | "stop" => matches!(p.status, ProcessStatus::Stopped),
-                    "zombie" => matches!(p.status, ProcessStatus::Zombie),
-                    _ => true,
-                };
-                if !status_match {
-                    return false;
-                }
+        if self.count || self.has_expectation() {
+            // Count the whole filtered set, ignoring --limit
+            let count = api::list_matching_any(
+                &Filter {
+                    limit: None,
+                    ..filter
+                },
+                &names,
+            )?
+            .0
+            .len();
+            if self.count {
+                printer.print_count(count)?;
+                return Ok(());
             }
-
-            true
-        });
-
-        // Sort processes
-        match self.sort.to_lowercase().as_str() {
-            "cpu" => processes.sort_by(|a, b| {
-                b.cpu_percent
-                    .partial_cmp(&a.cpu_percent)
-                    .unwrap_or(std::cmp::Ordering::Equal)
-            }),
-            "mem" | "memory" => processes.sort_by(|a, b| {
-                b.memory_mb
-                    .partial_cmp(&a.memory_mb)
-                    .unwrap_or(std::cmp::Ordering::Equal)
-            }),
-            "pid" => processes.sort_by_key(|p| p.pid),
-            "name" => processes.sort_by(|a, b| a.name.to_lowercase().cmp(&b.name.to_lowercase())),
-            _ => {} // Keep default order
+            return printer.check_count(count, self.expect, self.expect_min, self.expect_max);
         }
 
-        // Apply limit if specified
-        if let Some(limit) = self.limit {
-            processes.truncate(limit);
+        let (processes, hidden) = api::list_matching_any(&filter, &names)?;
+        if self.fail_empty && processes.is_empty() {
+            return Err(ProcError::ProcessNotFound(self.name.clone()));
+        }
+        if self.verbose && !self.glob {
+            printer = printer.with_notes(self.match_notes(&processes, &names, filter.match_field));
+        }
+        if self.with_matches {
+            // Each process reports the first pattern it matched
+            let spans = processes
+                .iter()
+                .filter_map(|p| {
+                    let span = names
+                        .iter()
+                        .find_map(|name| p.match_span(name, filter.match_field))?;
+                    Some((p.pid, span))
+                })
+                .collect();
+            printer = printer.with_match_spans(spans);
         }
 
         // Build context string for output
-        let mut context_parts = vec![format!("by '{}'", self.name)];
+        let patterns: Vec<String> = names.iter().map(|n| format!("'{}'", n)).collect();
+        let mut context_parts = vec![format!("by {}", patterns.join(", "))];
         if let Some(ref dir) = in_dir_filter {
             context_parts.push(format!("in {}", dir.display()));
         }
         let context = Some(context_parts.join(" "));
 
-        printer.print_processes_with_context(&processes, context.as_deref());
+        printer.print_processes_with_context(&processes, context.as_deref())?;
+        printer.print_hidden_by_permissions(hidden);
         Ok(())
     }
+
+    /// Describe where the (first matching) name pattern matched each process
+    fn match_notes(
+        &self,
+        processes: &[Process],
+        names: &[String],
+        field: MatchField,
+    ) -> HashMap<u32, String> {
+        processes
+            .iter()
+            .filter_map(|proc| {
+                let found = names.iter().find_map(|name| proc.name_match(name, field))?;
+                let note = match found {
+                    NameMatch::Name(name) => format!("{} {}", "matched name:".bright_black(), name),
+                    NameMatch::Command(snippet) => {
+                        format!("{} {}", "matched cmd:".bright_black(), snippet.yellow())
+                    }
+                };
+                Some((proc.pid, note))
+            })
+            .collect()
+    }
+
+    /// Whether any of --expect, --expect-min, or --expect-max was given
+    fn has_expectation(&self) -> bool {
+        self.expect.is_some() || self.expect_min.is_some() || self.expect_max.is_some()
+    }
 }
@@ -0,0 +1,80 @@
+//! `proc export` - Emit process and port metrics for monitoring systems
+//!
+//! Examples:
+//!   proc export --prometheus                 # Every process and listening port
+//!   proc export --filter node                # Only node processes and their ports
+//!   proc export --filter 'py*' --glob        # Glob match on the process name
+//!   proc export --filter api > api.prom      # For node_exporter's textfile collector
+
+use crate::core::{Filter, PortInfo, Process};
+use crate::error::Result;
+use crate::ui::prometheus;
+use clap::{Args, ValueEnum};
+use std::collections::HashSet;
+
+/// Metrics formats supported by `proc export`
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, ValueEnum)]
+pub enum ExportFormat {
+    /// Prometheus text exposition format
+    #[default]
+    Prometheus,
+}
+
+/// Emit process and port metrics
+#[derive(Args, Debug)]
+pub struct ExportCommand {
+    /// Metrics format
+    #[arg(long, value_enum, default_value_t = ExportFormat::Prometheus)]
+    pub format: ExportFormat,
+
+    /// Shorthand for --format prometheus
+    #[arg(long, conflicts_with = "format")]
+    pub prometheus: bool,
+
+    /// Only export processes whose name or command contains this pattern
+    #[arg(long, short = 'f')]
+    pub filter: Option<String>,
+
+    /// Match --filter as a shell-style glob against the whole process name
+    #[arg(long, requires = "filter")]
+    pub glob: bool,
+
+    /// Exclude processes whose name or command contains this pattern (repeatable)
+    #[arg(long, short = 'x')]
+    pub exclude: Vec<String>,
+
+    /// Skip listening-port metrics
+    #[arg(long)]
+    pub no_ports: bool,
+}
+
+impl ExportCommand {
+    /// Executes the export command, printing metrics to stdout.
+    pub fn execute(&self) -> Result<()> {
+        let filter = Filter {
+            name: self.filter.clone(),
+            glob: self.glob,
+            exclude: self.exclude.clone(),
+            sort: Some("pid".to_string()),
+            ..Filter::default()
+        };
+        // Two samples, so CPU usage is measured rather than always 0
+        let processes = filter.apply(Process::find_all_sampled()?);
+
+        let ports = if self.no_ports {
+            Vec::new()
+        } else {
+            let pids: HashSet<u32> = processes.iter().map(|p| p.pid).collect();
+            let mut ports = PortInfo::get_all_listening()?;
+            ports.retain(|p| pids.contains(&p.pid));
+            ports.sort_by_key(|p| p.port);
+            ports
+        };
+
+        match self.format {
+            ExportFormat::Prometheus => print!("{}", prometheus::render(&processes, &ports)),
+        }
+
+        Ok(())
+    }
+}
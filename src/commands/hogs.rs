@@ -0,0 +1,81 @@
+//! `proc cpu-hogs` / `proc mem-hogs` - Top processes by CPU or memory
+//!
+//! Shorthand for `proc list --sort cpu -n 10` (or `--sort mem`), with CPU
+//! usage sampled over a short interval so the numbers are meaningful.
+//!
+//! Examples:
+//!   proc cpu-hogs              # Ten busiest processes
+//!   proc mem-hogs --top 5      # Five largest processes by memory
+//!   proc cpu-hogs node         # Busiest node processes
+//!   proc mem-hogs -x chrome    # Largest processes, leaving out chrome
+
+use crate::core::{Filter, Process};
+use crate::error::Result;
+use crate::ui::{OutputFormat, Printer};
+use clap::Args;
+
+/// Resource a hogs command ranks processes by
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Resource {
+    /// CPU usage
+    Cpu,
+    /// Resident memory
+    Memory,
+}
+
+/// Show the processes using the most CPU or memory
+#[derive(Args, Debug)]
+pub struct HogsCommand {
+    /// Only consider processes whose name or command contains this pattern
+    pub name: Option<String>,
+
+    /// Number of processes to show
+    #[arg(long, short = 'n', default_value_t = 10)]
+    pub top: usize,
+
+    /// Exclude processes whose name or command contains this pattern (repeatable)
+    #[arg(long, short = 'x')]
+    pub exclude: Vec<String>,
+
+    /// Output as JSON
+    #[arg(long, short = 'j')]
+    pub json: bool,
+
+    /// Show verbose output with command line, cwd, and parent PID
+    #[arg(long, short = 'v')]
+    pub verbose: bool,
+}
+
+impl HogsCommand {
+    /// Executes the command, listing the top processes by `resource`.
+    pub fn execute(&self, resource: Resource) -> Result<()> {
+        let format = if self.json {
+            OutputFormat::Json
+        } else {
+            OutputFormat::Human
+        };
+        let printer = Printer::new(format, self.verbose);
+
+        let (sort, context) = match resource {
+            Resource::Cpu => ("cpu", "using the most CPU"),
+            Resource::Memory => ("mem", "using the most memory"),
+        };
+        let filter = Filter {
+            name: self.name.clone(),
+            exclude: self.exclude.clone(),
+            sort: Some(sort.to_string()),
+            limit: Some(self.top),
+            ..Filter::default()
+        };
+
+        // CPU needs two readings to mean anything; memory is accurate at once
+        let snapshot = match resource {
+            Resource::Cpu => Process::find_all_sampled()?,
+            Resource::Memory => Process::find_all()?,
+        };
+        let processes = filter.apply(snapshot);
+
+        printer.print_processes_with_context(&processes, Some(context))?;
+        Ok(())
+    }
+}
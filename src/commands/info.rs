@@ -6,18 +6,27 @@
 //!   proc info node              # Info for processes named node
 //!   proc info :3000,:8080       # Info for multiple targets
 //!   proc info :3000,1234,node   # Mixed targets (port + PID + name)
+//!   proc info nginx --cgroup    # Include the cgroup v2 path (Linux)
+//!   proc info supervisord -v    # Also list direct children
+//!   proc info 1234 --json --single  # One PID: the result is under `process`, not an array
 
-use crate::core::{parse_targets, resolve_target, Process, ProcessStatus};
+use crate::commands::read_targets;
+use crate::core::{
+    parse_target, resolve_target, split_threads, Process, ProcessStatus, TargetType,
+};
 use crate::error::Result;
-use crate::ui::{OutputFormat, Printer};
+use crate::ui::{format_bytes, format_duration, picker, OutputFormat, Printer};
 use clap::Args;
 use colored::*;
 use serde::Serialize;
 
+/// Most children listed by name in verbose output
+const MAX_CHILDREN_SHOWN: usize = 10;
+
 /// Show detailed process information
 #[derive(Args, Debug)]
 pub struct InfoCommand {
-    /// Target(s): PID, :port, or name (comma-separated for multiple)
+    /// Target(s): PID, :port, or name (comma-separated for multiple, or - to read from stdin)
     #[arg(required = true)]
     targets: Vec<String>,
 
@@ -28,6 +37,15 @@ pub struct InfoCommand {
     /// Show extra details
     #[arg(long, short)]
     verbose: bool,
+
+    /// Show the cgroup v2 path (Linux; also shown with --verbose)
+    #[arg(long)]
+    cgroup: bool,
+
+    /// With --json and a single PID, report the result under `process`
+    /// instead of a one-element `processes` array
+    #[arg(long)]
+    single: bool,
 }
 
 impl InfoCommand {
@@ -41,7 +59,10 @@ impl InfoCommand {
         let printer = Printer::new(format, self.verbose);
 
         // Flatten targets - support both space-separated and comma-separated
-        let all_targets: Vec<String> = self.targets.iter().flat_map(|t| parse_targets(t)).collect();
+        let mut all_targets = Vec::new();
+        for target in &self.targets {
+            all_targets.extend(read_targets(target)?);
+        }
 
         let mut found = Vec::new();
         let mut not_found = Vec::new();
@@ -50,6 +71,21 @@ impl InfoCommand {
         for target in &all_targets {
             match resolve_target(target) {
                 Ok(processes) => {
+                    // Ambiguous name on a terminal: let the user pick one
+                    let processes = if processes.len() > 1 && !self.json && picker::is_interactive()
+                    {
+                        let picked = picker::pick_one(
+                            &processes,
+                            &format!("'{}' matches {} processes", target, processes.len()),
+                        )?;
+                        if picked.is_empty() {
+                            continue;
+                        }
+                        picked
+                    } else {
+                        processes
+                    };
+
                     if processes.is_empty() {
                         not_found.push(target.clone());
                     } else {
@@ -65,18 +101,55 @@ impl InfoCommand {
             }
         }
 
-        if self.json {
+        // Threads are not children, so leave them out of the snapshot
+        let snapshot = if found.is_empty() {
+            Vec::new()
+        } else {
+            split_threads(Process::find_all()?).0
+        };
+        let children: Vec<Vec<Process>> = found
+            .iter()
+            .map(|proc| {
+                let mut children = proc.children(&snapshot);
+                children.sort_by_key(|c| c.pid);
+                children
+            })
+            .collect();
+
+        if printer.wants_json() {
+            let mut processes: Vec<ProcessInfoJson> = found
+                .iter()
+                .zip(&children)
+                .map(|(process, children)| ProcessInfoJson {
+                    process,
+                    cgroup: process.cgroup(),
+                    children_count: children.len(),
+                    children: children
+                        .iter()
+                        .map(|c| ChildJson {
+                            pid: c.pid,
+                            name: &c.name,
+                        })
+                        .collect(),
+                })
+                .collect();
+            // One PID asked for and found: no array to index into
+            let single = self.single
+                && processes.len() == 1
+                && matches!(all_targets.as_slice(), [t] if matches!(parse_target(t), TargetType::Pid(_)));
             printer.print_json(&InfoOutput {
                 action: "info",
                 success: !found.is_empty(),
                 found_count: found.len(),
                 not_found_count: not_found.len(),
-                processes: &found,
+                process: if single { processes.pop() } else { None },
+                processes: (!single).then_some(processes),
                 not_found: &not_found,
-            });
-        } else {
-            for proc in &found {
-                self.print_process_info(proc);
+            })?;
+        }
+        if !self.json {
+            for (proc, children) in found.iter().zip(&children) {
+                self.print_process_info(proc, children);
             }
 
             if !not_found.is_empty() {
@@ -89,13 +162,13 @@ impl InfoCommand {
         Ok(())
     }
 
-    fn print_process_info(&self, proc: &Process) {
+    fn print_process_info(&self, proc: &Process, children: &[Process]) {
         println!(
             "{} Process {}",
             "✓".green().bold(),
             proc.pid.to_string().cyan().bold()
         );
-        println!();
+        Printer::blank_line();
         println!("  {} {}", "Name:".bright_black(), proc.name.white().bold());
         println!(
             "  {} {}",
@@ -119,27 +192,72 @@ impl InfoCommand {
             );
         }
 
+        println!("  {} {}", "Children:".bright_black(), children.len());
+        if self.verbose {
+            for child in children.iter().take(MAX_CHILDREN_SHOWN) {
+                println!(
+                    "    {} {} [PID {}]",
+                    "→".bright_black(),
+                    child.name.white(),
+                    child.pid.to_string().cyan()
+                );
+            }
+            if children.len() > MAX_CHILDREN_SHOWN {
+                println!(
+                    "    {}",
+                    format!("... and {} more", children.len() - MAX_CHILDREN_SHOWN).bright_black()
+                );
+            }
+        }
+
         let status_str = format!("{:?}", proc.status);
         let status_colored = match proc.status {
             ProcessStatus::Running => status_str.green(),
             ProcessStatus::Sleeping => status_str.blue(),
             ProcessStatus::Stopped => status_str.yellow(),
             ProcessStatus::Zombie => status_str.red(),
+            ProcessStatus::DiskSleep => status_str.magenta(),
             _ => status_str.white(),
         };
         println!("  {} {}", "Status:".bright_black(), status_colored);
+        if let Some(nice) = proc.nice {
+            println!("  {} {}", "Nice:".bright_black(), nice);
+        }
 
         println!("  {} {:.1}%", "CPU:".bright_black(), proc.cpu_percent);
+        println!(
+            "  {} {}",
+            "CPU time:".bright_black(),
+            format_duration(proc.cpu_time_secs)
+        );
         println!("  {} {:.1} MB", "Memory:".bright_black(), proc.memory_mb);
+        println!(
+            "  {} {:.1} MB",
+            "Virtual:".bright_black(),
+            proc.virtual_memory_mb
+        );
+        if let Some(swap) = proc.swap_mb {
+            println!("  {} {:.1} MB", "Swap:".bright_black(), swap);
+        }
+        if let (Some(read), Some(written)) = (proc.read_bytes, proc.written_bytes) {
+            println!(
+                "  {} {} read, {} written",
+                "Disk I/O:".bright_black(),
+                format_bytes(read as f64),
+                format_bytes(written as f64)
+            );
+        }
 
-        if let Some(start_time) = proc.start_time {
-            let duration = std::time::SystemTime::now()
-                .duration_since(std::time::UNIX_EPOCH)
-                .map(|d| d.as_secs().saturating_sub(start_time))
-                .unwrap_or(0);
+        let uptime = proc
+            .uptime_secs
+            .map(format_duration)
+            .unwrap_or_else(|| "unknown".to_string());
+        println!("  {} {}", "Uptime:".bright_black(), uptime);
 
-            let uptime = format_duration(duration);
-            println!("  {} {}", "Uptime:".bright_black(), uptime);
+        if self.cgroup || self.verbose {
+            if let Some(cgroup) = proc.cgroup() {
+                println!("  {} {}", "Cgroup:".bright_black(), cgroup);
+            }
         }
 
         if self.verbose {
@@ -148,19 +266,7 @@ impl InfoCommand {
             }
         }
 
-        println!();
-    }
-}
-
-fn format_duration(secs: u64) -> String {
-    if secs < 60 {
-        format!("{}s", secs)
-    } else if secs < 3600 {
-        format!("{}m {}s", secs / 60, secs % 60)
-    } else if secs < 86400 {
-        format!("{}h {}m", secs / 3600, (secs % 3600) / 60)
-    } else {
-        format!("{}d {}h", secs / 86400, (secs % 86400) / 3600)
+        Printer::blank_line();
     }
 }
 
@@ -170,6 +276,24 @@ struct InfoOutput<'a> {
     success: bool,
     found_count: usize,
     not_found_count: usize,
-    processes: &'a [Process],
+    #[serde(skip_serializing_if = "Option::is_none")]
+    process: Option<ProcessInfoJson<'a>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    processes: Option<Vec<ProcessInfoJson<'a>>>,
     not_found: &'a [String],
 }
+
+#[derive(Serialize)]
+struct ProcessInfoJson<'a> {
+    #[serde(flatten)]
+    process: &'a Process,
+    cgroup: Option<String>,
+    children_count: usize,
+    children: Vec<ChildJson<'a>>,
+}
+
+#[derive(Serialize)]
+struct ChildJson<'a> {
+    pid: u32,
+    name: &'a str,
+}
@@ -5,20 +5,60 @@
 //!   proc kill :3000             # Kill what's on port 3000
 //!   proc kill 1234              # Kill specific PID
 //!   proc kill :3000,:8080       # Kill multiple targets
+//!   proc kill :3000-3999        # Kill everything listening in a port range
 //!   proc kill :3000,1234,node   # Mixed targets (port + PID + name)
 //!   proc kill node --yes        # Skip confirmation
+//!   proc kill node -x daemon    # Kill node processes except those matching 'daemon'
+//!   proc kill :3000 --wait      # Kill and confirm the process is gone
+//!   proc kill node --grace --grace-period 5s # SIGTERM, then SIGKILL whatever is left after 5s
+//!   proc kill node --grace --grace-period 300ms  # Same, for processes that exit fast
+//!   proc kill node --signal HUP # Send a specific signal (see `proc signals`)
+//!   proc kill 'worker-?' --glob # Kill worker-1, worker-2, ... but not worker-10
+//!   proc kill --regex '^chrome.*helper$'  # Regex on the process name, always confirmed
+//!   proc kill node -i           # Always pick from a checklist, even for one match
+//!   proc kill node --confirm-each  # Approve or skip each match in turn
+//!   proc kill node --max 5 -y   # Refuse if more than 5 processes match
+//!   proc kill node --tree --dry-run  # Preview the subtrees that would be killed
+//!   proc kill --pid-file /var/run/app.pid --remove-pidfile  # Stop a daemon by its pidfile
 
-use crate::core::{parse_targets, resolve_targets, Process};
+use crate::api::{self, KillOptions, KillReport};
+use crate::commands::{poll_interval, read_targets};
+use crate::core::{
+    find_ports_in_range, parse_signal, parse_target, resolve_targets, resolve_targets_glob,
+    resolve_targets_regex, PidFile, Process, TargetType,
+};
 use crate::error::{ProcError, Result};
-use crate::ui::{OutputFormat, Printer};
+use crate::ui::{parse_wait, picker, OutputFormat, Printer};
 use clap::Args;
 use dialoguer::Confirm;
+use std::collections::{HashMap, HashSet};
+use std::path::PathBuf;
+use std::time::Duration;
+
+/// Most processes `--regex` may match before `--yes` is required
+const MAX_REGEX_MATCHES: usize = 25;
+
+/// How long a signaled pidfile process gets to exit before its file is kept
+const PIDFILE_EXIT_CHECK: Duration = Duration::from_millis(200);
+
+/// Match count above which a kill without `--max` warns before going ahead
+const WARN_KILL_COUNT: usize = 50;
 
 /// Kill process(es)
 #[derive(Args, Debug)]
 pub struct KillCommand {
-    /// Target(s): process name, PID, or :port (comma-separated for multiple)
-    pub target: String,
+    /// Target(s): process name, PID, or :port (comma-separated for multiple, or - to read from stdin)
+    #[arg(required_unless_present = "pid_file")]
+    pub target: Option<String>,
+
+    /// Kill the process whose PID a daemon recorded in this file, refusing
+    /// if the file is stale (PID gone, or reused by another process)
+    #[arg(long, value_name = "PATH", conflicts_with_all = ["target", "glob", "regex"])]
+    pub pid_file: Option<PathBuf>,
+
+    /// Delete the --pid-file once its process has exited (kept if it survives the signal)
+    #[arg(long, requires = "pid_file")]
+    pub remove_pidfile: bool,
 
     /// Skip confirmation prompt
     #[arg(long, short = 'y')]
@@ -36,9 +76,64 @@ pub struct KillCommand {
     #[arg(long, short = 'v')]
     pub verbose: bool,
 
-    /// Send SIGTERM instead of SIGKILL (graceful)
-    #[arg(long, short = 'g')]
+    /// Send SIGTERM instead of SIGKILL (graceful); add --grace-period to SIGKILL stragglers
+    #[arg(long, short = 'g', visible_alias = "grace")]
     pub graceful: bool,
+
+    /// Send this signal instead of SIGKILL: name or number (see `proc signals`)
+    #[arg(long, short = 's', conflicts_with = "graceful")]
+    pub signal: Option<String>,
+
+    /// Exclude processes whose name or command contains this pattern (repeatable)
+    #[arg(long, short = 'x')]
+    pub exclude: Vec<String>,
+
+    /// Wait for killed processes to exit, failing any that survive
+    #[arg(long)]
+    pub wait: bool,
+
+    /// How long to wait with --wait (default 5s), or before escalating to
+    /// SIGKILL with --graceful (e.g. 500ms, 5s)
+    #[arg(
+        long,
+        visible_alias = "timeout",
+        value_name = "DURATION",
+        value_parser = parse_wait
+    )]
+    pub grace_period: Option<Duration>,
+
+    /// Longest gap between checks while waiting for processes to exit (e.g. 50ms)
+    #[arg(long, value_name = "DURATION", value_parser = poll_interval)]
+    pub poll_interval: Option<Duration>,
+
+    /// Match name targets as shell-style globs against the whole process name
+    #[arg(long)]
+    pub glob: bool,
+
+    /// Match name targets as regular expressions against the process name
+    /// (case-insensitive); always asks for an explicit y/n
+    #[arg(long, visible_alias = "name-regex", conflicts_with = "glob")]
+    pub regex: bool,
+
+    /// Allow killing ancestors of proc itself (e.g. your own shell)
+    #[arg(long)]
+    pub force_dangerous: bool,
+
+    /// Always choose which matches to kill from a checklist (requires a terminal)
+    #[arg(long, short = 'i', conflicts_with_all = ["yes", "json", "dry_run"])]
+    pub interactive: bool,
+
+    /// Ask about each match in turn (yes, no, or quit); skipped ones are reported
+    #[arg(long, conflicts_with_all = ["yes", "dry_run", "interactive"])]
+    pub confirm_each: bool,
+
+    /// Kill each match's descendants too (children, grandchildren, ...)
+    #[arg(long, visible_alias = "children")]
+    pub tree: bool,
+
+    /// Refuse, even with --yes, if more than this many processes would be killed
+    #[arg(long, value_name = "N")]
+    pub max: Option<usize>,
 }
 
 impl KillCommand {
@@ -51,43 +146,83 @@ impl KillCommand {
         };
         let printer = Printer::new(format, self.verbose);
 
-        // Parse comma-separated targets and resolve to processes
-        let targets = parse_targets(&self.target);
-        let (processes, not_found) = resolve_targets(&targets);
+        if self.interactive && !picker::is_interactive() {
+            return Err(ProcError::InvalidInput(
+                "--interactive needs a terminal on stdin and stdout".to_string(),
+            ));
+        }
+
+        let mut plan = self.plan()?;
 
         // Warn about targets that weren't found
-        for target in &not_found {
+        for target in &plan.not_found {
             printer.warning(&format!("Target not found: {}", target));
         }
-
-        if processes.is_empty() {
-            return Err(ProcError::ProcessNotFound(self.target.clone()));
+        for p in &plan.protected {
+            printer.warning(&format!(
+                "Skipping {} [PID {}]: it is an ancestor of this proc process (use --force-dangerous to kill it)",
+                p.name, p.pid
+            ));
         }
+        self.check_matches(&plan)?;
 
         // Dry run: just show what would be killed
         if self.dry_run {
+            if self.tree {
+                printer.print_kill_dry_run_tree(&plan.processes, &plan.matched)?;
+            } else {
+                printer.print_kill_dry_run(&plan.processes)?;
+            }
+            return Ok(());
+        }
+
+        if self.max.is_none() && plan.processes.len() > WARN_KILL_COUNT {
             printer.warning(&format!(
-                "Dry run: would kill {} process{}",
-                processes.len(),
-                if processes.len() == 1 { "" } else { "es" }
+                "About to kill {} processes; pass --max N to refuse when more than N match",
+                plan.processes.len()
             ));
-            printer.print_processes(&processes);
-            return Ok(());
         }
+        self.check_limits(plan.processes.len())?;
+
+        // Ports each process holds within any requested port range
+        let range_ports = self.range_ports(&plan.targets);
 
-        // Confirm before killing (unless --yes)
-        if !self.yes && !self.json {
-            self.print_confirmation_prompt(&processes);
+        // Several matches on a terminal: let the user pick which to kill
+        // (regex matches get the full list and a plain y/n instead)
+        let mut processes = std::mem::take(&mut plan.processes);
+        let wants_picker =
+            self.interactive || (!self.regex && !self.yes && !self.json && processes.len() > 1);
+        let mut skipped = Vec::new();
+        if self.confirm_each {
+            picker::require_confirmation_terminal("kill")?;
+            (processes, skipped) = confirm_each(processes)?;
+        } else if wants_picker && picker::is_interactive() {
+            if !range_ports.is_empty() {
+                self.print_confirmation_prompt(&processes, &range_ports);
+            }
+            processes = picker::pick_many(&processes, "Select processes to kill")?;
+            if processes.is_empty() {
+                printer.warning("Cancelled");
+                return Ok(());
+            }
+        } else if !self.yes && !self.json {
+            // Confirm before killing (unless --yes)
+            picker::require_confirmation_terminal("kill")?;
+            if self.regex {
+                self.print_regex_patterns(&plan.targets);
+            }
+            self.print_confirmation_prompt(&processes, &range_ports);
 
-            let confirmed = Confirm::new()
-                .with_prompt(format!(
-                    "Kill {} process{}?",
-                    processes.len(),
-                    if processes.len() == 1 { "" } else { "es" }
-                ))
-                .default(false)
-                .interact()
-                .unwrap_or(false);
+            let mut prompt = Confirm::new().with_prompt(format!(
+                "Kill {} process{}?",
+                processes.len(),
+                if processes.len() == 1 { "" } else { "es" }
+            ));
+            // Without a default, Enter alone doesn't answer: regex kills need an explicit y
+            if !self.regex {
+                prompt = prompt.default(false);
+            }
+            let confirmed = prompt.interact().unwrap_or(false);
 
             if !confirmed {
                 printer.warning("Cancelled");
@@ -96,24 +231,37 @@ impl KillCommand {
         }
 
         // Kill the processes
-        let mut killed = Vec::new();
-        let mut failed = Vec::new();
+        let report = api::kill_processes(processes, &plan.opts);
+        for proc in &report.reused {
+            printer.warning(&format!("PID {} reused, skipping", proc.pid));
+        }
+        let failed: Vec<(Process, String)> = report
+            .failed
+            .iter()
+            .map(|(proc, e)| (proc.clone(), e.to_string()))
+            .collect();
 
-        for proc in processes {
-            let result = if self.graceful {
-                proc.terminate()
-            } else {
-                proc.kill()
-            };
+        printer.print_kill_result(
+            &report.killed,
+            &report.confirmed,
+            &report.escalated,
+            &report.defunct,
+            &skipped,
+            &failed,
+        )?;
 
-            match result {
-                Ok(()) => killed.push(proc),
-                Err(e) => failed.push((proc, e.to_string())),
+        if let Some(ref pid_file) = plan.pid_file {
+            if self.remove_pidfile(pid_file, &report)? {
+                printer.success(&format!("Removed {}", pid_file.path.display()));
+            } else if self.remove_pidfile && failed.is_empty() {
+                printer.warning(&format!(
+                    "Kept {}: PID {} is still running (add --wait to give it time to exit)",
+                    pid_file.path.display(),
+                    pid_file.pid
+                ));
             }
         }
 
-        printer.print_kill_result(&killed, &failed);
-
         if failed.is_empty() {
             Ok(())
         } else {
@@ -124,15 +272,212 @@ impl KillCommand {
         }
     }
 
-    fn print_confirmation_prompt(&self, processes: &[Process]) {
+    /// Resolve the targets and kill every match without prompting or printing
+    ///
+    /// The confirmation flags (`--interactive`, `--confirm-each`) are ignored,
+    /// but `--max` and the `--regex` cap still apply. With `--dry-run`,
+    /// nothing is signaled and the matches are in [`KillReport::would_kill`].
+    /// Processes that could not be killed are reported in
+    /// [`KillReport::failed`] rather than as an error.
+    pub fn run(&self) -> Result<KillReport> {
+        let plan = self.plan()?;
+        self.check_matches(&plan)?;
+        self.check_limits(plan.processes.len())?;
+
+        let mut report = if self.dry_run {
+            KillReport {
+                would_kill: plan.processes,
+                ..KillReport::default()
+            }
+        } else {
+            api::kill_processes(plan.processes, &plan.opts)
+        };
+        if let Some(ref pid_file) = plan.pid_file {
+            self.remove_pidfile(pid_file, &report)?;
+        }
+        report.not_found = plan.not_found;
+        Ok(report)
+    }
+
+    /// Resolve targets (or the pidfile) to the processes a kill would signal
+    fn plan(&self) -> Result<KillPlan> {
+        // Validate the signal before resolving anything
+        let signal = self.signal.as_deref().map(parse_signal).transpose()?;
+
+        // Parse comma-separated targets and resolve to processes
+        let pid_file = self.pid_file.as_deref().map(PidFile::read).transpose()?;
+        let (targets, mut processes, not_found) = match (&pid_file, &self.target) {
+            (Some(pid_file), _) => (
+                vec![pid_file.pid.to_string()],
+                vec![pid_file.process()?],
+                Vec::new(),
+            ),
+            (None, target) => {
+                let targets = read_targets(target.as_deref().unwrap_or_default())?;
+                let (processes, not_found) = if self.regex {
+                    resolve_targets_regex(&targets)?
+                } else if self.glob {
+                    resolve_targets_glob(&targets)
+                } else {
+                    resolve_targets(&targets)
+                };
+                (targets, processes, not_found)
+            }
+        };
+        if self.grace_period.is_some() && !self.wait && !self.graceful {
+            return Err(ProcError::InvalidInput(
+                "--grace-period needs --wait or --graceful".to_string(),
+            ));
+        }
+        let opts = KillOptions {
+            graceful: self.graceful,
+            signal,
+            exclude: self.exclude.clone(),
+            wait: self
+                .wait
+                .then(|| self.grace_period.unwrap_or(Duration::from_secs(5))),
+            escalate_after: self.grace_period.filter(|_| self.graceful),
+            poll_interval: self.poll_interval,
+            // Every target is signaled at once, so they share the grace period
+            parallel: true,
+        };
+
+        // Drop anything matching an exclude pattern
+        processes.retain(|p| !opts.excludes(p));
+
+        // Never take down our own session (shell, terminal) by accident
+        let mut protected = Vec::new();
+        if !self.force_dangerous {
+            let ancestors = own_ancestors();
+            (protected, processes) = processes
+                .into_iter()
+                .partition(|p| ancestors.contains(&p.pid));
+        }
+
+        // Pull in every descendant, keeping each subtree after its root
+        let matched: HashSet<u32> = processes.iter().map(|p| p.pid).collect();
+        if self.tree {
+            let snapshot = Process::find_all()?;
+            let own_pid = std::process::id();
+            let mut seen = matched.clone();
+            let mut expanded = Vec::new();
+            for root in processes {
+                let descendants = root.descendants(&snapshot);
+                expanded.push(root);
+                expanded.extend(
+                    descendants
+                        .into_iter()
+                        .filter(|p| p.pid != own_pid && !opts.excludes(p) && seen.insert(p.pid)),
+                );
+            }
+            processes = expanded;
+        }
+
+        Ok(KillPlan {
+            targets,
+            processes,
+            matched,
+            not_found,
+            protected,
+            pid_file,
+            opts,
+        })
+    }
+
+    /// Fail when nothing is left to kill
+    fn check_matches(&self, plan: &KillPlan) -> Result<()> {
+        if !plan.processes.is_empty() {
+            Ok(())
+        } else if !plan.protected.is_empty() {
+            Err(ProcError::InvalidInput(
+                "refusing to kill an ancestor of proc; use --force-dangerous".to_string(),
+            ))
+        } else {
+            Err(ProcError::ProcessNotFound(plan.targets.join(",")))
+        }
+    }
+
+    /// Refuse kills broader than `--max`, or than the `--regex` cap without `--yes`
+    fn check_limits(&self, count: usize) -> Result<()> {
+        // A too-broad target can match far more than intended
+        if let Some(max) = self.max.filter(|&max| count > max) {
+            return Err(ProcError::InvalidInput(format!(
+                "would kill {} processes, more than --max {}; narrow the targets or raise --max",
+                count, max
+            )));
+        }
+
+        // A loose regex can sweep up far more than intended
+        if self.regex && !self.yes && count > MAX_REGEX_MATCHES {
+            return Err(ProcError::InvalidInput(format!(
+                "--regex matched {} processes (more than {}); narrow the pattern or pass --yes",
+                count, MAX_REGEX_MATCHES
+            )));
+        }
+        Ok(())
+    }
+
+    /// With `--remove-pidfile`, delete the pidfile once its process is gone
+    ///
+    /// Returns whether the file was removed.
+    fn remove_pidfile(&self, pid_file: &PidFile, report: &KillReport) -> Result<bool> {
+        if !self.remove_pidfile || !pidfile_process_gone(report) {
+            return Ok(false);
+        }
+        pid_file.remove()?;
+        Ok(true)
+    }
+
+    /// Map each PID to the ports it listens on inside any `:start-end` target
+    fn range_ports(&self, targets: &[String]) -> HashMap<u32, Vec<u16>> {
+        let mut map: HashMap<u32, Vec<u16>> = HashMap::new();
+        for target in targets {
+            if let TargetType::PortRange(start, end) = parse_target(target) {
+                for port in find_ports_in_range(start, end).unwrap_or_default() {
+                    let ports = map.entry(port.pid).or_default();
+                    if !ports.contains(&port.port) {
+                        ports.push(port.port);
+                    }
+                }
+            }
+        }
+        for ports in map.values_mut() {
+            ports.sort_unstable();
+        }
+        map
+    }
+
+    /// Echo the regular expressions name targets were matched with
+    fn print_regex_patterns(&self, targets: &[String]) {
         use colored::*;
 
+        for target in targets {
+            if let TargetType::Name(pattern) = parse_target(target) {
+                Printer::blank_line();
+                println!(
+                    "{} Matched process names against /{}/ (case-insensitive)",
+                    "ℹ".blue().bold(),
+                    pattern.cyan()
+                );
+            }
+        }
+    }
+
+    fn print_confirmation_prompt(
+        &self,
+        processes: &[Process],
+        range_ports: &HashMap<u32, Vec<u16>>,
+    ) {
+        use colored::*;
+
+        Printer::blank_line();
         println!(
-            "\n{} Found {} process{} to kill:\n",
+            "{} Found {} process{} to kill:",
             "⚠".yellow().bold(),
             processes.len().to_string().cyan().bold(),
             if processes.len() == 1 { "" } else { "es" }
         );
+        Printer::blank_line();
 
         for proc in processes {
             println!(
@@ -143,7 +488,136 @@ impl KillCommand {
                 proc.cpu_percent,
                 proc.memory_mb
             );
+            if let Some(ports) = range_ports.get(&proc.pid) {
+                let ports: Vec<String> = ports.iter().map(|p| format!(":{}", p)).collect();
+                println!(
+                    "      {} {}",
+                    "ports:".bright_black(),
+                    ports.join(", ").cyan()
+                );
+            }
+        }
+        Printer::blank_line();
+    }
+}
+
+/// Step through processes, splitting them into those to kill and those skipped
+fn confirm_each(processes: Vec<Process>) -> Result<(Vec<Process>, Vec<Process>)> {
+    let mut approved = Vec::new();
+    let mut skipped = Vec::new();
+    let mut remaining = processes.into_iter();
+
+    while let Some(proc) = remaining.next() {
+        match picker::confirm_step(&proc, "Kill")? {
+            picker::StepChoice::Yes => approved.push(proc),
+            picker::StepChoice::No => skipped.push(proc),
+            picker::StepChoice::Quit => {
+                skipped.push(proc);
+                skipped.extend(remaining);
+                break;
+            }
         }
-        println!();
+    }
+    Ok((approved, skipped))
+}
+
+/// Processes a kill resolved to, before any confirmation
+struct KillPlan {
+    targets: Vec<String>,
+    processes: Vec<Process>,
+    /// PIDs matched directly, before `--tree` added their descendants
+    matched: HashSet<u32>,
+    not_found: Vec<String>,
+    /// Ancestors of proc left out because `--force-dangerous` was not given
+    protected: Vec<Process>,
+    pid_file: Option<PidFile>,
+    opts: KillOptions,
+}
+
+/// Whether a pidfile's process is known to be gone after a kill
+///
+/// A delivered signal is not proof of exit (the process may ignore or handle
+/// it), so a merely signaled process must actually disappear.
+fn pidfile_process_gone(report: &KillReport) -> bool {
+    if !report.failed.is_empty() {
+        return false;
+    }
+    let confirmed = [&report.confirmed, &report.escalated, &report.defunct]
+        .iter()
+        .any(|procs| !procs.is_empty());
+    confirmed
+        || (!report.killed.is_empty()
+            && report
+                .killed
+                .iter()
+                .all(|p| p.wait_for_exit(PIDFILE_EXIT_CHECK)))
+}
+
+/// PIDs of every ancestor of the running proc process
+pub(crate) fn own_ancestors() -> HashSet<u32> {
+    let snapshot = Process::find_all().unwrap_or_default();
+    snapshot
+        .iter()
+        .find(|p| p.pid == std::process::id())
+        .map(|own| own.ancestors(&snapshot).iter().map(|p| p.pid).collect())
+        .unwrap_or_default()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_pidfile_kept_while_process_survives_signal() {
+        let mut child = std::process::Command::new("sleep")
+            .arg("30")
+            .spawn()
+            .unwrap();
+        let proc = Process::find_by_pid(child.id()).unwrap().unwrap();
+
+        // Signaled (say with an ignored USR1) but still running
+        let report = KillReport {
+            killed: vec![proc.clone()],
+            ..KillReport::default()
+        };
+        assert!(!pidfile_process_gone(&report));
+
+        child.kill().unwrap();
+        child.wait().unwrap();
+        assert!(pidfile_process_gone(&report));
+
+        let confirmed = KillReport {
+            confirmed: vec![proc],
+            ..KillReport::default()
+        };
+        assert!(pidfile_process_gone(&confirmed));
+    }
+
+    #[test]
+    fn test_dry_run_reports_matches_without_signaling() {
+        use clap::Parser;
+
+        #[derive(Parser)]
+        struct Cli {
+            #[command(flatten)]
+            kill: KillCommand,
+        }
+
+        let mut child = std::process::Command::new("sleep")
+            .arg("30")
+            .spawn()
+            .unwrap();
+        let pid = child.id().to_string();
+        let cmd = Cli::parse_from(["proc", pid.as_str(), "--dry-run"]).kill;
+
+        let report = cmd.run().unwrap();
+        let alive = child.try_wait().unwrap().is_none();
+        child.kill().unwrap();
+        child.wait().unwrap();
+
+        assert!(alive);
+        assert_eq!(report.would_kill.len(), 1);
+        assert_eq!(report.would_kill[0].pid, child.id());
+        assert!(report.killed.is_empty());
     }
 }
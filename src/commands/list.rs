@@ -6,15 +6,29 @@
 //!   proc list --in             # Processes in current directory
 //!   proc list --in /project    # Processes in /project
 //!   proc list --min-cpu 10     # Processes using >10% CPU
+//!   proc list node -x daemon   # Exclude processes matching 'daemon'
+//!   proc list --columns pid,user,cmd  # Choose table columns
+//!   proc list --format plain   # Tab-separated output for scripts
+//!   proc list node --tree      # Matches shown within their process tree
+//!   proc list 'python*' --glob # Names starting with 'python'
+//!   proc list node -I          # Check off matches and kill them
+//!   proc list --since 20m      # Processes started in the last 20 minutes
+//!   proc list --delta -s mem   # Who grew the most over two seconds
+//!   proc list --io -s io       # Disk read/write rates, busiest first
+//!   proc list --system         # Cores, overall CPU, and memory above the table
 
-use crate::core::{Process, ProcessStatus};
-use crate::error::Result;
-use crate::ui::{OutputFormat, Printer};
-use clap::Args;
-use std::path::PathBuf;
+use crate::api::{self, KillOptions};
+use crate::commands::kill::own_ancestors;
+use crate::commands::{process_sort, resolve_dir, warn_if_missing_dir, ThresholdArgs};
+use crate::core::{container_runtime, Filter, Process, StatusFilter, SystemSummary};
+use crate::error::{ProcError, Result};
+use crate::ui::{parse_duration, picker, Column, OutputFormat, Printer};
+use clap::{ArgGroup, Args};
+use std::time::Duration;
 
 /// List processes
 #[derive(Args, Debug)]
+#[command(group(ArgGroup::new("sampled").args(["delta", "io"])))]
 pub struct ListCommand {
     /// Process name or pattern to filter by
     pub name: Option<String>,
@@ -35,149 +49,199 @@ pub struct ListCommand {
     #[arg(long)]
     pub min_mem: Option<f64>,
 
-    /// Filter by status: running, sleeping, stopped, zombie
-    #[arg(long)]
-    pub status: Option<String>,
+    /// Only show processes in this state
+    #[arg(long, value_enum, ignore_case = true)]
+    pub status: Option<StatusFilter>,
 
     /// Output as JSON
     #[arg(long, short = 'j')]
     pub json: bool,
 
+    /// Output format: human, json, or plain (tab-separated, no color or header)
+    #[arg(long, value_enum, default_value_t = OutputFormat::Human)]
+    pub format: OutputFormat,
+
+    /// Leave out the table's column header and separator line
+    #[arg(long)]
+    pub no_header: bool,
+
     /// Show verbose output with command line, cwd, and parent PID
     #[arg(long, short = 'v')]
     pub verbose: bool,
 
+    /// Print only the number of matching processes
+    #[arg(long)]
+    pub count: bool,
+
+    /// Exit non-zero unless exactly this many processes match
+    #[arg(long)]
+    pub expect: Option<usize>,
+
+    /// Exit non-zero unless at least this many processes match
+    #[arg(long)]
+    pub expect_min: Option<usize>,
+
+    /// Exit non-zero unless at most this many processes match
+    #[arg(long)]
+    pub expect_max: Option<usize>,
+
     /// Limit the number of results
     #[arg(long, short = 'n')]
     pub limit: Option<usize>,
 
-    /// Sort by: cpu, mem, pid, name
-    #[arg(long, short = 's', default_value = "cpu")]
+    /// Exclude processes whose name or command contains this pattern (repeatable)
+    #[arg(long, short = 'x')]
+    pub exclude: Vec<String>,
+
+    /// Sort by: cpu, cputime, mem, vmem, io, nice, pid, name (add :asc or :desc to flip, e.g. cpu:asc)
+    #[arg(long, short = 's', default_value = "cpu", value_parser = process_sort)]
     pub sort: String,
+
+    /// Table columns, in order: pid, path, name, args, cpu, mem, vmem, swap, status, user, ppid, cmd
+    #[arg(long, short = 'c')]
+    pub columns: Option<String>,
+
+    /// Show matches as a tree, with their ancestors dimmed for context
+    #[arg(long, short = 't')]
+    pub tree: bool,
+
+    /// Only show processes started within this long (e.g. 20m, 1h30m)
+    #[arg(long, value_name = "DURATION", value_parser = parse_duration)]
+    pub since: Option<u64>,
+
+    /// Only show processes running for at least this long (e.g. 2h, 3d)
+    #[arg(long, value_name = "DURATION", value_parser = parse_duration)]
+    pub older_than: Option<u64>,
+
+    /// Only show direct children of this PID
+    #[arg(long, value_name = "PID")]
+    pub parent: Option<u32>,
+
+    /// Only show descendants of this PID (children, grandchildren, ...)
+    #[arg(long, value_name = "PID", conflicts_with = "parent")]
+    pub parent_recursive: Option<u32>,
+
+    /// Pick processes from the results in a checklist and kill them (requires a terminal)
+    #[arg(long, short = 'I', conflicts_with_all = ["json", "count", "tree"])]
+    pub interactive: bool,
+
+    /// Match the name as a shell-style glob against the whole process name (e.g. 'python*')
+    #[arg(long)]
+    pub glob: bool,
+
+    /// Exit non-zero when no processes match
+    #[arg(long, conflicts_with_all = ["count", "expect", "expect_min", "expect_max"])]
+    pub fail_empty: bool,
+
+    /// Sample twice and show how CPU and memory changed (sort cpu or mem by size of change)
+    #[arg(long, conflicts_with_all = ["tree", "interactive", "count", "expect", "expect_min", "expect_max"])]
+    pub delta: bool,
+
+    /// Sample disk reads and writes and show them as per-second rates
+    #[arg(long, conflicts_with_all = ["delta", "interactive", "count", "expect", "expect_min", "expect_max"])]
+    pub io: bool,
+
+    /// With --delta or --io, time between the two samples (e.g. 5s, 1m; default 2s, or 1s for --io)
+    #[arg(long, value_name = "DURATION", value_parser = parse_duration, requires = "sampled")]
+    pub interval: Option<u64>,
+
+    /// Show total cores, overall CPU load, and memory use above the table
+    /// (adds a `system` object to JSON)
+    #[arg(long, conflicts_with_all = ["delta", "interactive", "count"])]
+    pub system: bool,
+
+    /// Levels at which CPU and memory cells turn yellow, then red
+    #[command(flatten)]
+    pub thresholds: ThresholdArgs,
 }
 
 impl ListCommand {
     /// Executes the list command, displaying processes matching the filters.
     pub fn execute(&self) -> Result<()> {
+        self.thresholds.apply()?;
         let format = if self.json {
             OutputFormat::Json
         } else {
-            OutputFormat::Human
-        };
-        let printer = Printer::new(format, self.verbose);
-
-        // Get base process list
-        let mut processes = if let Some(ref name) = self.name {
-            Process::find_by_name(name)?
-        } else {
-            Process::find_all()?
+            self.format
         };
+        let mut printer = Printer::new(format, self.verbose).with_header(!self.no_header);
+        if let Some(ref columns) = self.columns {
+            printer = printer.with_columns(Column::parse_list(columns)?);
+        }
 
         // Resolve --in filter path
-        let in_dir_filter: Option<PathBuf> = self.in_dir.as_ref().map(|p| {
-            if p == "." {
-                std::env::current_dir().unwrap_or_else(|_| PathBuf::from("."))
-            } else {
-                let path = PathBuf::from(p);
-                if path.is_relative() {
-                    std::env::current_dir()
-                        .unwrap_or_else(|_| PathBuf::from("."))
-                        .join(path)
-                } else {
-                    path
-                }
-            }
-        });
+        let in_dir_filter = self.in_dir.as_deref().map(|p| resolve_dir(Some(p)));
+        if let Some(ref dir) = in_dir_filter {
+            warn_if_missing_dir(&printer, dir);
+        }
 
         // Resolve path filter
-        let path_filter: Option<PathBuf> = self.path.as_ref().map(|p| {
-            let path = PathBuf::from(p);
-            if path.is_relative() {
-                std::env::current_dir()
-                    .unwrap_or_else(|_| PathBuf::from("."))
-                    .join(path)
-            } else {
-                path
-            }
-        });
-
-        // Apply filters
-        processes.retain(|p| {
-            // Directory filter (--in)
-            if let Some(ref dir_path) = in_dir_filter {
-                if let Some(ref proc_cwd) = p.cwd {
-                    let proc_path = PathBuf::from(proc_cwd);
-                    if !proc_path.starts_with(dir_path) {
-                        return false;
-                    }
-                } else {
-                    return false;
-                }
-            }
+        let path_filter = self.path.as_deref().map(|p| resolve_dir(Some(p)));
 
-            // Path filter (executable path)
-            if let Some(ref exe_path) = path_filter {
-                if let Some(ref proc_exe) = p.exe_path {
-                    let proc_path = PathBuf::from(proc_exe);
-                    if !proc_path.starts_with(exe_path) {
-                        return false;
-                    }
-                } else {
-                    return false;
-                }
-            }
+        let filter = Filter {
+            name: self.name.clone(),
+            glob: self.glob,
+            in_dir: in_dir_filter.clone(),
+            exe_path: path_filter,
+            min_cpu: self.min_cpu,
+            min_mem: self.min_mem,
+            status: self.status,
+            exclude: self.exclude.clone(),
+            max_age_secs: self.since,
+            min_age_secs: self.older_than,
+            parent: self.parent.or(self.parent_recursive),
+            parent_recursive: self.parent_recursive.is_some(),
+            sort: Some(self.sort.clone()),
+            limit: self.limit,
+            ..Filter::default()
+        };
 
-            // CPU filter
-            if let Some(min_cpu) = self.min_cpu {
-                if p.cpu_percent < min_cpu {
-                    return false;
-                }
+        if self.count || self.has_expectation() {
+            // Count the whole filtered set, ignoring --limit
+            let count = api::list(&Filter {
+                limit: None,
+                ..filter
+            })?
+            .len();
+            if self.count {
+                printer.print_count(count)?;
+                return Ok(());
             }
+            return printer.check_count(count, self.expect, self.expect_min, self.expect_max);
+        }
 
-            // Memory filter
-            if let Some(min_mem) = self.min_mem {
-                if p.memory_mb < min_mem {
-                    return false;
-                }
-            }
+        if self.interactive {
+            return self.kill_interactively(&printer, &filter);
+        }
 
-            // Status filter
-            if let Some(ref status) = self.status {
-                let status_match = match status.to_lowercase().as_str() {
-                    "running" => matches!(p.status, ProcessStatus::Running),
-                    "sleeping" | "sleep" => matches!(p.status, ProcessStatus::Sleeping),
-                    "stopped" | "stop" => matches!(p.status, ProcessStatus::Stopped),
-                    "zombie" => matches!(p.status, ProcessStatus::Zombie),
-                    _ => true,
-                };
-                if !status_match {
-                    return false;
-                }
+        if self.delta {
+            let interval = self.interval.unwrap_or(2);
+            let deltas = api::list_delta(&filter, Duration::from_secs(interval))?;
+            if self.fail_empty && deltas.is_empty() {
+                return Err(ProcError::ProcessNotFound(
+                    self.name.clone().unwrap_or_else(|| "*".to_string()),
+                ));
             }
-
-            true
-        });
-
-        // Sort processes
-        match self.sort.to_lowercase().as_str() {
-            "cpu" => processes.sort_by(|a, b| {
-                b.cpu_percent
-                    .partial_cmp(&a.cpu_percent)
-                    .unwrap_or(std::cmp::Ordering::Equal)
-            }),
-            "mem" | "memory" => processes.sort_by(|a, b| {
-                b.memory_mb
-                    .partial_cmp(&a.memory_mb)
-                    .unwrap_or(std::cmp::Ordering::Equal)
-            }),
-            "pid" => processes.sort_by_key(|p| p.pid),
-            "name" => processes.sort_by(|a, b| a.name.to_lowercase().cmp(&b.name.to_lowercase())),
-            _ => {} // Keep default order
+            printer.print_process_deltas(&deltas, interval)?;
+            return Ok(());
         }
 
-        // Apply limit if specified
-        if let Some(limit) = self.limit {
-            processes.truncate(limit);
+        let (processes, hidden) = if self.io {
+            if self.columns.is_none() && format != OutputFormat::Json {
+                printer = printer.with_columns(Column::IO_DEFAULT.to_vec());
+            }
+            let interval = self.interval.unwrap_or(1);
+            api::list_io_counting_hidden(&filter, Duration::from_secs(interval))?
+        } else {
+            api::list_counting_hidden(&filter)?
+        };
+        if self.system {
+            printer = printer.with_system(SystemSummary::sample());
+        }
+        if self.fail_empty && processes.is_empty() {
+            return Err(ProcError::ProcessNotFound(
+                self.name.clone().unwrap_or_else(|| "*".to_string()),
+            ));
         }
 
         // Build context string for output (e.g., "in /path/to/dir")
@@ -185,7 +249,71 @@ impl ListCommand {
             .as_ref()
             .map(|p| format!("in {}", p.display()));
 
-        printer.print_processes_with_context(&processes, context.as_deref());
+        if self.tree {
+            let all_processes = Process::find_all()?;
+            printer.print_processes_as_tree(&processes, &all_processes, context.as_deref())?;
+        } else {
+            printer.print_processes_with_context(&processes, context.as_deref())?;
+        }
+        printer.print_hidden_by_permissions(hidden);
+        printer.print_container_scope(container_runtime());
         Ok(())
     }
+
+    /// Let the user check off matches in a picker, then kill the selection
+    fn kill_interactively(&self, printer: &Printer, filter: &Filter) -> Result<()> {
+        if !picker::is_interactive() {
+            return Err(ProcError::InvalidInput(
+                "--interactive needs a terminal on stdin and stdout".to_string(),
+            ));
+        }
+
+        // Never offer proc itself or the shell that launched it
+        let ancestors = own_ancestors();
+        let mut processes = api::list(filter)?;
+        processes.retain(|p| p.pid != std::process::id() && !ancestors.contains(&p.pid));
+        if processes.is_empty() {
+            printer.warning("No processes to choose from");
+            return Ok(());
+        }
+
+        let selected = picker::pick_many(&processes, "Select processes to kill")?;
+        if selected.is_empty() {
+            printer.warning("Nothing selected");
+            return Ok(());
+        }
+
+        let report = api::kill_processes(selected, &KillOptions::default());
+        for proc in &report.reused {
+            printer.warning(&format!("PID {} reused, skipping", proc.pid));
+        }
+        let failed: Vec<(Process, String)> = report
+            .failed
+            .into_iter()
+            .map(|(proc, e)| (proc, e.to_string()))
+            .collect();
+
+        printer.print_kill_result(
+            &report.killed,
+            &report.confirmed,
+            &report.escalated,
+            &report.defunct,
+            &[],
+            &failed,
+        )?;
+
+        if failed.is_empty() {
+            Ok(())
+        } else {
+            Err(ProcError::SignalError(format!(
+                "Failed to kill {} process(es)",
+                failed.len()
+            )))
+        }
+    }
+
+    /// Whether any of --expect, --expect-min, or --expect-max was given
+    fn has_expectation(&self) -> bool {
+        self.expect.is_some() || self.expect_min.is_some() || self.expect_max.is_some()
+    }
 }
@@ -6,25 +6,219 @@
 //! - Format and display results
 
 pub mod by;
+pub mod export;
 pub mod find_in;
+pub mod hogs;
 pub mod info;
 pub mod kill;
 pub mod list;
+pub mod monitor;
 pub mod on;
+pub mod orphans;
 pub mod ports;
+pub mod signals;
 pub mod stop;
 pub mod stuck;
 pub mod tree;
 pub mod unstick;
 
 pub use by::ByCommand;
+pub use export::ExportCommand;
 pub use find_in::InCommand;
+pub use hogs::{HogsCommand, Resource};
 pub use info::InfoCommand;
 pub use kill::KillCommand;
 pub use list::ListCommand;
+pub use monitor::MonitorCommand;
 pub use on::OnCommand;
+pub use orphans::OrphansCommand;
 pub use ports::PortsCommand;
+pub use signals::SignalsCommand;
 pub use stop::StopCommand;
 pub use stuck::StuckCommand;
 pub use tree::TreeCommand;
-pub use unstick::UnstickCommand;
+pub use unstick::{UnstickCommand, UnstickOutcome, UnstickReport};
+
+use crate::core::{parse_targets, PortSortKey, SortKey};
+use crate::error::{ProcError, Result};
+use crate::ui::{parse_wait, set_thresholds, Printer, Thresholds};
+use clap::Args;
+use std::io::Read;
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+
+/// Levels at which process table cells turn yellow, then red
+#[derive(Args, Debug, Default)]
+pub struct ThresholdArgs {
+    /// CPU % at which process table cells turn yellow (default 50)
+    #[arg(long, value_name = "PCT")]
+    pub cpu_warn: Option<f32>,
+
+    /// CPU % at which process table cells turn red (default 80)
+    #[arg(long, value_name = "PCT")]
+    pub cpu_crit: Option<f32>,
+
+    /// Memory (MB) at which process table cells turn yellow (default 1024)
+    #[arg(long, value_name = "MB")]
+    pub mem_warn: Option<f64>,
+
+    /// Memory (MB) at which process table cells turn red (default 4096)
+    #[arg(long, value_name = "MB")]
+    pub mem_crit: Option<f64>,
+}
+
+impl ThresholdArgs {
+    /// Use these levels for the process table for the rest of the run
+    pub fn apply(&self) -> Result<()> {
+        set_thresholds(Thresholds::from_levels(
+            self.cpu_warn,
+            self.cpu_crit,
+            self.mem_warn,
+            self.mem_crit,
+        )?);
+        Ok(())
+    }
+}
+
+/// Split comma-separated targets, reading them from stdin when the argument is `-`
+///
+/// Stdin may separate targets with newlines, commas, or both. Repeated
+/// targets are dropped so each is resolved once, and empty input is an error.
+pub(crate) fn read_targets(arg: &str) -> Result<Vec<String>> {
+    if arg.trim() != "-" {
+        return Ok(parse_targets(arg));
+    }
+    let mut input = String::new();
+    std::io::stdin().read_to_string(&mut input)?;
+    let targets = split_target_lines(&input);
+    if targets.is_empty() {
+        return Err(ProcError::InvalidInput("no targets on stdin".to_string()));
+    }
+    Ok(targets)
+}
+
+fn split_target_lines(input: &str) -> Vec<String> {
+    let mut seen = std::collections::HashSet::new();
+    input
+        .lines()
+        .flat_map(parse_targets)
+        .filter(|target| seen.insert(target.clone()))
+        .collect()
+}
+
+/// Resolve a directory argument against the current directory
+///
+/// `None` and `.` mean the current directory, a leading `~` expands to
+/// `$HOME`, and relative paths are joined onto the current directory.
+pub(crate) fn resolve_dir(path: Option<&str>) -> PathBuf {
+    let cwd = || std::env::current_dir().unwrap_or_else(|_| PathBuf::from("."));
+    let path = match path {
+        None | Some(".") => return cwd(),
+        Some(p) => p,
+    };
+
+    let home = std::env::var("HOME").ok();
+    let expanded = match (path.strip_prefix('~'), home) {
+        (Some(""), Some(home)) => PathBuf::from(home),
+        (Some(rest), Some(home)) if rest.starts_with('/') => {
+            PathBuf::from(home).join(rest.trim_start_matches('/'))
+        }
+        _ => PathBuf::from(path),
+    };
+
+    if expanded.is_relative() {
+        cwd().join(expanded)
+    } else {
+        expanded
+    }
+}
+
+/// Warn when a directory filter names a path that does not exist
+///
+/// Not an error: a process can still report a working directory that has
+/// since been deleted. But a typo otherwise looks like "no processes here".
+pub(crate) fn warn_if_missing_dir(printer: &Printer, dir: &Path) {
+    if !dir.exists() {
+        printer.warning(&format!(
+            "Directory {} does not exist; results may be empty",
+            dir.display()
+        ));
+    }
+}
+
+/// Value parser for `--poll-interval`: a short wait that must not be zero
+pub(crate) fn poll_interval(input: &str) -> std::result::Result<Duration, String> {
+    match parse_wait(input)? {
+        interval if interval.is_zero() => Err("poll interval must be greater than 0".to_string()),
+        interval => Ok(interval),
+    }
+}
+
+/// Value parser for process `--sort` specs: a [`SortKey`] with an optional
+/// `:asc`/`:desc` suffix
+pub(crate) fn process_sort(spec: &str) -> std::result::Result<String, String> {
+    let names: Vec<&str> = SortKey::ALL.iter().map(|k| k.name()).collect();
+    check_sort_spec(spec, |key| SortKey::parse(key).is_some(), &names)
+}
+
+/// Value parser for port `--sort` specs: a [`PortSortKey`] with an optional
+/// `:asc`/`:desc` suffix
+pub(crate) fn port_sort(spec: &str) -> std::result::Result<String, String> {
+    let names: Vec<&str> = PortSortKey::ALL.iter().map(|k| k.name()).collect();
+    check_sort_spec(spec, |key| PortSortKey::parse(key).is_some(), &names)
+}
+
+/// Reject a sort spec whose key is not `known` or whose direction is not asc/desc
+pub(crate) fn check_sort_spec(
+    spec: &str,
+    known: impl Fn(&str) -> bool,
+    names: &[&str],
+) -> std::result::Result<String, String> {
+    let (key, direction) = match spec.rsplit_once(':') {
+        Some((key, direction)) => (key, Some(direction)),
+        None => (spec, None),
+    };
+    if let Some(direction) = direction {
+        if !direction.eq_ignore_ascii_case("asc") && !direction.eq_ignore_ascii_case("desc") {
+            return Err(format!(
+                "unknown sort direction '{}' (expected asc or desc)",
+                direction
+            ));
+        }
+    }
+    if !known(key) {
+        return Err(format!(
+            "unknown sort key '{}' (expected one of: {})",
+            key,
+            names.join(", ")
+        ));
+    }
+    Ok(spec.to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_split_target_lines() {
+        assert_eq!(
+            split_target_lines("1234\n:3000, node\n\n1234\n"),
+            vec!["1234", ":3000", "node"]
+        );
+        assert!(split_target_lines("").is_empty());
+    }
+
+    #[test]
+    fn test_sort_spec_validation() {
+        assert_eq!(process_sort("cpu").unwrap(), "cpu");
+        assert_eq!(process_sort("Memory:ASC").unwrap(), "Memory:ASC");
+        assert!(port_sort("port:desc").is_ok());
+
+        let err = process_sort("cpuu").unwrap_err();
+        assert!(err.contains("'cpuu'"));
+        assert!(err.contains("cpu, cputime, mem, vmem, io, nice, pid, name"));
+        assert!(process_sort("cpu:up").unwrap_err().contains("asc or desc"));
+        assert!(port_sort("cpu").is_err());
+    }
+}
@@ -0,0 +1,124 @@
+//! `proc monitor` - Watch processes start and exit
+//!
+//! Takes a snapshot every interval and reports the difference from the last
+//! one. Processes are paired by PID and start time, so a recycled PID shows up
+//! as one process exiting and another starting.
+//!
+//! Examples:
+//!   proc monitor                          # Print starts and exits as they happen
+//!   proc monitor --events                 # NDJSON, one event per line
+//!   proc monitor --events --filter node   # Only node processes
+//!   proc monitor --interval 5s            # Compare snapshots every five seconds
+//!   proc monitor --events --samples 10    # Stop after ten intervals
+//!   proc monitor --output events.ndjson   # Text on the terminal, NDJSON in the file
+
+use crate::core::{
+    lifecycle_events, split_threads, Filter, LifecycleEvent, LifecycleKind, Process,
+};
+use crate::error::{ProcError, Result};
+use crate::ui::{has_json_output, parse_duration, stream_json_output, OutputFormat, Printer};
+use clap::Args;
+use colored::*;
+use std::io::Write;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+/// Watch processes start and exit
+#[derive(Args, Debug)]
+pub struct MonitorCommand {
+    /// Emit newline-delimited JSON events instead of text
+    #[arg(long)]
+    pub events: bool,
+
+    /// Only watch processes whose name or command contains this pattern
+    #[arg(long, short = 'f')]
+    pub filter: Option<String>,
+
+    /// Match --filter as a shell-style glob against the whole process name
+    #[arg(long, requires = "filter")]
+    pub glob: bool,
+
+    /// Exclude processes whose name or command contains this pattern (repeatable)
+    #[arg(long, short = 'x')]
+    pub exclude: Vec<String>,
+
+    /// Time between snapshots (e.g. 5s, 1m; default 1s)
+    #[arg(long, value_name = "DURATION", value_parser = parse_duration)]
+    pub interval: Option<u64>,
+
+    /// Stop after this many intervals instead of running until interrupted
+    #[arg(long, value_name = "N")]
+    pub samples: Option<u64>,
+}
+
+impl MonitorCommand {
+    /// Executes the monitor command, reporting events until interrupted.
+    pub fn execute(&self) -> Result<()> {
+        let interval = self.interval.unwrap_or(1);
+        if interval == 0 {
+            return Err(ProcError::InvalidInput(
+                "--interval must be at least 1s".to_string(),
+            ));
+        }
+        let printer = Printer::new(OutputFormat::Json, false);
+        stream_json_output();
+        let to_file = has_json_output();
+        let filter = Filter {
+            name: self.filter.clone(),
+            glob: self.glob,
+            exclude: self.exclude.clone(),
+            ..Filter::default()
+        };
+        let snapshot =
+            || -> Result<Vec<Process>> { Ok(filter.apply(split_threads(Process::find_all()?).0)) };
+
+        let mut previous = snapshot()?;
+        let mut taken = 0;
+        while self.samples.is_none_or(|limit| taken < limit) {
+            std::thread::sleep(Duration::from_secs(interval));
+            taken += 1;
+
+            let current = snapshot()?;
+            let ts = SystemTime::now()
+                .duration_since(UNIX_EPOCH)
+                .map(|d| d.as_secs())
+                .unwrap_or(0);
+            for event in lifecycle_events(previous, current.clone(), ts) {
+                // With --output, events go to the file and text stays on the terminal
+                if to_file {
+                    printer.print_json_line(&event)?;
+                }
+                let written = if !self.events {
+                    print_event(&event)
+                } else if !to_file {
+                    printer.print_json_line(&event)
+                } else {
+                    Ok(())
+                };
+                // The reader went away (e.g. `| head`); nothing left to do
+                if written.is_err() {
+                    return Ok(());
+                }
+            }
+            previous = current;
+        }
+
+        Ok(())
+    }
+}
+
+/// Print one event as a line of text
+fn print_event(event: &LifecycleEvent) -> std::io::Result<()> {
+    let label = match event.event {
+        LifecycleKind::Started => "+ started".green(),
+        LifecycleKind::Exited => "- exited ".red(),
+    };
+    let mut out = std::io::stdout().lock();
+    writeln!(
+        out,
+        "{} {} {}",
+        label,
+        event.name.white().bold(),
+        format!("[PID {}]", event.pid).cyan()
+    )?;
+    out.flush()
+}
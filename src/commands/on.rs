@@ -3,27 +3,36 @@
 //! Usage:
 //!   proc on :3000              # What process is on port 3000?
 //!   proc on :3000,:8080        # What's on multiple ports?
+//!   proc on localhost:3000     # host:port and pasted URLs work too (local sockets only)
 //!   proc on 1234               # What ports is PID 1234 listening on?
+//!   proc on 3000               # No PID 3000? Falls back to port 3000 if something listens there
 //!   proc on node               # What ports are node processes listening on?
 //!   proc on node --in .        # Node processes in cwd and their ports
+//!   proc on chrome -s ports -n 5 # Top 5 chrome processes by port count
+//!   proc on chrome --table     # One table of every (process, port) pair
 
+use crate::commands::{check_sort_spec, read_targets, resolve_dir, warn_if_missing_dir};
+use crate::core::filter::{canonical_dir, path_within};
+use crate::core::process::split_sort_spec;
 use crate::core::{
-    find_ports_for_pid, parse_target, parse_targets, resolve_target, PortInfo, Process, TargetType,
+    find_ports_for_pid, find_ports_in_range, is_local_host, parse_target, resolve_exe,
+    resolve_name, sort_by_process, split_host_port, PortInfo, Process, SortKey, TargetType,
 };
 use crate::error::{ProcError, Result};
+use crate::ui::{format_duration, OutputFormat, Printer};
 use clap::Args;
 use colored::*;
 use serde::Serialize;
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 
 /// Show what's on a port, or what ports a process is on
 #[derive(Args, Debug)]
 pub struct OnCommand {
-    /// Target(s): :port, PID, or process name (comma-separated for multiple)
+    /// Target(s): :port, PID, or process name (comma-separated for multiple, or - to read from stdin)
     pub target: String,
 
-    /// Filter by directory (for name targets)
-    #[arg(long = "in", short = 'i')]
+    /// Filter by directory for name targets (defaults to current directory if no path given)
+    #[arg(long = "in", short = 'i', num_args = 0..=1, default_missing_value = ".")]
     pub in_dir: Option<String>,
 
     /// Output as JSON
@@ -33,91 +42,227 @@ pub struct OnCommand {
     /// Show verbose output (full command line)
     #[arg(long, short = 'v')]
     pub verbose: bool,
+
+    /// Limit the number of processes shown for name targets
+    #[arg(long, short = 'n')]
+    pub limit: Option<usize>,
+
+    /// Sort name matches by: cpu, mem, pid, name, ports (add :asc or :desc to flip)
+    #[arg(long, short = 's', value_parser = on_sort)]
+    pub sort: Option<String>,
+
+    /// Show name matches as a single table of (process, port) rows
+    #[arg(long, short = 't')]
+    pub table: bool,
+}
+
+/// Result of looking up a single target
+enum Lookup {
+    /// A port and the process listening on it
+    Port(PortInfo, Option<Process>),
+    /// A process and the ports it listens on
+    Pid(Process, Vec<PortInfo>),
+    /// Processes matching a name or port range and the ports each listens on
+    Name(Vec<(Process, Vec<PortInfo>)>),
 }
 
 impl OnCommand {
     /// Executes the on command, performing bidirectional port/process lookup.
     pub fn execute(&self) -> Result<()> {
-        let targets = parse_targets(&self.target);
+        let targets = read_targets(&self.target)?;
+        if let Some(ref dir) = self.resolve_in_dir() {
+            warn_if_missing_dir(&self.printer(), dir);
+        }
 
         // For single target, use original behavior
         if targets.len() == 1 {
-            return match parse_target(&targets[0]) {
-                TargetType::Port(port) => self.show_process_on_port(port),
-                TargetType::Pid(pid) => self.show_ports_for_pid(pid),
-                TargetType::Name(name) => self.show_ports_for_name(&name),
-            };
+            let lookup = self.lookup(&targets[0])?;
+            return self.show_single(&targets[0], &lookup);
         }
 
-        // Multi-target handling
+        // Multi-target handling: gather everything, then print once
+        let mut found: Vec<(String, Lookup)> = Vec::new();
         let mut not_found = Vec::new();
 
         for target in &targets {
-            match parse_target(target) {
-                TargetType::Port(port) => {
-                    if let Err(e) = self.show_process_on_port(port) {
-                        if !self.json {
-                            println!("{} Port {}: {}", "⚠".yellow(), port, e);
-                        }
-                        not_found.push(target.clone());
-                    }
-                }
-                TargetType::Pid(pid) => {
-                    if let Err(e) = self.show_ports_for_pid(pid) {
-                        if !self.json {
-                            println!("{} PID {}: {}", "⚠".yellow(), pid, e);
-                        }
-                        not_found.push(target.clone());
-                    }
-                }
-                TargetType::Name(ref name) => {
-                    if let Err(e) = self.show_ports_for_name(name) {
-                        if !self.json {
-                            println!("{} '{}': {}", "⚠".yellow(), name, e);
-                        }
-                        not_found.push(target.clone());
+            match self.lookup(target) {
+                Ok(lookup) => found.push((target.clone(), lookup)),
+                Err(e) => {
+                    if !self.json {
+                        println!("{} '{}': {}", "⚠".yellow(), target, e);
                     }
+                    not_found.push(target.clone());
                 }
             }
         }
 
+        if self.printer().wants_json() {
+            let results: Vec<TargetResultJson> = found
+                .iter()
+                .map(|(target, lookup)| TargetResultJson::new(target, lookup))
+                .collect();
+            let output = MultiTargetOutput {
+                action: "on",
+                success: !found.is_empty(),
+                found_count: found.len(),
+                not_found_count: not_found.len(),
+                results,
+                not_found: &not_found,
+            };
+            self.printer().print_json(&output)?;
+        }
+        if !self.json {
+            for (_, lookup) in &found {
+                self.print_lookup(lookup);
+            }
+
+            if !not_found.is_empty() {
+                println!(
+                    "{} No match for {} of {} target{}: {}",
+                    "⚠".yellow().bold(),
+                    not_found.len(),
+                    targets.len(),
+                    if targets.len() == 1 { "" } else { "s" },
+                    not_found.join(", ").yellow()
+                );
+            }
+        }
+
+        if found.is_empty() {
+            return Err(ProcError::ProcessNotFound(targets.join(",")));
+        }
+
         Ok(())
     }
 
-    /// Resolve --in filter path
-    fn resolve_in_dir(&self) -> Option<PathBuf> {
-        self.in_dir.as_ref().map(|p| {
-            if p == "." {
-                std::env::current_dir().unwrap_or_else(|_| PathBuf::from("."))
-            } else {
-                let path = PathBuf::from(p);
-                if path.is_relative() {
-                    std::env::current_dir()
-                        .unwrap_or_else(|_| PathBuf::from("."))
-                        .join(path)
-                } else {
-                    path
-                }
+    /// Printer for JSON results
+    fn printer(&self) -> Printer {
+        let format = if self.json {
+            OutputFormat::Json
+        } else {
+            OutputFormat::Human
+        };
+        Printer::new(format, self.verbose)
+    }
+
+    /// Look up a single target without printing anything
+    fn lookup(&self, target: &str) -> Result<Lookup> {
+        match parse_target(target) {
+            TargetType::Port(port) => {
+                self.warn_if_remote(target, port);
+                self.lookup_port(port)
             }
-        })
+            TargetType::PortRange(start, end) => self.lookup_port_range(start, end),
+            // An explicit pid: prefix never falls back to a port
+            TargetType::Pid(pid) => self.lookup_pid(pid, !target.starts_with("pid:")),
+            TargetType::Name(name) => self.lookup_name(&name, resolve_name(&name)?),
+            TargetType::ExePath(path) => self.lookup_name(target, resolve_exe(&path)?),
+        }
     }
 
-    /// Check if process matches --in filter
-    fn matches_in_filter(&self, proc: &Process) -> bool {
-        if let Some(ref dir_path) = self.resolve_in_dir() {
-            if let Some(ref proc_cwd) = proc.cwd {
-                let proc_path = PathBuf::from(proc_cwd);
-                proc_path.starts_with(dir_path)
-            } else {
-                false
+    /// Warn that a `host:port` target naming another machine is looked up locally
+    fn warn_if_remote(&self, target: &str, port: u16) {
+        let Some((host, _)) = split_host_port(target) else {
+            return;
+        };
+        if !self.json && !is_local_host(host) {
+            println!(
+                "{} {} is not this machine; only local sockets can be inspected, so showing local port {}",
+                "⚠".yellow(),
+                host.yellow(),
+                port
+            );
+        }
+    }
+
+    /// Print a single-target result
+    fn show_single(&self, target: &str, lookup: &Lookup) -> Result<()> {
+        if !self.json {
+            self.print_lookup(lookup);
+        }
+        if !self.printer().wants_json() {
+            return Ok(());
+        }
+
+        match lookup {
+            Lookup::Name(results) => {
+                let output = NameLookupOutput {
+                    action: "on",
+                    query_type: "name",
+                    success: !results.is_empty(),
+                    count: results.len(),
+                    summary: GroupSummary::of(results),
+                    results: results
+                        .iter()
+                        .map(|(proc, ports)| ProcessPortsJson {
+                            process: proc,
+                            ports,
+                        })
+                        .collect(),
+                };
+                self.printer().print_json(&output)?;
+            }
+            _ => {
+                let result = TargetResultJson::new(target, lookup);
+                let output = PortLookupOutput {
+                    action: "on",
+                    query_type: result.query_type,
+                    success: true,
+                    port: result.port,
+                    protocol: result.protocol,
+                    address: result.address,
+                    process: result.process,
+                    ports: result.ports,
+                };
+                self.printer().print_json(&output)?;
+            }
+        }
+
+        Ok(())
+    }
+
+    fn print_lookup(&self, lookup: &Lookup) {
+        match lookup {
+            Lookup::Port(port_info, process) => {
+                self.print_process_on_port(port_info, process.as_ref())
+            }
+            Lookup::Pid(proc, ports) => self.print_ports_for_process(proc, ports),
+            Lookup::Name(results) => {
+                if self.table {
+                    self.print_ports_table(results);
+                } else {
+                    for (proc, ports) in results {
+                        self.print_ports_for_process(proc, ports);
+                    }
+                }
+                if let Some(summary) = GroupSummary::of(results) {
+                    summary.print();
+                }
             }
-        } else {
-            true
         }
     }
 
-    /// Show what process is on a specific port
-    fn show_process_on_port(&self, port: u16) -> Result<()> {
+    /// Resolve --in filter path
+    fn resolve_in_dir(&self) -> Option<PathBuf> {
+        self.in_dir.as_deref().map(|p| resolve_dir(Some(p)))
+    }
+
+    /// Resolve --in through symlinks, once per lookup, for [`OnCommand::matches_in_filter`]
+    fn canonical_in_dir(&self) -> Option<PathBuf> {
+        self.resolve_in_dir().as_deref().map(canonical_dir)
+    }
+
+    /// Check if process matches the --in filter from [`OnCommand::canonical_in_dir`]
+    fn matches_in_filter(proc: &Process, in_dir: Option<&Path>) -> bool {
+        in_dir.is_none_or(|dir_path| {
+            proc.cwd
+                .as_ref()
+                .is_some_and(|cwd| path_within(Path::new(cwd), dir_path))
+        })
+    }
+
+    /// Find what process is on a specific port
+    fn lookup_port(&self, port: u16) -> Result<Lookup> {
         let port_info = match PortInfo::find_by_port(port)? {
             Some(info) => info,
             None => return Err(ProcError::PortNotFound(port)),
@@ -127,7 +272,7 @@ impl OnCommand {
 
         // Apply --in filter if present
         if let Some(ref proc) = process {
-            if !self.matches_in_filter(proc) {
+            if !Self::matches_in_filter(proc, self.canonical_in_dir().as_deref()) {
                 return Err(ProcError::ProcessNotFound(format!(
                     "port {} (process not in specified directory)",
                     port
@@ -135,32 +280,43 @@ impl OnCommand {
             }
         }
 
-        if self.json {
-            let output = PortLookupOutput {
-                action: "on",
-                query_type: "port_to_process",
-                success: true,
-                port: Some(port_info.port),
-                protocol: Some(format!("{:?}", port_info.protocol).to_lowercase()),
-                address: port_info.address.clone(),
-                process: process.as_ref(),
-                ports: None,
-            };
-            println!("{}", serde_json::to_string_pretty(&output)?);
-        } else {
-            self.print_process_on_port(&port_info, process.as_ref());
+        Ok(Lookup::Port(port_info, process))
+    }
+
+    /// Find every process listening in a port range, with its ports in that range
+    fn lookup_port_range(&self, start: u16, end: u16) -> Result<Lookup> {
+        let ports = find_ports_in_range(start, end)?;
+        let pids: Vec<u32> = ports.iter().map(|p| p.pid).collect();
+        let processes = Process::find_by_pids(&pids)?;
+
+        let in_dir = self.canonical_in_dir();
+        let mut results: Vec<(Process, Vec<PortInfo>)> = Vec::new();
+        for port in ports {
+            if let Some(entry) = results.iter_mut().find(|(p, _)| p.pid == port.pid) {
+                entry.1.push(port);
+            } else if let Some(proc) = processes.iter().find(|p| p.pid == port.pid) {
+                if Self::matches_in_filter(proc, in_dir.as_deref()) {
+                    results.push((proc.clone(), vec![port]));
+                }
+            }
         }
 
-        Ok(())
+        if results.is_empty() {
+            return Err(ProcError::ProcessNotFound(format!(":{}-{}", start, end)));
+        }
+        Ok(Lookup::Name(results))
     }
 
-    /// Show what ports a PID is listening on
-    fn show_ports_for_pid(&self, pid: u32) -> Result<()> {
-        let process = Process::find_by_pid(pid)?
-            .ok_or_else(|| ProcError::ProcessNotFound(pid.to_string()))?;
+    /// Find what ports a PID is listening on
+    fn lookup_pid(&self, pid: u32, port_fallback: bool) -> Result<Lookup> {
+        let process = match Process::find_by_pid(pid)? {
+            Some(process) => process,
+            None if port_fallback => return self.lookup_bare_port(pid),
+            None => return Err(ProcError::ProcessNotFound(pid.to_string())),
+        };
 
         // Apply --in filter if present
-        if !self.matches_in_filter(&process) {
+        if !Self::matches_in_filter(&process, self.canonical_in_dir().as_deref()) {
             return Err(ProcError::ProcessNotFound(format!(
                 "PID {} (not in specified directory)",
                 pid
@@ -168,37 +324,35 @@ impl OnCommand {
         }
 
         let ports = find_ports_for_pid(pid)?;
-
-        if self.json {
-            let output = PortLookupOutput {
-                action: "on",
-                query_type: "process_to_ports",
-                success: true,
-                port: None,
-                protocol: None,
-                address: None,
-                process: Some(&process),
-                ports: Some(&ports),
-            };
-            println!("{}", serde_json::to_string_pretty(&output)?);
-        } else {
-            self.print_ports_for_process(&process, &ports);
-        }
-
-        Ok(())
+        Ok(Lookup::Pid(process, ports))
     }
 
-    /// Show what ports processes with a given name are listening on
-    fn show_ports_for_name(&self, name: &str) -> Result<()> {
-        let mut processes = resolve_target(name)?;
+    /// Treat a bare number that isn't a running PID as a port, if something
+    /// listens there (`proc on 3000` meaning `proc on :3000`)
+    fn lookup_bare_port(&self, number: u32) -> Result<Lookup> {
+        let not_found = || ProcError::ProcessNotFound(number.to_string());
+        let port = u16::try_from(number).map_err(|_| not_found())?;
+        if PortInfo::find_by_port(port)?.is_none() {
+            return Err(not_found());
+        }
 
-        if processes.is_empty() {
-            return Err(ProcError::ProcessNotFound(name.to_string()));
+        if !self.json {
+            println!(
+                "{} No PID {}; interpreting {} as :{}",
+                "ℹ".blue().bold(),
+                number,
+                number,
+                port
+            );
         }
+        self.lookup_port(port)
+    }
 
+    /// Find what ports the processes resolved from a name or exe target are listening on
+    fn lookup_name(&self, name: &str, mut processes: Vec<Process>) -> Result<Lookup> {
         // Apply --in filter if present
-        if self.in_dir.is_some() {
-            processes.retain(|p| self.matches_in_filter(p));
+        if let Some(in_dir) = self.canonical_in_dir() {
+            processes.retain(|p| Self::matches_in_filter(p, Some(&in_dir)));
             if processes.is_empty() {
                 return Err(ProcError::ProcessNotFound(format!(
                     "'{}' (no matches in specified directory)",
@@ -207,29 +361,35 @@ impl OnCommand {
             }
         }
 
-        let mut all_results: Vec<(Process, Vec<PortInfo>)> = Vec::new();
-
-        for proc in processes {
-            let ports = find_ports_for_pid(proc.pid)?;
-            all_results.push((proc, ports));
+        // Enumerate listening ports once and group them by process
+        let all_ports = PortInfo::get_all_listening()?;
+        let mut all_results: Vec<(Process, Vec<PortInfo>)> = processes
+            .into_iter()
+            .map(|proc| {
+                let ports = all_ports
+                    .iter()
+                    .filter(|p| p.pid == proc.pid)
+                    .cloned()
+                    .collect();
+                (proc, ports)
+            })
+            .collect();
+
+        if let Some(ref spec) = self.sort {
+            match split_sort_spec(spec) {
+                ("ports", Some(false)) => all_results.sort_by_key(|(_, ports)| ports.len()),
+                ("ports", _) => {
+                    all_results.sort_by_key(|(_, ports)| std::cmp::Reverse(ports.len()))
+                }
+                _ => sort_by_process(&mut all_results, spec, |(proc, _)| proc),
+            }
         }
 
-        if self.json {
-            let output: Vec<_> = all_results
-                .iter()
-                .map(|(proc, ports)| ProcessPortsJson {
-                    process: proc,
-                    ports,
-                })
-                .collect();
-            println!("{}", serde_json::to_string_pretty(&output)?);
-        } else {
-            for (proc, ports) in &all_results {
-                self.print_ports_for_process(proc, ports);
-            }
+        if let Some(limit) = self.limit {
+            all_results.truncate(limit);
         }
 
-        Ok(())
+        Ok(Lookup::Name(all_results))
     }
 
     fn print_process_on_port(&self, port_info: &PortInfo, process: Option<&Process>) {
@@ -238,7 +398,7 @@ impl OnCommand {
             "✓".green().bold(),
             port_info.port.to_string().cyan().bold()
         );
-        println!();
+        Printer::blank_line();
 
         println!(
             "  {} {} (PID {})",
@@ -269,11 +429,7 @@ impl OnCommand {
                 proc.memory_mb
             );
 
-            if let Some(start_time) = proc.start_time {
-                let uptime = std::time::SystemTime::now()
-                    .duration_since(std::time::UNIX_EPOCH)
-                    .map(|d| d.as_secs().saturating_sub(start_time))
-                    .unwrap_or(0);
+            if let Some(uptime) = proc.uptime_secs {
                 println!("  {} {}", "Uptime:".bright_black(), format_duration(uptime));
             }
 
@@ -284,7 +440,74 @@ impl OnCommand {
             }
         }
 
-        println!();
+        Printer::blank_line();
+    }
+
+    /// Print every (process, port) pair of a name lookup as one table
+    ///
+    /// Rows are ordered by port unless `--sort` chose a process order.
+    fn print_ports_table(&self, results: &[(Process, Vec<PortInfo>)]) {
+        let mut rows: Vec<(&Process, &PortInfo)> = results
+            .iter()
+            .flat_map(|(proc, ports)| ports.iter().map(move |port| (proc, port)))
+            .collect();
+        if self.sort.is_none() {
+            rows.sort_by_key(|(proc, port)| (port.port, proc.pid));
+        }
+
+        let idle = results.iter().filter(|(_, ports)| ports.is_empty()).count();
+
+        if rows.is_empty() {
+            println!(
+                "{} {} matching process{}, none listening on a port",
+                "ℹ".blue(),
+                results.len(),
+                if results.len() == 1 { "" } else { "es" }
+            );
+            return;
+        }
+
+        println!(
+            "{} Found {} port{} across {} process{}",
+            "✓".green().bold(),
+            rows.len().to_string().cyan().bold(),
+            if rows.len() == 1 { "" } else { "s" },
+            (results.len() - idle).to_string().cyan().bold(),
+            if results.len() - idle == 1 { "" } else { "es" }
+        );
+        Printer::blank_line();
+
+        println!(
+            "{:<8} {:<20} {:<8} {:<6} {}",
+            "PID".bright_blue().bold(),
+            "NAME".bright_blue().bold(),
+            "PORT".bright_blue().bold(),
+            "PROTO".bright_blue().bold(),
+            "ADDRESS".bright_blue().bold()
+        );
+        println!("{}", "─".repeat(60).bright_black());
+
+        for (proc, port) in rows {
+            println!(
+                "{:<8} {:<20} {:<8} {:<6} {}",
+                proc.pid.to_string().cyan(),
+                proc.name.white(),
+                port.port.to_string().cyan().bold(),
+                format!("{:?}", port.protocol).to_uppercase(),
+                port.address.as_deref().unwrap_or("*").bright_black()
+            );
+        }
+
+        if idle > 0 {
+            println!();
+            println!(
+                "  {} {} more matching process{} not listening on any port",
+                "ℹ".blue(),
+                idle,
+                if idle == 1 { "" } else { "es" }
+            );
+        }
+        Printer::blank_line();
     }
 
     fn print_ports_for_process(&self, process: &Process, ports: &[PortInfo]) {
@@ -294,7 +517,7 @@ impl OnCommand {
             process.name.white().bold(),
             process.pid.to_string().cyan().bold()
         );
-        println!();
+        Printer::blank_line();
 
         if ports.is_empty() {
             println!("  {} No listening ports", "ℹ".blue());
@@ -321,19 +544,7 @@ impl OnCommand {
             }
         }
 
-        println!();
-    }
-}
-
-fn format_duration(secs: u64) -> String {
-    if secs < 60 {
-        format!("{}s", secs)
-    } else if secs < 3600 {
-        format!("{}m {}s", secs / 60, secs % 60)
-    } else if secs < 86400 {
-        format!("{}h {}m", secs / 3600, (secs % 3600) / 60)
-    } else {
-        format!("{}d {}h", secs / 86400, (secs % 86400) / 3600)
+        Printer::blank_line();
     }
 }
 
@@ -354,8 +565,171 @@ struct PortLookupOutput<'a> {
     ports: Option<&'a [PortInfo]>,
 }
 
+/// Value parser for `--sort`: any process key, or `ports`
+fn on_sort(spec: &str) -> std::result::Result<String, String> {
+    let mut names: Vec<&str> = SortKey::ALL.iter().map(|k| k.name()).collect();
+    names.push("ports");
+    check_sort_spec(
+        spec,
+        |key| key == "ports" || SortKey::parse(key).is_some(),
+        &names,
+    )
+}
+
+#[derive(Serialize)]
+struct NameLookupOutput<'a> {
+    action: &'static str,
+    query_type: &'static str,
+    success: bool,
+    count: usize,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    summary: Option<GroupSummary>,
+    results: Vec<ProcessPortsJson<'a>>,
+}
+
+/// Combined footprint of the processes a name matched
+#[derive(Serialize)]
+struct GroupSummary {
+    process_count: usize,
+    total_ports: usize,
+    total_cpu: f32,
+    total_mem: f64,
+}
+
+impl GroupSummary {
+    /// Totals for a name lookup; `None` for a single match, where they
+    /// would only repeat its own figures
+    fn of(results: &[(Process, Vec<PortInfo>)]) -> Option<Self> {
+        (results.len() > 1).then(|| Self {
+            process_count: results.len(),
+            total_ports: results.iter().map(|(_, ports)| ports.len()).sum(),
+            total_cpu: results.iter().map(|(proc, _)| proc.cpu_percent).sum(),
+            total_mem: results.iter().map(|(proc, _)| proc.memory_mb).sum(),
+        })
+    }
+
+    fn print(&self) {
+        println!(
+            "{} {} processes: {} port{}, {:.1}% CPU, {:.1} MB",
+            "Σ".bright_black().bold(),
+            self.process_count.to_string().cyan().bold(),
+            self.total_ports.to_string().cyan(),
+            if self.total_ports == 1 { "" } else { "s" },
+            self.total_cpu,
+            self.total_mem
+        );
+        Printer::blank_line();
+    }
+}
+
 #[derive(Serialize)]
 struct ProcessPortsJson<'a> {
     process: &'a Process,
     ports: &'a [PortInfo],
 }
+
+#[derive(Serialize)]
+struct MultiTargetOutput<'a> {
+    action: &'static str,
+    success: bool,
+    found_count: usize,
+    not_found_count: usize,
+    results: Vec<TargetResultJson<'a>>,
+    not_found: &'a [String],
+}
+
+#[derive(Serialize)]
+struct TargetResultJson<'a> {
+    target: &'a str,
+    query_type: &'static str,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    port: Option<u16>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    protocol: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    address: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    process: Option<&'a Process>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    ports: Option<&'a [PortInfo]>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    processes: Option<Vec<ProcessPortsJson<'a>>>,
+}
+
+impl<'a> TargetResultJson<'a> {
+    fn new(target: &'a str, lookup: &'a Lookup) -> Self {
+        let mut result = TargetResultJson {
+            target,
+            query_type: "process_to_ports",
+            port: None,
+            protocol: None,
+            address: None,
+            process: None,
+            ports: None,
+            processes: None,
+        };
+
+        match lookup {
+            Lookup::Port(port_info, process) => {
+                result.query_type = "port_to_process";
+                result.port = Some(port_info.port);
+                result.protocol = Some(format!("{:?}", port_info.protocol).to_lowercase());
+                result.address = port_info.address.clone();
+                result.process = process.as_ref();
+            }
+            Lookup::Pid(process, ports) => {
+                result.process = Some(process);
+                result.ports = Some(ports);
+            }
+            Lookup::Name(results) => {
+                result.query_type = "name_to_processes";
+                result.processes = Some(
+                    results
+                        .iter()
+                        .map(|(proc, ports)| ProcessPortsJson {
+                            process: proc,
+                            ports,
+                        })
+                        .collect(),
+                );
+            }
+        }
+
+        result
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use clap::Parser;
+
+    #[derive(Parser)]
+    struct Cli {
+        #[command(flatten)]
+        on: OnCommand,
+    }
+
+    #[test]
+    fn test_name_prefix_is_looked_up_as_name() {
+        let mut child = std::process::Command::new("sleep")
+            .arg("30")
+            .spawn()
+            .unwrap();
+        let target = format!("name:{}", child.id());
+        let cmd = Cli::parse_from(["proc", target.as_str()]).on;
+
+        let lookup = cmd.lookup(&target);
+        child.kill().unwrap();
+        child.wait().unwrap();
+
+        // The digits are a process name, so the sleep is never matched by PID
+        match lookup {
+            Ok(Lookup::Name(results)) => {
+                assert!(results.iter().all(|(p, _)| p.pid != child.id()))
+            }
+            Ok(_) => panic!("name:{} was looked up as a PID or port", child.id()),
+            Err(e) => assert!(matches!(e, ProcError::ProcessNotFound(_))),
+        }
+    }
+}
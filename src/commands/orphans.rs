@@ -0,0 +1,110 @@
+//! `proc orphans` - Find processes reparented to init
+//!
+//! Examples:
+//!   proc orphans              # Orphans, excluding known daemons
+//!   proc orphans --in .       # Orphans started from the current project
+//!   proc orphans --all        # Every child of PID 1, daemons included
+//!   proc orphans -x postgres  # Also skip anything matching 'postgres'
+
+use crate::commands::{process_sort, resolve_dir, warn_if_missing_dir, ThresholdArgs};
+use crate::core::{Filter, Process};
+use crate::error::Result;
+use crate::ui::{OutputFormat, Printer};
+use clap::Args;
+
+/// Process names that are expected to run directly under init
+const KNOWN_DAEMONS: &[&str] = &[
+    "systemd",
+    "launchd",
+    "init",
+    "sshd",
+    "cron",
+    "crond",
+    "dbus-daemon",
+    "rsyslogd",
+    "syslogd",
+    "udevd",
+    "containerd",
+    "dockerd",
+    "agetty",
+    "login",
+    "polkitd",
+    "networkmanager",
+    "wininit",
+    "services",
+];
+
+/// Find orphaned processes (reparented to init)
+#[derive(Args, Debug)]
+pub struct OrphansCommand {
+    /// Only show orphans whose working directory is under this path
+    #[arg(long = "in", short = 'i', num_args = 0..=1, default_missing_value = ".")]
+    pub in_dir: Option<String>,
+
+    /// Include known daemons and processes running from /
+    #[arg(long, short = 'a')]
+    pub all: bool,
+
+    /// Exclude processes whose name or command contains this pattern (repeatable)
+    #[arg(long, short = 'x')]
+    pub exclude: Vec<String>,
+
+    /// Output as JSON
+    #[arg(long, short = 'j')]
+    pub json: bool,
+
+    /// Show verbose output with command line, cwd, and parent PID
+    #[arg(long, short = 'v')]
+    pub verbose: bool,
+
+    /// Sort by: cpu, cputime, mem, vmem, io, nice, pid, name (add :asc or :desc to flip, e.g. cpu:asc)
+    #[arg(long, short = 's', default_value = "pid", value_parser = process_sort)]
+    pub sort: String,
+
+    /// Levels at which CPU and memory cells turn yellow, then red
+    #[command(flatten)]
+    pub thresholds: ThresholdArgs,
+}
+
+impl OrphansCommand {
+    /// Executes the orphans command, listing processes whose parent is init.
+    pub fn execute(&self) -> Result<()> {
+        self.thresholds.apply()?;
+        let format = if self.json {
+            OutputFormat::Json
+        } else {
+            OutputFormat::Human
+        };
+        let printer = Printer::new(format, self.verbose);
+
+        let in_dir_filter = self.in_dir.as_deref().map(|p| resolve_dir(Some(p)));
+        if let Some(ref dir) = in_dir_filter {
+            warn_if_missing_dir(&printer, dir);
+        }
+
+        let filter = Filter {
+            in_dir: in_dir_filter,
+            exclude: self.exclude.clone(),
+            sort: Some(self.sort.clone()),
+            ..Filter::default()
+        };
+
+        let orphans: Vec<Process> = Process::find_all()?
+            .into_iter()
+            .filter(|p| p.is_orphan() && (self.all || !Self::looks_like_daemon(p)))
+            .collect();
+        let orphans = filter.apply(orphans);
+
+        printer.print_processes_with_context(&orphans, Some("reparented to init"))?;
+        Ok(())
+    }
+
+    /// Heuristic: known daemon names, or a working directory of `/`
+    ///
+    /// Daemons conventionally chdir to the root, while leaked workers keep the
+    /// directory of the project that spawned them.
+    fn looks_like_daemon(proc: &Process) -> bool {
+        let name = proc.name.to_lowercase();
+        KNOWN_DAEMONS.contains(&name.as_str()) || proc.cwd.as_deref() == Some("/")
+    }
+}
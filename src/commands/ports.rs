@@ -3,13 +3,26 @@
 //! Examples:
 //!   proc ports              # Show all listening ports
 //!   proc ports --filter node # Filter by process name
+//!   proc ports --pid 1234,5678 -v  # Only ports held by these PIDs
 //!   proc ports --exposed    # Only network-accessible ports (0.0.0.0)
 //!   proc ports --local      # Only localhost ports (127.0.0.1)
-//!   proc ports -v           # Show with executable paths
+//!   proc ports --tcp        # Only TCP listeners
+//!   proc ports -v           # Show with executable paths, connection counts, and age
+//!   proc ports --min-conns 10 # Only listeners with at least 10 established connections
+//!   proc ports --free :3000-3100  # Print the lowest port in the range nothing listens on
+//!   proc ports --free :3000-3100 --count 3  # The first three free ports
+//!   proc ports --listen-and-conn  # Per process: what it listens on and how many peers it has
+//!   proc ports --conflicts :3000,:8080  # Fail (exit 7) if anything holds a reserved port
 
-use crate::core::{PortInfo, Process};
-use crate::error::Result;
-use crate::ui::{OutputFormat, Printer};
+use crate::api;
+use crate::commands::port_sort;
+use crate::core::{
+    parse_port, parse_port_range, PortCheck, PortFilter, PortInfo, Process, ProcessSockets,
+    Protocol,
+};
+use crate::error::{ProcError, Result};
+use crate::ui::output::truncate_string;
+use crate::ui::{format_duration, OutputFormat, Printer};
 use clap::Args;
 use colored::*;
 use serde::Serialize;
@@ -22,6 +35,10 @@ pub struct PortsCommand {
     #[arg(long, short = 'f')]
     pub filter: Option<String>,
 
+    /// Only show ports held by this PID (comma-separated for several)
+    #[arg(long, value_delimiter = ',', value_name = "PID")]
+    pub pid: Vec<u32>,
+
     /// Only show network-exposed ports (0.0.0.0, ::)
     #[arg(long, short = 'e')]
     pub exposed: bool,
@@ -30,86 +47,319 @@ pub struct PortsCommand {
     #[arg(long, short = 'l')]
     pub local: bool,
 
+    /// Only show TCP ports
+    #[arg(long, conflicts_with = "udp")]
+    pub tcp: bool,
+
+    /// Only show UDP ports (UDP listeners are not enumerated yet, so this is currently empty)
+    #[arg(long)]
+    pub udp: bool,
+
     /// Output as JSON
     #[arg(long, short = 'j')]
     pub json: bool,
 
-    /// Show verbose output (includes executable path)
+    /// Show verbose output (includes executable path and connection count)
     #[arg(long, short = 'v')]
     pub verbose: bool,
 
-    /// Sort by: port, pid, name
-    #[arg(long, short = 's', default_value = "port")]
+    /// Leave out the table's column header and separator line
+    #[arg(long)]
+    pub no_header: bool,
+
+    /// Only show listeners with at least this many established connections
+    #[arg(long)]
+    pub min_conns: Option<usize>,
+
+    /// Sort by: port, pid, name (add :asc or :desc to flip, e.g. port:desc)
+    #[arg(long, short = 's', default_value = "port", value_parser = port_sort)]
     pub sort: String,
+
+    /// Print the lowest port in this range that nothing listens on (e.g. :3000-3100)
+    #[arg(long, value_name = "RANGE")]
+    pub free: Option<String>,
+
+    /// With --free, print this many free ports instead of one
+    #[arg(long, value_name = "N", requires = "free", default_value_t = 1)]
+    pub count: usize,
+
+    /// Summarize each process's listening ports and established connections
+    /// (-v lists every connection)
+    #[arg(long, conflicts_with_all = ["free", "min_conns"])]
+    pub listen_and_conn: bool,
+
+    /// Check that these ports are free (e.g. :3000,:8080), listing any owners
+    /// and exiting with code 7 if one is taken
+    #[arg(long, value_name = "PORTS", conflicts_with_all = ["free", "min_conns", "listen_and_conn"])]
+    pub conflicts: Option<String>,
 }
 
 impl PortsCommand {
     /// Executes the ports command, listing all listening network ports.
     pub fn execute(&self) -> Result<()> {
-        let mut ports = PortInfo::get_all_listening()?;
-
-        // Filter by process name if specified
-        if let Some(ref filter) = self.filter {
-            let filter_lower = filter.to_lowercase();
-            ports.retain(|p| p.process_name.to_lowercase().contains(&filter_lower));
-        }
-
-        // Filter by address exposure
-        if self.exposed {
-            ports.retain(|p| {
-                p.address
-                    .as_ref()
-                    .map(|a| a == "0.0.0.0" || a == "::" || a == "*")
-                    .unwrap_or(true)
-            });
-        }
-
-        if self.local {
-            ports.retain(|p| {
-                p.address
-                    .as_ref()
-                    .map(|a| a == "127.0.0.1" || a == "::1" || a.starts_with("[::1]"))
-                    .unwrap_or(false)
-            });
-        }
-
-        // Sort ports
-        match self.sort.to_lowercase().as_str() {
-            "port" => ports.sort_by_key(|p| p.port),
-            "pid" => ports.sort_by_key(|p| p.pid),
-            "name" => ports.sort_by(|a, b| {
-                a.process_name
-                    .to_lowercase()
-                    .cmp(&b.process_name.to_lowercase())
-            }),
-            _ => ports.sort_by_key(|p| p.port),
-        }
-
-        // In verbose mode, fetch process info for paths
-        let process_map: HashMap<u32, Process> = if self.verbose {
-            let mut map = HashMap::new();
-            for port in &ports {
-                if let std::collections::hash_map::Entry::Vacant(e) = map.entry(port.pid) {
-                    if let Ok(Some(proc)) = Process::find_by_pid(port.pid) {
-                        e.insert(proc);
-                    }
-                }
-            }
-            map
+        if let Some(ref range) = self.free {
+            return self.print_free(range);
+        }
+        if let Some(ref ports) = self.conflicts {
+            return self.check_conflicts(ports);
+        }
+
+        let filter = PortFilter {
+            name: self.filter.clone(),
+            pids: self.pid.clone(),
+            exposed: self.exposed,
+            local: self.local,
+            protocol: if self.tcp {
+                Some(Protocol::Tcp)
+            } else if self.udp {
+                Some(Protocol::Udp)
+            } else {
+                None
+            },
+            sort: Some(self.sort.clone()),
+        };
+
+        if self.listen_and_conn {
+            return self.print_sockets(&api::sockets(&filter)?);
+        }
+
+        let ports = api::ports(&filter)?;
+
+        // Established connection counts, when shown or filtered on
+        let conns: Option<HashMap<u16, usize>> = if self.verbose || self.min_conns.is_some() {
+            Some(PortInfo::established_counts()?)
+        } else {
+            None
+        };
+
+        let ports: Vec<PortInfo> = match (self.min_conns, &conns) {
+            (Some(min), Some(counts)) => ports
+                .into_iter()
+                .filter(|p| counts.get(&p.port).copied().unwrap_or(0) >= min)
+                .collect(),
+            _ => ports,
+        };
+
+        let format = if self.json {
+            OutputFormat::Json
+        } else {
+            OutputFormat::Human
+        };
+        let printer = Printer::new(format, self.verbose).with_header(!self.no_header);
+
+        // Owning processes, for paths and ages in verbose and JSON output
+        let process_map: HashMap<u32, Process> = if self.verbose || printer.wants_json() {
+            let pids: Vec<u32> = ports.iter().map(|p| p.pid).collect();
+            Process::find_by_pids(&pids)
+                .unwrap_or_default()
+                .into_iter()
+                .map(|proc| (proc.pid, proc))
+                .collect()
         } else {
             HashMap::new()
         };
 
-        if self.json {
-            self.print_json(&ports, &process_map);
+        if printer.wants_json() {
+            self.print_json(&printer, &ports, &process_map, conns.as_ref())?;
+        }
+        if !self.json {
+            self.print_human(&printer, &ports, &process_map, conns.as_ref());
+        }
+
+        Ok(())
+    }
+
+    /// Print free ports in a range, one per line, for use in `$(...)`
+    fn print_free(&self, range: &str) -> Result<()> {
+        let (start, end) = parse_port_range(range)?;
+        let free = PortInfo::find_free(start, end, self.count)?;
+        if free.is_empty() {
+            return Err(ProcError::CheckFailed(format!(
+                "every port in {}-{} is in use",
+                start, end
+            )));
+        }
+
+        let format = if self.json {
+            OutputFormat::Json
         } else {
-            self.print_human(&ports, &process_map);
+            OutputFormat::Human
+        };
+        let printer = Printer::new(format, false);
+        if printer.wants_json() {
+            #[derive(Serialize)]
+            struct Output<'a> {
+                action: &'static str,
+                success: bool,
+                count: usize,
+                ports: &'a [u16],
+            }
+            printer.print_json(&Output {
+                action: "free-ports",
+                success: true,
+                count: free.len(),
+                ports: &free,
+            })?;
+        }
+        if !self.json {
+            for port in &free {
+                println!("{}", port);
+            }
         }
+        Ok(())
+    }
 
+    /// Report which of a list of ports are taken, failing if any are
+    fn check_conflicts(&self, list: &str) -> Result<()> {
+        let ports = list
+            .split(',')
+            .map(str::trim)
+            .filter(|s| !s.is_empty())
+            .map(parse_port)
+            .collect::<Result<Vec<u16>>>()?;
+        if ports.is_empty() {
+            return Err(ProcError::InvalidInput(
+                "--conflicts needs at least one port".to_string(),
+            ));
+        }
+        let checks = PortInfo::check_ports(&ports)?;
+        let occupied = checks.iter().filter(|c| c.occupied).count();
+
+        let format = if self.json {
+            OutputFormat::Json
+        } else {
+            OutputFormat::Human
+        };
+        let printer = Printer::new(format, false);
+        if printer.wants_json() {
+            #[derive(Serialize)]
+            struct Output<'a> {
+                action: &'static str,
+                success: bool,
+                checked: usize,
+                occupied_count: usize,
+                ports: &'a [PortCheck],
+            }
+            printer.print_json(&Output {
+                action: "port-conflicts",
+                success: occupied == 0,
+                checked: checks.len(),
+                occupied_count: occupied,
+                ports: &checks,
+            })?;
+        }
+        if !self.json {
+            for check in &checks {
+                match check.owner {
+                    Some(ref owner) => println!(
+                        "{} :{} is in use by {} [PID {}]",
+                        "✗".red().bold(),
+                        check.port.to_string().cyan(),
+                        owner.name.white().bold(),
+                        owner.pid.to_string().cyan()
+                    ),
+                    None => println!("{} :{} is free", "✓".green().bold(), check.port),
+                }
+            }
+        }
+
+        if occupied > 0 {
+            return Err(ProcError::PortConflict(format!(
+                "{} of {} port{} in use",
+                occupied,
+                checks.len(),
+                if checks.len() == 1 { "" } else { "s" }
+            )));
+        }
+        Ok(())
+    }
+
+    /// Print each process's listeners and connections on one line
+    fn print_sockets(&self, sockets: &[ProcessSockets]) -> Result<()> {
+        let format = if self.json {
+            OutputFormat::Json
+        } else {
+            OutputFormat::Human
+        };
+        let printer = Printer::new(format, self.verbose);
+        if printer.wants_json() {
+            #[derive(Serialize)]
+            struct Output<'a> {
+                action: &'static str,
+                success: bool,
+                count: usize,
+                processes: &'a [ProcessSockets],
+            }
+            printer.print_json(&Output {
+                action: "sockets",
+                success: true,
+                count: sockets.len(),
+                processes: sockets,
+            })?;
+        }
+        if self.json {
+            return Ok(());
+        }
+
+        if sockets.is_empty() {
+            println!(
+                "{} No listening ports or connections found",
+                "⚠".yellow().bold()
+            );
+            return Ok(());
+        }
+
+        println!(
+            "{} Found {} process{} with sockets",
+            "✓".green().bold(),
+            sockets.len().to_string().cyan().bold(),
+            if sockets.len() == 1 { "" } else { "es" }
+        );
+        Printer::blank_line();
+
+        for entry in sockets {
+            let mut parts = Vec::new();
+            if !entry.listening.is_empty() {
+                let ports: Vec<String> = entry
+                    .listening
+                    .iter()
+                    .map(|p| format!(":{}", p.port))
+                    .collect();
+                parts.push(format!("listening {}", ports.join(",").cyan()));
+            }
+            if !entry.connections.is_empty() {
+                parts.push(format!("{} established", entry.connections.len()));
+            }
+            println!(
+                "{} ({}): {}",
+                entry.process_name.white().bold(),
+                entry.pid.to_string().cyan(),
+                parts.join("; ")
+            );
+
+            if self.verbose {
+                for conn in &entry.connections {
+                    println!(
+                        "  {} {} {} {}",
+                        "↳".bright_black(),
+                        conn.local.bright_black(),
+                        "→".bright_black(),
+                        conn.remote
+                    );
+                }
+            }
+        }
+        Printer::blank_line();
         Ok(())
     }
 
-    fn print_human(&self, ports: &[PortInfo], process_map: &HashMap<u32, Process>) {
+    fn print_human(
+        &self,
+        printer: &Printer,
+        ports: &[PortInfo],
+        process_map: &HashMap<u32, Process>,
+        conns: Option<&HashMap<u16, usize>>,
+    ) {
         if ports.is_empty() {
             println!("{} No listening ports found", "⚠".yellow().bold());
             return;
@@ -121,57 +371,91 @@ impl PortsCommand {
             ports.len().to_string().cyan().bold(),
             if ports.len() == 1 { "" } else { "s" }
         );
-        println!();
+        Printer::blank_line();
 
-        // Header
-        println!(
-            "{:<8} {:<10} {:<8} {:<20} {:<15}",
-            "PORT".bright_blue().bold(),
-            "PROTO".bright_blue().bold(),
-            "PID".bright_blue().bold(),
-            "PROCESS".bright_blue().bold(),
-            "ADDRESS".bright_blue().bold()
-        );
-        println!("{}", "─".repeat(65).bright_black());
+        // Header; verbose adds the owning process's age
+        if printer.shows_header() {
+            let age_header = if self.verbose {
+                format!("{:<9}", "AGE".bright_blue().bold())
+            } else {
+                String::new()
+            };
+            println!(
+                "{:<8} {:<10} {:<8} {:<20} {}{:<15}",
+                "PORT".bright_blue().bold(),
+                "PROTO".bright_blue().bold(),
+                "PID".bright_blue().bold(),
+                "PROCESS".bright_blue().bold(),
+                age_header,
+                "ADDRESS".bright_blue().bold()
+            );
+            let width = if self.verbose { 74 } else { 65 };
+            println!("{}", "─".repeat(width).bright_black());
+        }
 
         for port in ports {
             let addr = port.address.as_deref().unwrap_or("*");
             let proto = format!("{:?}", port.protocol).to_uppercase();
+            let age = if self.verbose {
+                let age = process_map
+                    .get(&port.pid)
+                    .and_then(|proc| proc.uptime_secs)
+                    .map(format_duration)
+                    .unwrap_or_else(|| "-".to_string());
+                format!("{:<9}", age.yellow())
+            } else {
+                String::new()
+            };
 
             println!(
-                "{:<8} {:<10} {:<8} {:<20} {:<15}",
+                "{:<8} {:<10} {:<8} {:<20} {}{:<15}",
                 port.port.to_string().cyan().bold(),
                 proto.white(),
                 port.pid.to_string().cyan(),
                 truncate_string(&port.process_name, 19).white(),
+                age,
                 addr.bright_black()
             );
 
-            // In verbose mode, show path
+            // In verbose mode, show path and connection count
             if self.verbose {
-                if let Some(proc) = process_map.get(&port.pid) {
-                    if let Some(ref path) = proc.exe_path {
-                        println!(
-                            "         {} {}",
-                            "↳".bright_black(),
-                            truncate_string(path, 55).bright_black()
-                        );
-                    }
-                }
+                let path = process_map
+                    .get(&port.pid)
+                    .and_then(|proc| proc.exe_path.as_deref())
+                    .map(|path| truncate_string(path, 55));
+                let conns = conns.map(|c| c.get(&port.port).copied().unwrap_or(0));
+                let detail = match (path, conns) {
+                    (Some(path), Some(n)) => format!("{}  conns: {}", path, n),
+                    (Some(path), None) => path,
+                    (None, Some(n)) => format!("conns: {}", n),
+                    (None, None) => continue,
+                };
+                println!("         {} {}", "↳".bright_black(), detail.bright_black());
             }
         }
-        println!();
+        Printer::blank_line();
     }
 
-    fn print_json(&self, ports: &[PortInfo], process_map: &HashMap<u32, Process>) {
-        let printer = Printer::new(OutputFormat::Json, self.verbose);
-
+    fn print_json(
+        &self,
+        printer: &Printer,
+        ports: &[PortInfo],
+        process_map: &HashMap<u32, Process>,
+        conns: Option<&HashMap<u16, usize>>,
+    ) -> Result<()> {
+        /// A listening port; `protocol` is always present as "tcp" or "udp"
         #[derive(Serialize)]
         struct PortWithProcess<'a> {
             #[serde(flatten)]
             port: &'a PortInfo,
             #[serde(skip_serializing_if = "Option::is_none")]
             exe_path: Option<&'a str>,
+            #[serde(skip_serializing_if = "Option::is_none")]
+            established: Option<usize>,
+            /// Seconds the owning process has been running, a stand-in for
+            /// how long the port has been held
+            #[serde(skip_serializing_if = "Option::is_none")]
+            age_secs: Option<u64>,
         }
 
         let enriched: Vec<PortWithProcess> = ports
@@ -181,6 +465,8 @@ impl PortsCommand {
                 exe_path: process_map
                     .get(&p.pid)
                     .and_then(|proc| proc.exe_path.as_deref()),
+                established: conns.map(|c| c.get(&p.port).copied().unwrap_or(0)),
+                age_secs: process_map.get(&p.pid).and_then(|proc| proc.uptime_secs),
             })
             .collect();
 
@@ -197,14 +483,6 @@ impl PortsCommand {
             success: true,
             count: ports.len(),
             ports: enriched,
-        });
-    }
-}
-
-fn truncate_string(s: &str, max_len: usize) -> String {
-    if s.len() <= max_len {
-        s.to_string()
-    } else {
-        format!("{}...", &s[..max_len.saturating_sub(3)])
+        })
     }
 }
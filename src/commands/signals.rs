@@ -0,0 +1,77 @@
+//! `proc signals` - List supported signals
+//!
+//! Examples:
+//!   proc signals            # Signal names and numbers for this platform
+//!   proc signals --json     # Same, as JSON
+
+use crate::core::supported_signals;
+use crate::error::Result;
+use crate::ui::{OutputFormat, Printer};
+use clap::Args;
+use colored::*;
+use serde::Serialize;
+
+/// List signals accepted by `kill --signal`
+#[derive(Args, Debug)]
+pub struct SignalsCommand {
+    /// Output as JSON
+    #[arg(long, short = 'j')]
+    pub json: bool,
+}
+
+impl SignalsCommand {
+    /// Executes the signals command, printing supported signal names and numbers.
+    pub fn execute(&self) -> Result<()> {
+        let signals = supported_signals();
+
+        let printer = Printer::new(
+            if self.json {
+                OutputFormat::Json
+            } else {
+                OutputFormat::Human
+            },
+            false,
+        );
+        if printer.wants_json() {
+            printer.print_json(&SignalsOutput {
+                action: "signals",
+                success: true,
+                signals: signals
+                    .iter()
+                    .map(|(name, number)| SignalJson {
+                        name,
+                        number: *number,
+                    })
+                    .collect(),
+            })?;
+        }
+        if self.json {
+            return Ok(());
+        }
+
+        println!(
+            "{:<6} {}",
+            "NUM".bright_blue().bold(),
+            "NAME".bright_blue().bold()
+        );
+        println!("{}", "─".repeat(20).bright_black());
+        for (name, number) in &signals {
+            println!("{:<6} {}", number.to_string().cyan(), name.white());
+        }
+
+        Ok(())
+    }
+}
+
+#[derive(Serialize)]
+struct SignalsOutput<'a> {
+    action: &'static str,
+    success: bool,
+    signals: Vec<SignalJson<'a>>,
+}
+
+#[derive(Serialize)]
+struct SignalJson<'a> {
+    name: &'a str,
+    number: i32,
+}
@@ -6,18 +6,23 @@
 //!   proc stop node              # Stop all node processes
 //!   proc stop :3000,:8080       # Stop multiple targets
 //!   proc stop :3000,1234,node   # Mixed targets (port + PID + name)
+//!   proc stop node --grace-period 500ms --poll-interval 20ms  # Fast local shutdown
+//!   proc stop node --parallel   # Stop every match at once under one timeout
 
-use crate::core::{parse_targets, resolve_targets, Process};
+use crate::api::{self, KillOptions, KillReport};
+use crate::commands::{poll_interval, read_targets};
+use crate::core::{resolve_targets, EscalationTiming, Process};
 use crate::error::{ProcError, Result};
-use crate::ui::{OutputFormat, Printer};
+use crate::ui::{format_wait, parse_wait, picker, OutputFormat, Printer};
 use clap::Args;
 use dialoguer::Confirm;
 use serde::Serialize;
+use std::time::Duration;
 
 /// Stop process(es) gracefully with SIGTERM
 #[derive(Args, Debug)]
 pub struct StopCommand {
-    /// Target(s): process name, PID, or :port (comma-separated for multiple)
+    /// Target(s): process name, PID, or :port (comma-separated for multiple, or - to read from stdin)
     #[arg(required = true)]
     target: String,
 
@@ -29,9 +34,26 @@ pub struct StopCommand {
     #[arg(long, short)]
     json: bool,
 
-    /// Timeout in seconds to wait before force kill
-    #[arg(long, short, default_value = "10")]
-    timeout: u64,
+    /// How long to wait before force kill (e.g. 500ms, 10s), for each target
+    /// in turn, or shared by all of them with --parallel
+    #[arg(
+        long,
+        short = 't',
+        visible_alias = "timeout",
+        value_name = "DURATION",
+        value_parser = parse_wait,
+        default_value = "10s"
+    )]
+    grace_period: Duration,
+
+    /// Longest gap between checks on whether the processes have exited (e.g. 50ms)
+    #[arg(long, value_name = "DURATION", value_parser = poll_interval)]
+    poll_interval: Option<Duration>,
+
+    /// Signal every target at once and wait for them together, so stopping
+    /// many takes about one timeout in total
+    #[arg(long)]
+    parallel: bool,
 }
 
 impl StopCommand {
@@ -45,8 +67,8 @@ impl StopCommand {
         let printer = Printer::new(format, false);
 
         // Parse comma-separated targets and resolve to processes
-        let targets = parse_targets(&self.target);
-        let (processes, not_found) = resolve_targets(&targets);
+        let targets = read_targets(&self.target)?;
+        let (mut processes, not_found) = resolve_targets(&targets);
 
         // Warn about targets that weren't found
         for target in &not_found {
@@ -54,11 +76,19 @@ impl StopCommand {
         }
 
         if processes.is_empty() {
-            return Err(ProcError::ProcessNotFound(self.target.clone()));
+            return Err(ProcError::ProcessNotFound(targets.join(",")));
         }
 
-        // Confirm if not --yes
-        if !self.yes && !self.json {
+        // Several matches on a terminal: let the user pick which to stop
+        if !self.yes && !self.json && processes.len() > 1 && picker::is_interactive() {
+            processes = picker::pick_many(&processes, "Select processes to stop")?;
+            if processes.is_empty() {
+                printer.warning("Aborted");
+                return Ok(());
+            }
+        } else if !self.yes && !self.json {
+            // Confirm if not --yes
+            picker::require_confirmation_terminal("stop")?;
             self.show_processes(&processes);
 
             let prompt = format!(
@@ -77,37 +107,36 @@ impl StopCommand {
             }
         }
 
-        // Stop processes
-        let mut stopped = Vec::new();
-        let mut failed = Vec::new();
-
-        for proc in &processes {
-            match proc.terminate() {
-                Ok(()) => {
-                    // Wait for process to exit
-                    let stopped_gracefully = self.wait_for_exit(proc);
-                    if stopped_gracefully {
-                        stopped.push(proc.clone());
-                    } else {
-                        // Force kill after timeout - use kill_and_wait for reliability
-                        match proc.kill_and_wait() {
-                            Ok(_) => stopped.push(proc.clone()),
-                            Err(e) => failed.push((proc.clone(), e.to_string())),
-                        }
-                    }
-                }
-                Err(e) => failed.push((proc.clone(), e.to_string())),
-            }
+        // SIGTERM, then SIGKILL whatever outlives the timeout
+        let report = api::kill_processes(processes, &self.options());
+        for proc in &report.reused {
+            printer.warning(&format!("PID {} reused, skipping", proc.pid));
         }
+        let escalated = report.escalated;
+        let defunct = report.defunct;
+        let stopped: Vec<Process> = report
+            .confirmed
+            .into_iter()
+            .chain(escalated.iter().cloned())
+            .collect();
+        let failed: Vec<(Process, String)> = report
+            .failed
+            .into_iter()
+            .map(|(proc, e)| (proc, e.to_string()))
+            .collect();
 
         // Output results
-        if self.json {
+        if printer.wants_json() {
             printer.print_json(&StopOutput {
                 action: "stop",
                 success: failed.is_empty(),
                 stopped_count: stopped.len(),
+                escalated_count: escalated.len(),
+                defunct_count: defunct.len(),
                 failed_count: failed.len(),
                 stopped: &stopped,
+                escalated: &escalated,
+                defunct: &defunct,
                 failed: &failed
                     .iter()
                     .map(|(p, e)| FailedStop {
@@ -115,37 +144,47 @@ impl StopCommand {
                         error: e,
                     })
                     .collect::<Vec<_>>(),
-            });
-        } else {
-            self.print_results(&printer, &stopped, &failed);
+            })?;
+        }
+        if !self.json {
+            self.print_results(&printer, &stopped, &escalated, &defunct, &failed);
         }
 
         Ok(())
     }
 
-    fn wait_for_exit(&self, proc: &Process) -> bool {
-        let start = std::time::Instant::now();
-        let timeout = std::time::Duration::from_secs(self.timeout);
+    /// Resolve the targets and stop every match without prompting or printing
+    ///
+    /// Processes that exited after SIGTERM are in [`KillReport::confirmed`],
+    /// those that needed SIGKILL in [`KillReport::escalated`], and those that
+    /// survived both in [`KillReport::failed`].
+    pub fn run(&self) -> Result<KillReport> {
+        api::kill(&read_targets(&self.target)?, &self.options())
+    }
 
-        while start.elapsed() < timeout {
-            if !proc.is_running() {
-                return true;
-            }
-            std::thread::sleep(std::time::Duration::from_millis(100));
+    /// SIGTERM with escalation to SIGKILL after the grace period
+    fn options(&self) -> KillOptions {
+        let timing = self.timing();
+        KillOptions {
+            graceful: true,
+            escalate_after: Some(timing.grace_period),
+            poll_interval: Some(timing.poll_interval),
+            parallel: self.parallel,
+            ..KillOptions::default()
         }
-
-        false
     }
 
     fn show_processes(&self, processes: &[Process]) {
         use colored::*;
 
+        Printer::blank_line();
         println!(
-            "\n{} Found {} process{}:\n",
+            "{} Found {} process{}:",
             "!".yellow().bold(),
             processes.len().to_string().cyan().bold(),
             if processes.len() == 1 { "" } else { "es" }
         );
+        Printer::blank_line();
 
         for proc in processes {
             println!(
@@ -157,10 +196,27 @@ impl StopCommand {
                 proc.memory_mb
             );
         }
-        println!();
+        Printer::blank_line();
+    }
+
+    /// Grace period and poll interval from --grace-period and --poll-interval
+    fn timing(&self) -> EscalationTiming {
+        EscalationTiming {
+            grace_period: self.grace_period,
+            poll_interval: self
+                .poll_interval
+                .unwrap_or(EscalationTiming::DEFAULT_POLL_INTERVAL),
+        }
     }
 
-    fn print_results(&self, printer: &Printer, stopped: &[Process], failed: &[(Process, String)]) {
+    fn print_results(
+        &self,
+        printer: &Printer,
+        stopped: &[Process],
+        escalated: &[Process],
+        defunct: &[Process],
+        failed: &[(Process, String)],
+    ) {
         use colored::*;
 
         if !stopped.is_empty() {
@@ -171,15 +227,23 @@ impl StopCommand {
                 if stopped.len() == 1 { "" } else { "es" }
             );
             for proc in stopped {
+                let forced = if escalated.iter().any(|p| p.pid == proc.pid) {
+                    format!(" (SIGKILL after {})", format_wait(self.grace_period))
+                } else {
+                    String::new()
+                };
                 println!(
-                    "  {} {} [PID {}]",
+                    "  {} {} [PID {}]{}",
                     "→".bright_black(),
                     proc.name.white(),
-                    proc.pid.to_string().cyan()
+                    proc.pid.to_string().cyan(),
+                    forced.yellow()
                 );
             }
         }
 
+        printer.print_defunct(defunct);
+
         if !failed.is_empty() {
             printer.error(&format!(
                 "Failed to stop {} process{}",
@@ -204,8 +268,12 @@ struct StopOutput<'a> {
     action: &'static str,
     success: bool,
     stopped_count: usize,
+    escalated_count: usize,
+    defunct_count: usize,
     failed_count: usize,
     stopped: &'a [Process],
+    escalated: &'a [Process],
+    defunct: &'a [Process],
     failed: &'a [FailedStop<'a>],
 }
 
@@ -4,14 +4,43 @@
 //!   proc stuck              # Find processes stuck > 5 minutes
 //!   proc stuck --timeout 60 # Find processes stuck > 1 minute
 //!   proc stuck --kill       # Find and kill stuck processes
+//!   proc stuck --cpu-threshold 20 # Treat >20% CPU as stuck
+//!   proc stuck --by-memory  # Find processes leaking memory (>50MB in 5s)
+//!   proc stuck --include-sleeping  # Also catch hangs in uninterruptible sleep (D-state)
+//!   proc stuck -n 5 --sort cpu    # The five hottest stuck processes
 
-use crate::core::Process;
-use crate::error::Result;
-use crate::ui::{OutputFormat, Printer};
-use clap::Args;
+use crate::core::{sort_by_process, Process, StuckReason};
+use crate::error::{ProcError, Result};
+use crate::ui::{has_json_output, picker, OutputFormat, Printer};
+use clap::{Args, ValueEnum};
+use colored::*;
 use dialoguer::Confirm;
+use serde::Serialize;
 use std::time::Duration;
 
+/// Orderings for `proc stuck --sort`
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+pub enum StuckSort {
+    /// Highest CPU usage first
+    Cpu,
+    /// Most resident memory first
+    Mem,
+    /// Longest running first
+    Uptime,
+}
+
+impl StuckSort {
+    fn sort<T>(self, items: &mut [T], process: impl Fn(&T) -> &Process) {
+        match self {
+            StuckSort::Cpu => sort_by_process(items, "cpu", process),
+            StuckSort::Mem => sort_by_process(items, "mem", process),
+            StuckSort::Uptime => {
+                items.sort_by_key(|item| std::cmp::Reverse(process(item).uptime_secs))
+            }
+        }
+    }
+}
+
 /// Find stuck/hung processes
 #[derive(Args, Debug)]
 pub struct StuckCommand {
@@ -19,6 +48,10 @@ pub struct StuckCommand {
     #[arg(long, short = 't', default_value = "300")]
     pub timeout: u64,
 
+    /// CPU percentage above which a long-running process is considered stuck (0-100)
+    #[arg(long, default_value = "50", value_parser = parse_percent)]
+    pub cpu_threshold: f32,
+
     /// Kill found stuck processes
     #[arg(long, short = 'k')]
     pub kill: bool,
@@ -34,6 +67,32 @@ pub struct StuckCommand {
     /// Show verbose output
     #[arg(long, short = 'v')]
     pub verbose: bool,
+
+    /// Also flag long-running processes in uninterruptible sleep (D-state,
+    /// e.g. blocked on a dead NFS mount) in two samples 0.5s apart, whatever
+    /// their CPU usage; --timeout is their run time, not time spent in D-state
+    #[arg(long, conflicts_with = "by_memory")]
+    pub include_sleeping: bool,
+
+    /// Detect runaway memory growth instead of high CPU
+    #[arg(long)]
+    pub by_memory: bool,
+
+    /// Seconds between the two memory samples (with --by-memory)
+    #[arg(long, default_value = "5", requires = "by_memory")]
+    pub window: u64,
+
+    /// Memory growth in MB during the window that counts as a leak (with --by-memory)
+    #[arg(long, default_value = "50", requires = "by_memory")]
+    pub growth_threshold: f64,
+
+    /// Order results (default: cpu, or largest growth first with --by-memory)
+    #[arg(long, short = 's', value_enum, ignore_case = true)]
+    pub sort: Option<StuckSort>,
+
+    /// Only show (and kill) the first N results
+    #[arg(long, short = 'n')]
+    pub limit: Option<usize>,
 }
 
 impl StuckCommand {
@@ -46,30 +105,174 @@ impl StuckCommand {
         };
         let printer = Printer::new(format, self.verbose);
 
+        // The list and the kill result are two JSON results; the file holds one
+        if self.kill && has_json_output() {
+            return Err(ProcError::InvalidInput(
+                "--output records one JSON result, but stuck --kill produces two; use --json instead"
+                    .to_string(),
+            ));
+        }
+
+        if self.by_memory {
+            return self.execute_by_memory(&printer);
+        }
+
         let timeout = Duration::from_secs(self.timeout);
-        let processes = Process::find_stuck(timeout)?;
+        let mut flagged =
+            Process::find_stuck_with_reasons(timeout, self.cpu_threshold, self.include_sleeping)?;
 
-        if processes.is_empty() {
+        let found = flagged.len();
+        self.sort
+            .unwrap_or(StuckSort::Cpu)
+            .sort(&mut flagged, |(proc, _)| proc);
+        self.truncate(&mut flagged);
+
+        if printer.wants_json() {
+            printer.print_json(&StuckOutput {
+                action: "stuck",
+                success: true,
+                count: flagged.len(),
+                processes: flagged
+                    .iter()
+                    .map(|(process, reason)| StuckJson {
+                        process,
+                        reason: *reason,
+                    })
+                    .collect(),
+            })?;
+        }
+        if self.json {
+            // Result already printed as JSON
+        } else if flagged.is_empty() {
             printer.success(&format!(
                 "No stuck processes found (threshold: {}s)",
                 self.timeout
             ));
             return Ok(());
+        } else {
+            printer.warning(&format!(
+                "Found {} potentially stuck process{}{}",
+                found,
+                if found == 1 { "" } else { "es" },
+                self.shown_note(found, flagged.len())
+            ));
+            let processes: Vec<Process> = flagged.iter().map(|(proc, _)| proc.clone()).collect();
+            printer.print_processes_human(&processes, None);
         }
+        if self.include_sleeping && !self.json {
+            for (proc, reason) in &flagged {
+                println!(
+                    "  {} {} [PID {}]: {}",
+                    "→".bright_black(),
+                    proc.name.white().bold(),
+                    proc.pid.to_string().cyan(),
+                    reason.label().yellow()
+                );
+            }
+            Printer::blank_line();
+        }
+
+        let processes = flagged.into_iter().map(|(proc, _)| proc).collect();
+        self.kill_if_requested(&printer, processes)
+    }
 
-        printer.warning(&format!(
-            "Found {} potentially stuck process{}",
-            processes.len(),
-            if processes.len() == 1 { "" } else { "es" }
-        ));
-        printer.print_processes(&processes);
+    /// Memory-growth mode: sample twice and report processes that grew too fast
+    fn execute_by_memory(&self, printer: &Printer) -> Result<()> {
+        let window = Duration::from_secs(self.window);
+        if !self.json {
+            println!(
+                "{} Sampling memory over {}s...",
+                "ℹ".blue(),
+                self.window.to_string().cyan()
+            );
+        }
+        let mut grown = Process::find_memory_growth(window, self.growth_threshold)?;
+        let found = grown.len();
+        if let Some(sort) = self.sort {
+            sort.sort(&mut grown, |(proc, _)| proc);
+        }
+        self.truncate(&mut grown);
+
+        if printer.wants_json() {
+            printer.print_json(&MemoryGrowthOutput {
+                action: "stuck",
+                success: true,
+                mode: "memory",
+                window_secs: self.window,
+                growth_threshold_mb: self.growth_threshold,
+                count: grown.len(),
+                processes: grown
+                    .iter()
+                    .map(|(process, growth_mb)| MemoryGrowthJson {
+                        process,
+                        growth_mb: *growth_mb,
+                        growth_mb_per_sec: *growth_mb / self.window.max(1) as f64,
+                    })
+                    .collect(),
+            })?;
+        }
+        if self.json {
+            // Result already printed as JSON
+        } else if grown.is_empty() {
+            printer.success(&format!(
+                "No processes grew more than {}MB in {}s",
+                self.growth_threshold, self.window
+            ));
+            return Ok(());
+        } else {
+            printer.warning(&format!(
+                "Found {} process{} with growing memory{}",
+                found,
+                if found == 1 { "" } else { "es" },
+                self.shown_note(found, grown.len())
+            ));
+            for (proc, growth_mb) in &grown {
+                println!(
+                    "  {} {} [PID {}] - {:.1}MB now, {} over {}s ({:.1}MB/s)",
+                    "→".bright_black(),
+                    proc.name.white().bold(),
+                    proc.pid.to_string().cyan(),
+                    proc.memory_mb,
+                    format!("+{:.1}MB", growth_mb).yellow(),
+                    self.window,
+                    growth_mb / self.window.max(1) as f64
+                );
+            }
+            Printer::blank_line();
+        }
+
+        let processes = grown.into_iter().map(|(proc, _)| proc).collect();
+        self.kill_if_requested(printer, processes)
+    }
+
+    /// Apply --limit
+    fn truncate<T>(&self, items: &mut Vec<T>) {
+        if let Some(limit) = self.limit {
+            items.truncate(limit);
+        }
+    }
+
+    /// ", showing N" when --limit cut the results short
+    fn shown_note(&self, found: usize, shown: usize) -> String {
+        if shown < found {
+            format!(", showing {}", shown)
+        } else {
+            String::new()
+        }
+    }
+
+    /// Kill the found processes when --kill was given, confirming first
+    fn kill_if_requested(&self, printer: &Printer, processes: Vec<Process>) -> Result<()> {
+        if processes.is_empty() {
+            return Ok(());
+        }
 
-        // Kill if requested
         if self.kill {
             if !self.yes && !self.json {
+                picker::require_confirmation_terminal("kill")?;
                 let confirmed = Confirm::new()
                     .with_prompt(format!(
-                        "Kill {} stuck process{}?",
+                        "Kill the {} stuck process{} listed above?",
                         processes.len(),
                         if processes.len() == 1 { "" } else { "es" }
                     ))
@@ -94,9 +297,53 @@ impl StuckCommand {
                 }
             }
 
-            printer.print_kill_result(&killed, &failed);
+            printer.print_kill_result(&killed, &[], &[], &[], &[], &failed)?;
         }
 
         Ok(())
     }
 }
+
+#[derive(Serialize)]
+struct StuckOutput<'a> {
+    action: &'static str,
+    success: bool,
+    count: usize,
+    processes: Vec<StuckJson<'a>>,
+}
+
+#[derive(Serialize)]
+struct StuckJson<'a> {
+    #[serde(flatten)]
+    process: &'a Process,
+    reason: StuckReason,
+}
+
+#[derive(Serialize)]
+struct MemoryGrowthOutput<'a> {
+    action: &'static str,
+    success: bool,
+    mode: &'static str,
+    window_secs: u64,
+    growth_threshold_mb: f64,
+    count: usize,
+    processes: Vec<MemoryGrowthJson<'a>>,
+}
+
+#[derive(Serialize)]
+struct MemoryGrowthJson<'a> {
+    #[serde(flatten)]
+    process: &'a Process,
+    growth_mb: f64,
+    growth_mb_per_sec: f64,
+}
+
+/// Parse a percentage argument, rejecting values outside 0-100
+pub(crate) fn parse_percent(s: &str) -> std::result::Result<f32, String> {
+    let value: f32 = s.parse().map_err(|_| format!("'{}' is not a number", s))?;
+    if (0.0..=100.0).contains(&value) {
+        Ok(value)
+    } else {
+        Err(format!("{} is not between 0 and 100", value))
+    }
+}
@@ -7,10 +7,17 @@
 //!   proc tree 1234         # Tree for PID 1234
 //!   proc tree --min-cpu 10 # Only processes using >10% CPU
 //!   proc tree 1234 -a      # Show ancestry (path UP to root)
-
-use crate::core::{parse_target, resolve_target, Process, ProcessStatus, TargetType};
+//!   proc tree 1234 -c      # Ancestry above, descendants below, in one view
+//!   proc tree node --threads # Show each process's threads (Linux)
+//!   proc tree -C --json    # Topology only: root PIDs and {pid, ppid} edges
+//!   proc tree --user-only  # Leave out kernel threads (kthreadd and its workers)
+
+use crate::core::{
+    children_map, parse_target, resolve_target, split_threads, Process, StatusFilter, TargetType,
+};
 use crate::error::Result;
-use crate::ui::{OutputFormat, Printer};
+use crate::ui::tree::status_indicator;
+use crate::ui::{OutputFormat, Printer, TreePrinter};
 use clap::Args;
 use colored::*;
 use serde::Serialize;
@@ -26,6 +33,10 @@ pub struct TreeCommand {
     #[arg(long, short)]
     ancestors: bool,
 
+    /// Show ancestry (dimmed) above the target and its descendants below
+    #[arg(long, short, conflicts_with = "ancestors")]
+    context: bool,
+
     /// Output as JSON
     #[arg(long, short)]
     json: bool,
@@ -34,7 +45,8 @@ pub struct TreeCommand {
     #[arg(long, short, default_value = "10")]
     depth: usize,
 
-    /// Show PIDs only (compact view)
+    /// Show PIDs only (compact view); with --json, emit root PIDs and
+    /// {pid, ppid} edges instead of full nodes
     #[arg(long, short = 'C')]
     compact: bool,
 
@@ -46,9 +58,17 @@ pub struct TreeCommand {
     #[arg(long)]
     min_mem: Option<f64>,
 
-    /// Filter by status: running, sleeping, stopped, zombie
+    /// Only show processes in this state
+    #[arg(long, value_enum, ignore_case = true)]
+    status: Option<StatusFilter>,
+
+    /// Show each process's threads as leaves (Linux only)
     #[arg(long)]
-    status: Option<String>,
+    threads: bool,
+
+    /// Hide kernel threads (kthreadd and its children on Linux)
+    #[arg(long, short = 'u')]
+    user_only: bool,
 }
 
 impl TreeCommand {
@@ -61,31 +81,44 @@ impl TreeCommand {
         };
         let printer = Printer::new(format, false);
 
-        // Get all processes
-        let all_processes = Process::find_all()?;
-
-        // Build PID -> Process map for quick lookup
-        let pid_map: HashMap<u32, &Process> = all_processes.iter().map(|p| (p.pid, p)).collect();
+        // Get all processes, setting threads aside so they don't pose as children
+        let (mut all_processes, threads) = split_threads(Process::find_all()?);
+        if self.user_only {
+            all_processes.retain(|p| !p.is_kernel_thread());
+        }
+        if self.threads && cfg!(not(target_os = "linux")) {
+            printer.warning("--threads is only supported on Linux; showing processes only");
+        }
 
         // Build parent -> children map
-        let mut children_map: HashMap<u32, Vec<&Process>> = HashMap::new();
-
-        for proc in &all_processes {
-            if let Some(ppid) = proc.parent_pid {
-                children_map.entry(ppid).or_default().push(proc);
-            }
-        }
+        let children_map = children_map(&all_processes);
 
         // Handle --ancestors mode
         if self.ancestors {
-            return self.show_ancestors(&printer, &pid_map);
+            return self.show_ancestors(&printer, &all_processes);
+        }
+
+        let mut tree_printer = TreePrinter::new(&children_map)
+            .max_depth(self.depth)
+            .compact(self.compact);
+        if self.threads {
+            tree_printer = tree_printer.threads(&threads);
+        }
+        let threads = self.threads.then_some(&threads);
+
+        // Handle --context mode
+        if self.context {
+            return self.show_context(&printer, &all_processes, &tree_printer, threads);
         }
 
         // Determine target processes
         let target_processes: Vec<&Process> = if let Some(ref target) = self.target {
             // Use unified target resolution
             match parse_target(target) {
-                TargetType::Port(_) | TargetType::Pid(_) => {
+                TargetType::Port(_)
+                | TargetType::PortRange(..)
+                | TargetType::Pid(_)
+                | TargetType::ExePath(_) => {
                     // For port or PID, resolve to specific process(es)
                     let resolved = resolve_target(target)?;
                     if resolved.is_empty() {
@@ -130,17 +163,8 @@ impl TreeCommand {
                     return false;
                 }
             }
-            if let Some(ref status) = self.status {
-                let status_match = match status.to_lowercase().as_str() {
-                    "running" => matches!(p.status, ProcessStatus::Running),
-                    "sleeping" | "sleep" => matches!(p.status, ProcessStatus::Sleeping),
-                    "stopped" | "stop" => matches!(p.status, ProcessStatus::Stopped),
-                    "zombie" => matches!(p.status, ProcessStatus::Zombie),
-                    _ => true,
-                };
-                if !status_match {
-                    return false;
-                }
+            if self.status.is_some_and(|status| !status.matches(p.status)) {
+                return false;
             }
             true
         };
@@ -148,34 +172,51 @@ impl TreeCommand {
         // Apply filters to target processes or find filtered roots
         let has_filters = self.min_cpu.is_some() || self.min_mem.is_some() || self.status.is_some();
 
-        if self.json {
-            let tree_nodes = if self.target.is_some() {
+        if printer.wants_json() {
+            let tree_nodes: Vec<TreeNode> = if self.target.is_some() {
                 target_processes
                     .iter()
                     .filter(|p| matches_filters(p))
-                    .map(|p| self.build_tree_node(p, &children_map, 0))
+                    .map(|p| self.build_tree_node(p, &children_map, threads, 0))
                     .collect()
             } else if has_filters {
                 // Show only processes matching filters
                 all_processes
                     .iter()
                     .filter(|p| matches_filters(p))
-                    .map(|p| self.build_tree_node(p, &children_map, 0))
+                    .map(|p| self.build_tree_node(p, &children_map, threads, 0))
                     .collect()
             } else {
                 // Show full tree from roots
                 all_processes
                     .iter()
                     .filter(|p| p.parent_pid.is_none() || p.parent_pid == Some(0))
-                    .map(|p| self.build_tree_node(p, &children_map, 0))
+                    .map(|p| self.build_tree_node(p, &children_map, threads, 0))
                     .collect()
             };
 
-            printer.print_json(&TreeOutput {
-                action: "tree",
-                success: true,
-                tree: tree_nodes,
-            });
+            if self.compact {
+                let mut edges = Vec::new();
+                for node in &tree_nodes {
+                    collect_edges(node, &mut edges);
+                }
+                printer.print_json(&CompactTreeOutput {
+                    action: "tree",
+                    success: true,
+                    roots: tree_nodes.iter().map(|n| n.pid).collect(),
+                    edges,
+                })?;
+            } else {
+                printer.print_json(&TreeOutput {
+                    action: "tree",
+                    success: true,
+                    tree: tree_nodes,
+                })?;
+            }
+        }
+
+        if self.json {
+            // Result already printed as JSON
         } else if self.target.is_some() {
             let filtered: Vec<_> = target_processes
                 .into_iter()
@@ -190,15 +231,19 @@ impl TreeCommand {
             }
 
             println!(
-                "{} Process tree for '{}':\n",
+                "{} Process tree for '{}':",
                 "✓".green().bold(),
                 self.target.as_ref().unwrap().cyan()
             );
+            Printer::blank_line();
 
-            for proc in &filtered {
-                self.print_tree(proc, &children_map, "", true, 0);
-                println!();
+            for (i, proc) in filtered.iter().enumerate() {
+                if i > 0 {
+                    println!();
+                }
+                tree_printer.print(proc, "", true, 0);
             }
+            Printer::blank_line();
         } else if has_filters {
             let filtered: Vec<_> = all_processes
                 .iter()
@@ -210,18 +255,17 @@ impl TreeCommand {
             }
 
             println!(
-                "{} {} process{} matching filters:\n",
+                "{} {} process{} matching filters:",
                 "✓".green().bold(),
                 filtered.len().to_string().cyan().bold(),
                 if filtered.len() == 1 { "" } else { "es" }
             );
+            Printer::blank_line();
 
-            for (i, proc) in filtered.iter().enumerate() {
-                let is_last = i == filtered.len() - 1;
-                self.print_tree(proc, &children_map, "", is_last, 0);
-            }
+            tree_printer.print_roots(&filtered);
         } else {
-            println!("{} Process tree:\n", "✓".green().bold());
+            println!("{} Process tree:", "✓".green().bold());
+            Printer::blank_line();
 
             // Find processes with PID 1 or no parent as roots
             let display_roots: Vec<&Process> = all_processes
@@ -229,78 +273,17 @@ impl TreeCommand {
                 .filter(|p| p.parent_pid.is_none() || p.parent_pid == Some(0))
                 .collect();
 
-            for (i, proc) in display_roots.iter().enumerate() {
-                let is_last = i == display_roots.len() - 1;
-                self.print_tree(proc, &children_map, "", is_last, 0);
-            }
+            tree_printer.print_roots(&display_roots);
         }
 
         Ok(())
     }
 
-    fn print_tree(
-        &self,
-        proc: &Process,
-        children_map: &HashMap<u32, Vec<&Process>>,
-        prefix: &str,
-        is_last: bool,
-        depth: usize,
-    ) {
-        if depth > self.depth {
-            return;
-        }
-
-        let connector = if is_last { "└── " } else { "├── " };
-
-        if self.compact {
-            println!(
-                "{}{}{}",
-                prefix.bright_black(),
-                connector.bright_black(),
-                proc.pid.to_string().cyan()
-            );
-        } else {
-            let status_indicator = match proc.status {
-                crate::core::ProcessStatus::Running => "●".green(),
-                crate::core::ProcessStatus::Sleeping => "○".blue(),
-                crate::core::ProcessStatus::Stopped => "◐".yellow(),
-                crate::core::ProcessStatus::Zombie => "✗".red(),
-                _ => "?".white(),
-            };
-
-            println!(
-                "{}{}{} {} [{}] {:.1}% {:.1}MB",
-                prefix.bright_black(),
-                connector.bright_black(),
-                status_indicator,
-                proc.name.white().bold(),
-                proc.pid.to_string().cyan(),
-                proc.cpu_percent,
-                proc.memory_mb
-            );
-        }
-
-        let child_prefix = if is_last {
-            format!("{}    ", prefix)
-        } else {
-            format!("{}│   ", prefix)
-        };
-
-        if let Some(children) = children_map.get(&proc.pid) {
-            let mut sorted_children: Vec<&&Process> = children.iter().collect();
-            sorted_children.sort_by_key(|p| p.pid);
-
-            for (i, child) in sorted_children.iter().enumerate() {
-                let child_is_last = i == sorted_children.len() - 1;
-                self.print_tree(child, children_map, &child_prefix, child_is_last, depth + 1);
-            }
-        }
-    }
-
     fn build_tree_node(
         &self,
         proc: &Process,
         children_map: &HashMap<u32, Vec<&Process>>,
+        threads: Option<&HashMap<u32, Vec<Process>>>,
         depth: usize,
     ) -> TreeNode {
         let children = if depth < self.depth {
@@ -308,7 +291,7 @@ impl TreeCommand {
                 .get(&proc.pid)
                 .map(|kids| {
                     kids.iter()
-                        .map(|p| self.build_tree_node(p, children_map, depth + 1))
+                        .map(|p| self.build_tree_node(p, children_map, threads, depth + 1))
                         .collect()
                 })
                 .unwrap_or_default()
@@ -316,35 +299,142 @@ impl TreeCommand {
             Vec::new()
         };
 
+        let mut threads: Vec<ThreadNode> = threads
+            .and_then(|map| map.get(&proc.pid))
+            .map(|tasks| {
+                tasks
+                    .iter()
+                    .map(|t| ThreadNode {
+                        tid: t.pid,
+                        name: t.name.clone(),
+                        cpu_percent: t.cpu_percent,
+                        cpu_time_secs: t.cpu_time_secs,
+                    })
+                    .collect::<Vec<_>>()
+            })
+            .unwrap_or_default();
+        threads.sort_by_key(|t| t.tid);
+
         TreeNode {
             pid: proc.pid,
             name: proc.name.clone(),
             cpu_percent: proc.cpu_percent,
             memory_mb: proc.memory_mb,
             status: format!("{:?}", proc.status),
+            threads,
             children,
         }
     }
 
     /// Show ancestry (path UP to root) for target processes
-    fn show_ancestors(&self, printer: &Printer, pid_map: &HashMap<u32, &Process>) -> Result<()> {
-        use crate::core::{parse_target, resolve_target, TargetType};
+    fn show_ancestors(&self, printer: &Printer, all_processes: &[Process]) -> Result<()> {
+        let Some((target, target_processes)) =
+            self.resolve_for_chain(printer, "--ancestors", all_processes)?
+        else {
+            return Ok(());
+        };
+
+        if printer.wants_json() {
+            let ancestry_output: Vec<AncestryNode> = target_processes
+                .iter()
+                .map(|proc| self.build_ancestry_node(proc, all_processes))
+                .collect();
+            printer.print_json(&AncestryOutput {
+                action: "ancestry",
+                success: true,
+                ancestry: ancestry_output,
+            })?;
+        }
+        if !self.json {
+            println!("{} Ancestry for '{}':", "✓".green().bold(), target.cyan());
+            Printer::blank_line();
+
+            for (i, proc) in target_processes.iter().enumerate() {
+                if i > 0 {
+                    println!();
+                }
+                self.print_ancestry(proc, all_processes, false);
+            }
+            Printer::blank_line();
+        }
 
+        Ok(())
+    }
+
+    /// Show each target with its ancestry above and its descendants below
+    fn show_context(
+        &self,
+        printer: &Printer,
+        all_processes: &[Process],
+        tree_printer: &TreePrinter,
+        threads: Option<&HashMap<u32, Vec<Process>>>,
+    ) -> Result<()> {
+        let Some((target, target_processes)) =
+            self.resolve_for_chain(printer, "--context", all_processes)?
+        else {
+            return Ok(());
+        };
+
+        if printer.wants_json() {
+            let children_map = children_map(all_processes);
+            let context: Vec<ContextNode> = target_processes
+                .iter()
+                .map(|proc| ContextNode {
+                    ancestry: self.build_ancestry_node(proc, all_processes),
+                    descendants: self
+                        .build_tree_node(proc, &children_map, threads, 0)
+                        .children,
+                })
+                .collect();
+            printer.print_json(&ContextOutput {
+                action: "context",
+                success: true,
+                context,
+            })?;
+        }
+        if !self.json {
+            println!("{} Context for '{}':", "✓".green().bold(), target.cyan());
+            Printer::blank_line();
+
+            for (i, proc) in target_processes.iter().enumerate() {
+                if i > 0 {
+                    println!();
+                }
+                let depth = self.print_ancestry(proc, all_processes, true);
+                tree_printer.print_children(proc, &"    ".repeat(depth + 1), 0);
+            }
+            Printer::blank_line();
+        }
+
+        Ok(())
+    }
+
+    /// Resolve the target for --ancestors or --context, warning when there is
+    /// no target or nothing matches
+    fn resolve_for_chain<'t>(
+        &'t self,
+        printer: &Printer,
+        flag: &str,
+        all_processes: &[Process],
+    ) -> Result<Option<(&'t String, Vec<Process>)>> {
         let target = match &self.target {
             Some(t) => t,
             None => {
-                printer.warning("--ancestors requires a target (PID, :port, or name)");
-                return Ok(());
+                printer.warning(&format!("{} requires a target (PID, :port, or name)", flag));
+                return Ok(None);
             }
         };
 
         // Resolve target to processes
         let target_processes = match parse_target(target) {
-            TargetType::Port(_) | TargetType::Pid(_) => resolve_target(target)?,
+            TargetType::Port(_)
+            | TargetType::PortRange(..)
+            | TargetType::Pid(_)
+            | TargetType::ExePath(_) => resolve_target(target)?,
             TargetType::Name(ref pattern) => {
                 let pattern_lower = pattern.to_lowercase();
-                pid_map
-                    .values()
+                all_processes
+                    .iter()
                     .filter(|p| {
                         p.name.to_lowercase().contains(&pattern_lower)
                             || p.command
@@ -352,56 +442,25 @@ impl TreeCommand {
                                 .map(|c| c.to_lowercase().contains(&pattern_lower))
                                 .unwrap_or(false)
                     })
-                    .map(|p| (*p).clone())
+                    .cloned()
                     .collect()
             }
         };
 
         if target_processes.is_empty() {
             printer.warning(&format!("No process found for '{}'", target));
-            return Ok(());
-        }
-
-        if self.json {
-            let ancestry_output: Vec<AncestryNode> = target_processes
-                .iter()
-                .map(|proc| self.build_ancestry_node(proc, pid_map))
-                .collect();
-            printer.print_json(&AncestryOutput {
-                action: "ancestry",
-                success: true,
-                ancestry: ancestry_output,
-            });
-        } else {
-            println!("{} Ancestry for '{}':\n", "✓".green().bold(), target.cyan());
-
-            for proc in &target_processes {
-                self.print_ancestry(proc, pid_map);
-                println!();
-            }
+            return Ok(None);
         }
 
-        Ok(())
+        Ok(Some((target, target_processes)))
     }
 
-    /// Trace and print ancestry from root down to target
-    fn print_ancestry(&self, target: &Process, pid_map: &HashMap<u32, &Process>) {
+    /// Trace and print ancestry from root down to target, dimming the
+    /// ancestors with `dim`; returns the target's depth in the chain
+    fn print_ancestry(&self, target: &Process, all_processes: &[Process], dim: bool) -> usize {
         // Build the ancestor chain (from target up to root)
-        let mut chain: Vec<&Process> = Vec::new();
-        let mut current_pid = Some(target.pid);
-
-        while let Some(pid) = current_pid {
-            if let Some(proc) = pid_map.get(&pid) {
-                chain.push(proc);
-                current_pid = proc.parent_pid;
-                // Prevent infinite loops
-                if chain.len() > 100 {
-                    break;
-                }
-            } else {
-                break;
-            }
-        }
+        let ancestors = target.ancestors(all_processes);
+        let mut chain: Vec<&Process> = std::iter::once(target).chain(&ancestors).collect();
 
         // Reverse to print from root to target
         chain.reverse();
@@ -412,13 +471,7 @@ impl TreeCommand {
             let indent = "    ".repeat(i);
             let connector = if i == 0 { "" } else { "└── " };
 
-            let status_indicator = match proc.status {
-                ProcessStatus::Running => "●".green(),
-                ProcessStatus::Sleeping => "○".blue(),
-                ProcessStatus::Stopped => "◐".yellow(),
-                ProcessStatus::Zombie => "✗".red(),
-                _ => "?".white(),
-            };
+            let status_indicator = status_indicator(&proc.status);
 
             if is_target {
                 // Highlight the target
@@ -433,6 +486,17 @@ impl TreeCommand {
                     proc.memory_mb,
                     "← target".yellow()
                 );
+            } else if dim {
+                println!(
+                    "{}{}{}",
+                    indent.bright_black(),
+                    connector.bright_black(),
+                    format!(
+                        "{} [{}] {:.1}% {:.1}MB",
+                        proc.name, proc.pid, proc.cpu_percent, proc.memory_mb
+                    )
+                    .bright_black()
+                );
             } else {
                 println!(
                     "{}{}{} {} [{}] {:.1}% {:.1}MB",
@@ -446,34 +510,23 @@ impl TreeCommand {
                 );
             }
         }
+
+        chain.len() - 1
     }
 
     /// Build ancestry node for JSON output
-    fn build_ancestry_node(
-        &self,
-        target: &Process,
-        pid_map: &HashMap<u32, &Process>,
-    ) -> AncestryNode {
-        let mut chain: Vec<ProcessInfo> = Vec::new();
-        let mut current_pid = Some(target.pid);
-
-        while let Some(pid) = current_pid {
-            if let Some(proc) = pid_map.get(&pid) {
-                chain.push(ProcessInfo {
-                    pid: proc.pid,
-                    name: proc.name.clone(),
-                    cpu_percent: proc.cpu_percent,
-                    memory_mb: proc.memory_mb,
-                    status: format!("{:?}", proc.status),
-                });
-                current_pid = proc.parent_pid;
-                if chain.len() > 100 {
-                    break;
-                }
-            } else {
-                break;
-            }
-        }
+    fn build_ancestry_node(&self, target: &Process, all_processes: &[Process]) -> AncestryNode {
+        let ancestors = target.ancestors(all_processes);
+        let mut chain: Vec<ProcessInfo> = std::iter::once(target)
+            .chain(&ancestors)
+            .map(|proc| ProcessInfo {
+                pid: proc.pid,
+                name: proc.name.clone(),
+                cpu_percent: proc.cpu_percent,
+                memory_mb: proc.memory_mb,
+                status: format!("{:?}", proc.status),
+            })
+            .collect();
 
         chain.reverse();
 
@@ -493,6 +546,20 @@ struct AncestryOutput {
     ancestry: Vec<AncestryNode>,
 }
 
+#[derive(Serialize)]
+struct ContextOutput {
+    action: &'static str,
+    success: bool,
+    context: Vec<ContextNode>,
+}
+
+#[derive(Serialize)]
+struct ContextNode {
+    #[serde(flatten)]
+    ancestry: AncestryNode,
+    descendants: Vec<TreeNode>,
+}
+
 #[derive(Serialize)]
 struct AncestryNode {
     target_pid: u32,
@@ -517,6 +584,32 @@ struct TreeOutput {
     tree: Vec<TreeNode>,
 }
 
+/// Topology-only tree: the top-level PIDs and every parent-child link below them
+#[derive(Serialize)]
+struct CompactTreeOutput {
+    action: &'static str,
+    success: bool,
+    roots: Vec<u32>,
+    edges: Vec<TreeEdge>,
+}
+
+#[derive(Serialize)]
+struct TreeEdge {
+    pid: u32,
+    ppid: u32,
+}
+
+/// Append an edge for each child under `node`, depth-first
+fn collect_edges(node: &TreeNode, edges: &mut Vec<TreeEdge>) {
+    for child in &node.children {
+        edges.push(TreeEdge {
+            pid: child.pid,
+            ppid: node.pid,
+        });
+        collect_edges(child, edges);
+    }
+}
+
 #[derive(Serialize)]
 struct TreeNode {
     pid: u32,
@@ -524,5 +617,15 @@ struct TreeNode {
     cpu_percent: f32,
     memory_mb: f64,
     status: String,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    threads: Vec<ThreadNode>,
     children: Vec<TreeNode>,
 }
+
+#[derive(Serialize)]
+struct ThreadNode {
+    tid: u32,
+    name: String,
+    cpu_percent: f32,
+    cpu_time_secs: u64,
+}
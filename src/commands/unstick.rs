@@ -15,10 +15,14 @@
 //!   proc unstick :3000     # Unstick process on port 3000
 //!   proc unstick 1234      # Unstick PID 1234
 //!   proc unstick node      # Unstick stuck node processes
+//!   proc unstick :3000 --force --grace-period 500ms  # Shorter waits between signals
 
-use crate::core::{resolve_target, Process};
+use crate::commands::poll_interval;
+use crate::commands::stuck::parse_percent;
+use crate::core::process::wait_for_exits;
+use crate::core::{resolve_target, EscalationTiming, Process};
 use crate::error::{ProcError, Result};
-use crate::ui::{OutputFormat, Printer};
+use crate::ui::{format_duration, parse_wait, picker, OutputFormat, Printer};
 use clap::Args;
 use colored::*;
 use dialoguer::Confirm;
@@ -40,6 +44,14 @@ pub struct UnstickCommand {
     #[arg(long, short, default_value = "300")]
     timeout: u64,
 
+    /// CPU percentage above which a process is considered stuck (0-100)
+    #[arg(long, default_value = "50", value_parser = parse_percent)]
+    cpu_threshold: f32,
+
+    /// CPU percentage below which a process counts as recovered (0-100)
+    #[arg(long, default_value = "10", value_parser = parse_percent)]
+    recovery_threshold: f32,
+
     /// Force termination if recovery fails
     #[arg(long, short = 'f')]
     force: bool,
@@ -55,17 +67,48 @@ pub struct UnstickCommand {
     /// Output as JSON
     #[arg(long, short)]
     json: bool,
+
+    /// How long to wait after each signal (default: 1s after SIGCONT, 3s after
+    /// SIGINT, 5s after SIGTERM); takes milliseconds too, e.g. 500ms
+    #[arg(long, value_name = "DURATION", value_parser = parse_wait)]
+    grace_period: Option<Duration>,
+
+    /// Longest gap between checks on whether a signaled process has exited (e.g. 50ms)
+    #[arg(long, value_name = "DURATION", value_parser = poll_interval)]
+    poll_interval: Option<Duration>,
 }
 
+/// What happened to one process an unstick was attempted on
 #[derive(Debug, Clone, PartialEq)]
-enum Outcome {
-    Recovered,  // Process unstuck and still running
-    Terminated, // Had to kill it (only with --force)
-    StillStuck, // Could not recover, not terminated (no --force)
-    NotStuck,   // Process wasn't stuck to begin with
+pub enum UnstickOutcome {
+    /// Unstuck and still running
+    Recovered,
+    /// Had to be killed (only with `--force`)
+    Terminated,
+    /// Could not be recovered, and was not terminated (no `--force`)
+    StillStuck,
+    /// Wasn't stuck to begin with
+    NotStuck,
+    /// Could not be signaled, or its PID was reused
     Failed(String),
 }
 
+/// Outcome of an unstick run
+#[derive(Debug, Default)]
+pub struct UnstickReport {
+    /// Processes found stuck (or matching the target)
+    pub found: Vec<Process>,
+    /// Each process attempted, with what happened to it (empty on a dry run)
+    pub outcomes: Vec<(Process, UnstickOutcome)>,
+}
+
+impl UnstickReport {
+    /// Number of processes that ended with an outcome matching `pred`
+    pub fn count(&self, pred: impl Fn(&UnstickOutcome) -> bool) -> usize {
+        self.outcomes.iter().filter(|(_, o)| pred(o)).count()
+    }
+}
+
 impl UnstickCommand {
     /// Executes the unstick command, attempting to recover hung processes.
     pub fn execute(&self) -> Result<()> {
@@ -76,18 +119,10 @@ impl UnstickCommand {
         };
         let printer = Printer::new(format, false);
 
-        // Get processes to unstick
-        let stuck = if let Some(ref target) = self.target {
-            // Specific target
-            self.resolve_target_processes(target)?
-        } else {
-            // Auto-discover stuck processes
-            let timeout = Duration::from_secs(self.timeout);
-            Process::find_stuck(timeout)?
-        };
+        let stuck = self.find()?;
 
         if stuck.is_empty() {
-            if self.json {
+            if printer.wants_json() {
                 printer.print_json(&UnstickOutput {
                     action: "unstick",
                     success: true,
@@ -100,7 +135,10 @@ impl UnstickCommand {
                     terminated: 0,
                     failed: 0,
                     processes: Vec::new(),
-                });
+                })?;
+            }
+            if self.json {
+                // Result already printed as JSON
             } else if self.target.is_some() {
                 printer.warning("Target process not found");
             } else {
@@ -116,7 +154,7 @@ impl UnstickCommand {
 
         // Dry run
         if self.dry_run {
-            if self.json {
+            if printer.wants_json() {
                 printer.print_json(&UnstickOutput {
                     action: "unstick",
                     success: true,
@@ -136,10 +174,12 @@ impl UnstickCommand {
                             outcome: "would_attempt".to_string(),
                         })
                         .collect(),
-                });
-            } else {
+                })?;
+            }
+            if !self.json {
+                Printer::blank_line();
                 println!(
-                    "\n{} Dry run: Would attempt to unstick {} process{}",
+                    "{} Dry run: Would attempt to unstick {} process{}",
                     "ℹ".blue().bold(),
                     stuck.len().to_string().cyan().bold(),
                     if stuck.len() == 1 { "" } else { "es" }
@@ -149,24 +189,27 @@ impl UnstickCommand {
                 } else {
                     println!("  Without --force: will only attempt recovery");
                 }
-                println!();
+                Printer::blank_line();
             }
             return Ok(());
         }
 
         // Confirm
         if !self.yes && !self.json {
+            picker::require_confirmation_terminal("unstick")?;
+            Printer::blank_line();
             if self.force {
                 println!(
-                    "\n{} With --force: processes will be terminated if recovery fails.\n",
+                    "{} With --force: processes will be terminated if recovery fails.",
                     "!".yellow().bold()
                 );
             } else {
                 println!(
-                    "\n{} Will attempt recovery only. Use --force to terminate if needed.\n",
+                    "{} Will attempt recovery only. Use --force to terminate if needed.",
                     "ℹ".blue().bold()
                 );
             }
+            Printer::blank_line();
 
             let prompt = format!(
                 "Unstick {} process{}?",
@@ -185,7 +228,7 @@ impl UnstickCommand {
         }
 
         // Attempt to unstick each process
-        let mut outcomes: Vec<(Process, Outcome)> = Vec::new();
+        let mut outcomes: Vec<(Process, UnstickOutcome)> = Vec::new();
 
         for proc in &stuck {
             if !self.json {
@@ -197,15 +240,15 @@ impl UnstickCommand {
                 );
             }
 
-            let outcome = self.attempt_unstick(proc);
+            let outcome = self.attempt(proc);
 
             if !self.json {
                 match &outcome {
-                    Outcome::Recovered => println!("{}", "recovered".green()),
-                    Outcome::Terminated => println!("{}", "terminated".yellow()),
-                    Outcome::StillStuck => println!("{}", "still stuck".red()),
-                    Outcome::NotStuck => println!("{}", "not stuck".blue()),
-                    Outcome::Failed(e) => println!("{}: {}", "failed".red(), e),
+                    UnstickOutcome::Recovered => println!("{}", "recovered".green()),
+                    UnstickOutcome::Terminated => println!("{}", "terminated".yellow()),
+                    UnstickOutcome::StillStuck => println!("{}", "still stuck".red()),
+                    UnstickOutcome::NotStuck => println!("{}", "not stuck".blue()),
+                    UnstickOutcome::Failed(e) => println!("{}: {}", "failed".red(), e),
                 }
             }
 
@@ -213,57 +256,48 @@ impl UnstickCommand {
         }
 
         // Count outcomes
-        let recovered = outcomes
-            .iter()
-            .filter(|(_, o)| *o == Outcome::Recovered)
-            .count();
-        let terminated = outcomes
-            .iter()
-            .filter(|(_, o)| *o == Outcome::Terminated)
-            .count();
-        let still_stuck = outcomes
-            .iter()
-            .filter(|(_, o)| *o == Outcome::StillStuck)
-            .count();
-        let not_stuck = outcomes
-            .iter()
-            .filter(|(_, o)| *o == Outcome::NotStuck)
-            .count();
-        let failed = outcomes
-            .iter()
-            .filter(|(_, o)| matches!(o, Outcome::Failed(_)))
-            .count();
+        let report = UnstickReport {
+            found: stuck,
+            outcomes,
+        };
+        let recovered = report.count(|o| *o == UnstickOutcome::Recovered);
+        let terminated = report.count(|o| *o == UnstickOutcome::Terminated);
+        let still_stuck = report.count(|o| *o == UnstickOutcome::StillStuck);
+        let not_stuck = report.count(|o| *o == UnstickOutcome::NotStuck);
+        let failed = report.count(|o| matches!(o, UnstickOutcome::Failed(_)));
 
         // Output results
-        if self.json {
+        if printer.wants_json() {
             printer.print_json(&UnstickOutput {
                 action: "unstick",
                 success: failed == 0 && still_stuck == 0,
                 dry_run: false,
                 force: self.force,
-                found: stuck.len(),
+                found: report.found.len(),
                 recovered,
                 not_stuck,
                 still_stuck,
                 terminated,
                 failed,
-                processes: outcomes
+                processes: report
+                    .outcomes
                     .iter()
                     .map(|(p, o)| ProcessOutcome {
                         pid: p.pid,
                         name: p.name.clone(),
                         outcome: match o {
-                            Outcome::Recovered => "recovered".to_string(),
-                            Outcome::Terminated => "terminated".to_string(),
-                            Outcome::StillStuck => "still_stuck".to_string(),
-                            Outcome::NotStuck => "not_stuck".to_string(),
-                            Outcome::Failed(e) => format!("failed: {}", e),
+                            UnstickOutcome::Recovered => "recovered".to_string(),
+                            UnstickOutcome::Terminated => "terminated".to_string(),
+                            UnstickOutcome::StillStuck => "still_stuck".to_string(),
+                            UnstickOutcome::NotStuck => "not_stuck".to_string(),
+                            UnstickOutcome::Failed(e) => format!("failed: {}", e),
                         },
                     })
                     .collect(),
-            });
-        } else {
-            println!();
+            })?;
+        }
+        if !self.json {
+            Printer::blank_line();
             if recovered > 0 {
                 println!(
                     "{} {} process{} recovered",
@@ -309,6 +343,40 @@ impl UnstickCommand {
         Ok(())
     }
 
+    /// Find the processes to unstick and try each, without prompting or printing
+    ///
+    /// With `--dry-run`, the report lists what was found but nothing is
+    /// signaled.
+    pub fn run(&self) -> Result<UnstickReport> {
+        let found = self.find()?;
+        let outcomes = if self.dry_run {
+            Vec::new()
+        } else {
+            found.iter().map(|p| (p.clone(), self.attempt(p))).collect()
+        };
+        Ok(UnstickReport { found, outcomes })
+    }
+
+    /// The target's processes, or every stuck process when no target is given
+    fn find(&self) -> Result<Vec<Process>> {
+        if let Some(ref target) = self.target {
+            self.resolve_target_processes(target)
+        } else {
+            let timeout = Duration::from_secs(self.timeout);
+            Process::find_stuck(timeout, self.cpu_threshold)
+        }
+    }
+
+    /// Try to unstick one process
+    fn attempt(&self, proc: &Process) -> UnstickOutcome {
+        // Guard against the PID having been recycled since resolution
+        if proc.is_same_process() {
+            self.attempt_unstick(proc)
+        } else {
+            UnstickOutcome::Failed("PID reused, skipping".to_string())
+        }
+    }
+
     /// Resolve target to processes
     fn resolve_target_processes(&self, target: &str) -> Result<Vec<Process>> {
         resolve_target(target).map_err(|_| ProcError::ProcessNotFound(target.to_string()))
@@ -316,98 +384,114 @@ impl UnstickCommand {
 
     /// Check if a process appears stuck (high CPU)
     fn is_stuck(&self, proc: &Process) -> bool {
-        proc.cpu_percent > 50.0
+        proc.cpu_percent > self.cpu_threshold
     }
 
     /// Attempt to unstick a process using recovery signals
     #[cfg(unix)]
-    fn attempt_unstick(&self, proc: &Process) -> Outcome {
+    fn attempt_unstick(&self, proc: &Process) -> UnstickOutcome {
         // For targeted processes, check if actually stuck
         if self.target.is_some() && !self.is_stuck(proc) {
-            return Outcome::NotStuck;
+            return UnstickOutcome::NotStuck;
         }
 
         let pid = Pid::from_raw(proc.pid as i32);
 
         // Step 1: SIGCONT (wake if stopped)
         let _ = kill(pid, Signal::SIGCONT);
-        std::thread::sleep(Duration::from_secs(1));
+        std::thread::sleep(self.timing(1).grace_period);
 
         if self.check_recovered(proc) {
-            return Outcome::Recovered;
+            return UnstickOutcome::Recovered;
         }
 
         // Step 2: SIGINT (interrupt)
         if kill(pid, Signal::SIGINT).is_err() && !proc.is_running() {
-            return Outcome::Terminated;
+            return UnstickOutcome::Terminated;
         }
-        std::thread::sleep(Duration::from_secs(3));
-
-        if !proc.is_running() {
-            return Outcome::Terminated;
+        if self.wait_for_exit(proc, self.timing(3)) {
+            return UnstickOutcome::Terminated;
         }
         if self.check_recovered(proc) {
-            return Outcome::Recovered;
+            return UnstickOutcome::Recovered;
         }
 
         // Without --force, stop here
         if !self.force {
-            return Outcome::StillStuck;
+            return UnstickOutcome::StillStuck;
         }
 
         // Step 3: SIGTERM (polite termination) - only with --force
         if proc.terminate().is_err() && !proc.is_running() {
-            return Outcome::Terminated;
+            return UnstickOutcome::Terminated;
         }
-        std::thread::sleep(Duration::from_secs(5));
-
-        if !proc.is_running() {
-            return Outcome::Terminated;
+        if self.wait_for_exit(proc, self.timing(5)) {
+            return UnstickOutcome::Terminated;
         }
 
         // Step 4: SIGKILL (force, last resort) - only with --force
         match proc.kill() {
-            Ok(()) => Outcome::Terminated,
+            Ok(()) => UnstickOutcome::Terminated,
             Err(e) => {
                 if !proc.is_running() {
-                    Outcome::Terminated
+                    UnstickOutcome::Terminated
                 } else {
-                    Outcome::Failed(e.to_string())
+                    UnstickOutcome::Failed(e.to_string())
                 }
             }
         }
     }
 
     #[cfg(not(unix))]
-    fn attempt_unstick(&self, proc: &Process) -> Outcome {
+    fn attempt_unstick(&self, proc: &Process) -> UnstickOutcome {
         // For targeted processes, check if actually stuck
         if self.target.is_some() && !self.is_stuck(proc) {
-            return Outcome::NotStuck;
+            return UnstickOutcome::NotStuck;
         }
 
         // On non-Unix, we can only terminate
         if !self.force {
-            return Outcome::StillStuck;
+            return UnstickOutcome::StillStuck;
         }
 
-        if proc.terminate().is_ok() {
-            std::thread::sleep(Duration::from_secs(3));
-            if !proc.is_running() {
-                return Outcome::Terminated;
-            }
+        if proc.terminate().is_ok() && self.wait_for_exit(proc, self.timing(3)) {
+            return UnstickOutcome::Terminated;
         }
 
         match proc.kill() {
-            Ok(()) => Outcome::Terminated,
-            Err(e) => Outcome::Failed(e.to_string()),
+            Ok(()) => UnstickOutcome::Terminated,
+            Err(e) => UnstickOutcome::Failed(e.to_string()),
+        }
+    }
+
+    /// Timing for one recovery step: --grace-period if given, else the step's
+    /// own default in seconds
+    fn timing(&self, default_secs: u64) -> EscalationTiming {
+        EscalationTiming {
+            grace_period: self
+                .grace_period
+                .unwrap_or(Duration::from_secs(default_secs)),
+            poll_interval: self
+                .poll_interval
+                .unwrap_or(EscalationTiming::DEFAULT_POLL_INTERVAL),
         }
     }
 
+    /// Wait out a step's grace period, returning early (with true) if the process exits
+    fn wait_for_exit(&self, proc: &Process, timing: EscalationTiming) -> bool {
+        let (exited, _) = wait_for_exits(
+            vec![proc.clone()],
+            timing.grace_period,
+            timing.poll_interval,
+        );
+        !exited.is_empty()
+    }
+
     /// Check if process has recovered (no longer stuck)
     #[cfg(unix)]
     fn check_recovered(&self, proc: &Process) -> bool {
         if let Ok(Some(current)) = Process::find_by_pid(proc.pid) {
-            current.cpu_percent < 10.0
+            current.cpu_percent < self.recovery_threshold
         } else {
             false
         }
@@ -420,24 +504,20 @@ impl UnstickCommand {
             "Found stuck"
         };
 
+        Printer::blank_line();
         println!(
-            "\n{} {} {} process{}:\n",
+            "{} {} {} process{}:",
             "!".yellow().bold(),
             label,
             processes.len().to_string().cyan().bold(),
             if processes.len() == 1 { "" } else { "es" }
         );
+        Printer::blank_line();
 
         for proc in processes {
             let uptime = proc
-                .start_time
-                .map(|st| {
-                    let now = std::time::SystemTime::now()
-                        .duration_since(std::time::UNIX_EPOCH)
-                        .map(|d| d.as_secs().saturating_sub(st))
-                        .unwrap_or(0);
-                    format_duration(now)
-                })
+                .uptime_secs
+                .map(format_duration)
                 .unwrap_or_else(|| "unknown".to_string());
 
             println!(
@@ -452,18 +532,6 @@ impl UnstickCommand {
     }
 }
 
-fn format_duration(secs: u64) -> String {
-    if secs < 60 {
-        format!("{}s", secs)
-    } else if secs < 3600 {
-        format!("{}m", secs / 60)
-    } else if secs < 86400 {
-        format!("{}h {}m", secs / 3600, (secs % 3600) / 60)
-    } else {
-        format!("{}d {}h", secs / 86400, (secs % 86400) / 3600)
-    }
-}
-
 #[derive(Serialize)]
 struct UnstickOutput {
     action: &'static str,
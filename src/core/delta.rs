@@ -0,0 +1,232 @@
+//! Changes between two process samples
+//!
+//! Pairs processes across samples by PID (and start time, so a recycled PID
+//! counts as one process exiting and another starting) and reports how their
+//! CPU and memory usage moved, or just which processes started and exited.
+
+use crate::core::process::split_sort_spec;
+use crate::core::{sort_by_process, Process, SortKey};
+use serde::Serialize;
+use std::collections::HashMap;
+
+/// How a process changed between samples
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum DeltaChange {
+    /// Present in both samples
+    Changed,
+    /// Only present in the second sample
+    New,
+    /// Only present in the first sample
+    Gone,
+}
+
+/// A process and how its usage moved between two samples
+#[derive(Debug, Clone, Serialize)]
+pub struct ProcessDelta {
+    /// The process as last seen (second sample, or first if it exited)
+    #[serde(flatten)]
+    pub process: Process,
+    /// Change in CPU usage, in percentage points
+    pub cpu_delta: f32,
+    /// Change in resident memory, in megabytes
+    pub memory_delta_mb: f64,
+    /// Whether the process appeared, disappeared, or was present throughout
+    pub change: DeltaChange,
+}
+
+impl ProcessDelta {
+    fn new(process: Process) -> Self {
+        Self {
+            cpu_delta: process.cpu_percent,
+            memory_delta_mb: process.memory_mb,
+            change: DeltaChange::New,
+            process,
+        }
+    }
+
+    fn gone(process: Process) -> Self {
+        Self {
+            cpu_delta: -process.cpu_percent,
+            memory_delta_mb: -process.memory_mb,
+            change: DeltaChange::Gone,
+            process,
+        }
+    }
+}
+
+/// Pair up two samples and compute each process's change
+pub fn diff_samples(before: Vec<Process>, after: Vec<Process>) -> Vec<ProcessDelta> {
+    let mut earlier: HashMap<u32, Process> = before.into_iter().map(|p| (p.pid, p)).collect();
+    let mut deltas = Vec::new();
+
+    for proc in after {
+        match earlier.remove(&proc.pid) {
+            Some(old) if old.start_time == proc.start_time => deltas.push(ProcessDelta {
+                cpu_delta: proc.cpu_percent - old.cpu_percent,
+                memory_delta_mb: proc.memory_mb - old.memory_mb,
+                change: DeltaChange::Changed,
+                process: proc,
+            }),
+            // The PID was recycled between samples
+            Some(old) => {
+                deltas.push(ProcessDelta::gone(old));
+                deltas.push(ProcessDelta::new(proc));
+            }
+            None => deltas.push(ProcessDelta::new(proc)),
+        }
+    }
+
+    deltas.extend(earlier.into_values().map(ProcessDelta::gone));
+    deltas
+}
+
+/// Whether a process started or exited between snapshots
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum LifecycleKind {
+    /// Only present in the later snapshot
+    Started,
+    /// Only present in the earlier snapshot
+    Exited,
+}
+
+/// A process starting or exiting, as seen by comparing two snapshots
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct LifecycleEvent {
+    /// What happened
+    pub event: LifecycleKind,
+    /// Process ID
+    pub pid: u32,
+    /// Process name
+    pub name: String,
+    /// When the later snapshot was taken, in seconds since the Unix epoch
+    pub ts: u64,
+}
+
+/// Report the processes that exited or started between two snapshots
+///
+/// A recycled PID yields an exit followed by a start. Exits come first, then
+/// starts, each ordered by PID.
+pub fn lifecycle_events(before: Vec<Process>, after: Vec<Process>, ts: u64) -> Vec<LifecycleEvent> {
+    let mut events: Vec<LifecycleEvent> = diff_samples(before, after)
+        .into_iter()
+        .filter_map(|d| {
+            let event = match d.change {
+                DeltaChange::Changed => return None,
+                DeltaChange::New => LifecycleKind::Started,
+                DeltaChange::Gone => LifecycleKind::Exited,
+            };
+            Some(LifecycleEvent {
+                event,
+                pid: d.process.pid,
+                name: d.process.name,
+                ts,
+            })
+        })
+        .collect();
+    events.sort_by_key(|e| (e.event == LifecycleKind::Started, e.pid));
+    events
+}
+
+/// Sort deltas by: cpu or mem (largest change either way first), or any
+/// process key understood by [`sort_by_process`]
+pub fn sort_deltas(deltas: &mut [ProcessDelta], spec: &str) {
+    let (key, descending) = split_sort_spec(spec);
+    match SortKey::parse(key) {
+        Some(SortKey::Cpu) => {
+            deltas.sort_by(|a, b| b.cpu_delta.abs().total_cmp(&a.cpu_delta.abs()))
+        }
+        Some(SortKey::Mem) => {
+            deltas.sort_by(|a, b| b.memory_delta_mb.abs().total_cmp(&a.memory_delta_mb.abs()))
+        }
+        _ => return sort_by_process(deltas, spec, |d| &d.process),
+    }
+    if descending == Some(false) {
+        deltas.reverse();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn sample(pid: u32, start_time: u64, cpu_percent: f32, memory_mb: f64) -> Process {
+        Process {
+            pid,
+            start_time: Some(start_time),
+            cpu_percent,
+            memory_mb,
+            ..Process::default()
+        }
+    }
+
+    #[test]
+    fn test_diff_samples() {
+        let before = vec![
+            sample(1, 100, 5.0, 50.0),
+            sample(2, 100, 1.0, 10.0),
+            sample(3, 100, 2.0, 20.0),
+        ];
+        let after = vec![
+            sample(1, 100, 25.0, 80.0),
+            sample(3, 200, 4.0, 5.0),
+            sample(4, 300, 3.0, 30.0),
+        ];
+        let mut deltas = diff_samples(before, after);
+        deltas.sort_by_key(|d| (d.process.pid, d.process.start_time));
+        let summary: Vec<(u32, DeltaChange, f32, f64)> = deltas
+            .iter()
+            .map(|d| (d.process.pid, d.change, d.cpu_delta, d.memory_delta_mb))
+            .collect();
+
+        assert_eq!(
+            summary,
+            vec![
+                (1, DeltaChange::Changed, 20.0, 30.0),
+                (2, DeltaChange::Gone, -1.0, -10.0),
+                (3, DeltaChange::Gone, -2.0, -20.0),
+                (3, DeltaChange::New, 4.0, 5.0),
+                (4, DeltaChange::New, 3.0, 30.0),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_lifecycle_events() {
+        let before = vec![sample(1, 100, 0.0, 0.0), sample(2, 100, 0.0, 0.0)];
+        let after = vec![
+            sample(1, 100, 9.0, 9.0),
+            sample(2, 200, 0.0, 0.0),
+            sample(5, 300, 0.0, 0.0),
+        ];
+        let events: Vec<(LifecycleKind, u32, u64)> = lifecycle_events(before, after, 42)
+            .into_iter()
+            .map(|e| (e.event, e.pid, e.ts))
+            .collect();
+
+        assert_eq!(
+            events,
+            vec![
+                (LifecycleKind::Exited, 2, 42),
+                (LifecycleKind::Started, 2, 42),
+                (LifecycleKind::Started, 5, 42),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_sort_deltas_by_magnitude() {
+        let mut deltas = diff_samples(
+            vec![sample(1, 0, 0.0, 100.0), sample(2, 0, 0.0, 100.0)],
+            vec![sample(1, 0, 0.0, 110.0), sample(2, 0, 0.0, 40.0)],
+        );
+        sort_deltas(&mut deltas, "mem");
+        let pids: Vec<u32> = deltas.iter().map(|d| d.process.pid).collect();
+        assert_eq!(pids, vec![2, 1]);
+
+        sort_deltas(&mut deltas, "mem:asc");
+        let pids: Vec<u32> = deltas.iter().map(|d| d.process.pid).collect();
+        assert_eq!(pids, vec![1, 2]);
+    }
+}
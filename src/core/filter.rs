@@ -0,0 +1,545 @@
+//! Process and port filtering
+//!
+//! Selection criteria shared by the listing commands and the library API.
+
+use crate::core::process::split_sort_spec;
+use crate::core::{glob_match, sort_processes, PortInfo, Process, Protocol, StatusFilter};
+use std::collections::HashSet;
+use std::path::{Path, PathBuf};
+
+/// Which process fields a name pattern is matched against
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum MatchField {
+    /// Executable name or command line
+    #[default]
+    Both,
+    /// Executable name only
+    Name,
+    /// Command line only
+    Command,
+}
+
+/// Criteria for selecting, ordering, and capping a list of processes
+///
+/// Fields may be added in minor releases, so start from `Filter::default()`
+/// and set the fields you need.
+#[derive(Debug, Clone, Default)]
+#[non_exhaustive]
+pub struct Filter {
+    /// Name or command line must contain this pattern (case-insensitive)
+    pub name: Option<String>,
+    /// Treat `name` as a shell-style glob anchored to the process name
+    pub glob: bool,
+    /// Fields `name` is matched against (ignored for globs)
+    pub match_field: MatchField,
+    /// Working directory must be under this path
+    pub in_dir: Option<PathBuf>,
+    /// With `in_dir`, the working directory must be that directory itself,
+    /// not one beneath it
+    pub in_dir_exact: bool,
+    /// Executable must be under this path
+    pub exe_path: Option<PathBuf>,
+    /// Minimum CPU usage percentage
+    pub min_cpu: Option<f32>,
+    /// Minimum memory usage in megabytes
+    pub min_mem: Option<f64>,
+    /// Only processes in this state
+    pub status: Option<StatusFilter>,
+    /// Drop processes whose name or command contains any of these patterns
+    pub exclude: Vec<String>,
+    /// Only processes that started less than this many seconds ago
+    pub max_age_secs: Option<u64>,
+    /// Only processes that started at least this many seconds ago
+    pub min_age_secs: Option<u64>,
+    /// Only children of this PID
+    pub parent: Option<u32>,
+    /// With `parent`, keep the whole subtree rather than direct children
+    /// (needs the full snapshot, so only [`Filter::apply`] honors it)
+    pub parent_recursive: bool,
+    /// Sort key: cpu, cputime, mem, vmem, io, nice, pid, name, with an optional `:asc`/`:desc` suffix
+    pub sort: Option<String>,
+    /// Maximum number of results
+    pub limit: Option<usize>,
+}
+
+impl Filter {
+    /// Check whether a process satisfies every criterion (sort and limit aside)
+    pub fn matches(&self, p: &Process) -> bool {
+        let in_dir = self.in_dir.as_deref().map(canonical_dir);
+        self.matches_in(p, in_dir.as_deref())
+    }
+
+    /// [`Filter::matches`] with `in_dir` already resolved by [`canonical_dir`]
+    fn matches_in(&self, p: &Process, in_dir: Option<&Path>) -> bool {
+        // Name filter
+        if let Some(ref name) = self.name {
+            let matched = match self.match_field {
+                _ if self.glob => glob_match(name, &p.name),
+                MatchField::Both => p.matches_pattern(name),
+                MatchField::Name => p.matches_name(name),
+                MatchField::Command => p.matches_command(name),
+            };
+            if !matched {
+                return false;
+            }
+        }
+
+        // Directory filter (--in)
+        if let Some(dir_path) = in_dir {
+            let within = if self.in_dir_exact {
+                same_path
+            } else {
+                path_within
+            };
+            match p.cwd {
+                Some(ref proc_cwd) if within(Path::new(proc_cwd), dir_path) => {}
+                _ => return false,
+            }
+        }
+
+        // Path filter (executable path)
+        if let Some(ref exe_path) = self.exe_path {
+            if let Some(ref proc_exe) = p.exe_path {
+                let proc_path = PathBuf::from(proc_exe);
+                if !proc_path.starts_with(exe_path) {
+                    return false;
+                }
+            } else {
+                return false;
+            }
+        }
+
+        // CPU filter
+        if let Some(min_cpu) = self.min_cpu {
+            if p.cpu_percent < min_cpu {
+                return false;
+            }
+        }
+
+        // Memory filter
+        if let Some(min_mem) = self.min_mem {
+            if p.memory_mb < min_mem {
+                return false;
+            }
+        }
+
+        // Exclude patterns
+        if self.exclude.iter().any(|pat| p.matches_pattern(pat)) {
+            return false;
+        }
+
+        // Status filter
+        if self.status.is_some_and(|status| !status.matches(p.status)) {
+            return false;
+        }
+
+        // Age window (processes with an unknown start are excluded)
+        if self.max_age_secs.is_some() || self.min_age_secs.is_some() {
+            let Some(age) = p.uptime_secs else {
+                return false;
+            };
+            if self.max_age_secs.is_some_and(|max| age >= max)
+                || self.min_age_secs.is_some_and(|min| age < min)
+            {
+                return false;
+            }
+        }
+
+        // Parent filter (direct children only; subtrees are handled in apply)
+        if let Some(ppid) = self.parent {
+            if !self.parent_recursive && p.parent_pid != Some(ppid) {
+                return false;
+            }
+        }
+
+        true
+    }
+
+    /// Count processes that fail only because the working directory or
+    /// executable path that `in_dir`/`exe_path` need could not be read
+    ///
+    /// On Linux without root this is typically other users' processes. Kernel
+    /// threads and processes without a command line (zombies) have no working
+    /// directory or executable for anyone, so they are never counted.
+    pub fn count_hidden_by_permissions(&self, processes: &[Process]) -> usize {
+        if self.in_dir.is_none() && self.exe_path.is_none() {
+            return 0;
+        }
+        let rest = Filter {
+            in_dir: None,
+            exe_path: None,
+            ..self.clone()
+        };
+        processes
+            .iter()
+            .filter(|p| !p.is_kernel_thread() && p.command.is_some())
+            .filter(|p| {
+                (self.in_dir.is_some() && p.cwd.is_none())
+                    || (self.exe_path.is_some() && p.exe_path.is_none())
+            })
+            .filter(|p| rest.matches(p))
+            .count()
+    }
+
+    /// Keep matching processes, then sort and apply the limit
+    pub fn apply(&self, mut processes: Vec<Process>) -> Vec<Process> {
+        if let Some(ppid) = self.parent.filter(|_| self.parent_recursive) {
+            let root = Process {
+                pid: ppid,
+                ..Process::default()
+            };
+            let subtree: HashSet<u32> =
+                root.descendants(&processes).iter().map(|p| p.pid).collect();
+            processes.retain(|p| subtree.contains(&p.pid));
+        }
+
+        // Resolve the directory once, not for every process
+        let in_dir = self.in_dir.as_deref().map(canonical_dir);
+        processes.retain(|p| self.matches_in(p, in_dir.as_deref()));
+
+        if let Some(ref sort) = self.sort {
+            sort_processes(&mut processes, sort);
+        }
+
+        if let Some(limit) = self.limit {
+            processes.truncate(limit);
+        }
+
+        processes
+    }
+}
+
+/// Resolve a directory through symlinks, as [`path_within`] and
+/// [`same_path`] expect, keeping it as given when that fails
+pub(crate) fn canonical_dir(dir: &Path) -> PathBuf {
+    std::fs::canonicalize(dir).unwrap_or_else(|_| dir.to_path_buf())
+}
+
+/// Check whether `path` is `dir` or lies beneath it
+///
+/// `path` is resolved through symlinks when possible, so a process in
+/// `/Volumes/data/work` matches a filter on `~/work` that links there. `dir`
+/// should already be resolved with [`canonical_dir`], once per filter rather
+/// than once per process. Falls back to the literal comparison when `path`
+/// cannot be canonicalized.
+pub(crate) fn path_within(path: &Path, dir: &Path) -> bool {
+    if path.starts_with(dir) {
+        return true;
+    }
+    std::fs::canonicalize(path).is_ok_and(|path| path.starts_with(dir))
+}
+
+/// Check whether two paths name the same directory, resolving `path` through
+/// symlinks like [`path_within`] (`dir` should come from [`canonical_dir`])
+pub(crate) fn same_path(path: &Path, dir: &Path) -> bool {
+    if path == dir {
+        return true;
+    }
+    std::fs::canonicalize(path).is_ok_and(|path| path == dir)
+}
+
+/// A key listening ports can be sorted by
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PortSortKey {
+    /// Port number
+    Port,
+    /// Owning process ID
+    Pid,
+    /// Owning process name
+    Name,
+}
+
+impl PortSortKey {
+    /// Every key, in the order help text and errors list them
+    pub const ALL: &'static [PortSortKey] =
+        &[PortSortKey::Port, PortSortKey::Pid, PortSortKey::Name];
+
+    /// Parse a key name, case-insensitively
+    pub fn parse(key: &str) -> Option<Self> {
+        match key.to_lowercase().as_str() {
+            "port" => Some(PortSortKey::Port),
+            "pid" => Some(PortSortKey::Pid),
+            "name" => Some(PortSortKey::Name),
+            _ => None,
+        }
+    }
+
+    /// The key as written on the command line
+    pub fn name(self) -> &'static str {
+        match self {
+            PortSortKey::Port => "port",
+            PortSortKey::Pid => "pid",
+            PortSortKey::Name => "name",
+        }
+    }
+}
+
+/// Criteria for selecting and ordering listening ports
+#[derive(Debug, Clone, Default)]
+pub struct PortFilter {
+    /// Process name must contain this pattern (case-insensitive)
+    pub name: Option<String>,
+    /// Only ports held by one of these PIDs (any PID when empty)
+    pub pids: Vec<u32>,
+    /// Only network-exposed ports (0.0.0.0, ::)
+    pub exposed: bool,
+    /// Only localhost ports (127.0.0.1, ::1)
+    pub local: bool,
+    /// Only ports using this protocol
+    pub protocol: Option<Protocol>,
+    /// Sort key: port, pid, name (defaults to port), with an optional `:asc`/`:desc` suffix
+    pub sort: Option<String>,
+}
+
+impl PortFilter {
+    /// Check whether a port satisfies every criterion
+    pub fn matches(&self, p: &PortInfo) -> bool {
+        // Filter by process name if specified
+        if let Some(ref filter) = self.name {
+            if !p
+                .process_name
+                .to_lowercase()
+                .contains(&filter.to_lowercase())
+            {
+                return false;
+            }
+        }
+
+        if !self.pids.is_empty() && !self.pids.contains(&p.pid) {
+            return false;
+        }
+
+        // Filter by protocol
+        if self.protocol.is_some_and(|proto| proto != p.protocol) {
+            return false;
+        }
+
+        // Filter by address exposure
+        if self.exposed
+            && !p
+                .address
+                .as_ref()
+                .map(|a| a == "0.0.0.0" || a == "::" || a == "*")
+                .unwrap_or(true)
+        {
+            return false;
+        }
+
+        if self.local
+            && !p
+                .address
+                .as_ref()
+                .map(|a| a == "127.0.0.1" || a == "::1" || a.starts_with("[::1]"))
+                .unwrap_or(false)
+        {
+            return false;
+        }
+
+        true
+    }
+
+    /// Keep matching ports, then sort them
+    pub fn apply(&self, mut ports: Vec<PortInfo>) -> Vec<PortInfo> {
+        ports.retain(|p| self.matches(p));
+
+        let (key, descending) = split_sort_spec(self.sort.as_deref().unwrap_or("port"));
+        match PortSortKey::parse(key) {
+            Some(PortSortKey::Pid) => ports.sort_by_key(|p| p.pid),
+            Some(PortSortKey::Name) => ports.sort_by_key(|p| p.process_name.to_lowercase()),
+            _ => ports.sort_by_key(|p| p.port),
+        }
+        if descending == Some(true) {
+            ports.reverse();
+        }
+
+        ports
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::ProcessStatus;
+
+    fn process(pid: u32, name: &str, cpu: f32, cwd: &str) -> Process {
+        Process {
+            pid,
+            name: name.to_string(),
+            cwd: Some(cwd.to_string()),
+            cpu_percent: cpu,
+            status: ProcessStatus::Running,
+            ..Process::default()
+        }
+    }
+
+    #[test]
+    fn test_filter_matches() {
+        let filter = Filter {
+            name: Some("node".to_string()),
+            in_dir: Some(PathBuf::from("/srv")),
+            min_cpu: Some(5.0),
+            exclude: vec!["daemon".to_string()],
+            ..Filter::default()
+        };
+        assert!(filter.matches(&process(1, "node", 10.0, "/srv/app")));
+        assert!(!filter.matches(&process(2, "node", 1.0, "/srv/app")));
+        assert!(!filter.matches(&process(3, "node", 10.0, "/home")));
+        assert!(!filter.matches(&process(4, "node-daemon", 10.0, "/srv/app")));
+        assert!(!filter.matches(&process(5, "python", 10.0, "/srv/app")));
+    }
+
+    #[test]
+    fn test_filter_match_field() {
+        let proc = Process {
+            command: Some("python app.py --config /etc/server.conf".to_string()),
+            ..process(1, "python", 0.0, "/")
+        };
+        let filter = |match_field| Filter {
+            name: Some("server".to_string()),
+            match_field,
+            ..Filter::default()
+        };
+        assert!(filter(MatchField::Both).matches(&proc));
+        assert!(filter(MatchField::Command).matches(&proc));
+        assert!(!filter(MatchField::Name).matches(&proc));
+    }
+
+    #[test]
+    fn test_filter_in_dir_exact() {
+        let filter = |in_dir_exact| Filter {
+            in_dir: Some(PathBuf::from("/srv/")),
+            in_dir_exact,
+            ..Filter::default()
+        };
+        assert!(filter(false).matches(&process(1, "app", 0.0, "/srv/app")));
+        assert!(!filter(true).matches(&process(1, "app", 0.0, "/srv/app")));
+        assert!(filter(true).matches(&process(2, "app", 0.0, "/srv")));
+        assert!(!filter(true).matches(&process(3, "app", 0.0, "/srv2")));
+    }
+
+    #[test]
+    fn test_filter_age_window() {
+        let aged = |pid, uptime_secs| Process {
+            uptime_secs,
+            ..process(pid, "app", 0.0, "/")
+        };
+        let filter = Filter {
+            max_age_secs: Some(1200),
+            min_age_secs: Some(60),
+            ..Filter::default()
+        };
+        assert!(filter.matches(&aged(1, Some(300))));
+        assert!(!filter.matches(&aged(2, Some(30))));
+        assert!(!filter.matches(&aged(3, Some(1200))));
+        assert!(!filter.matches(&aged(4, None)));
+    }
+
+    #[test]
+    fn test_count_hidden_by_permissions() {
+        let unreadable = |pid, name: &str| Process {
+            cwd: None,
+            command: Some(name.to_string()),
+            ..process(pid, name, 0.0, "/")
+        };
+        let processes = vec![
+            process(1, "node", 0.0, "/srv/app"),
+            unreadable(20, "node"),
+            unreadable(30, "python"),
+            // A zombie and a kernel thread have no cwd for anyone
+            Process {
+                command: None,
+                ..unreadable(40, "node")
+            },
+            unreadable(50, "[node]"),
+        ];
+        let filter = Filter {
+            name: Some("node".to_string()),
+            in_dir: Some(PathBuf::from("/srv")),
+            ..Filter::default()
+        };
+        assert_eq!(filter.count_hidden_by_permissions(&processes), 1);
+        assert_eq!(Filter::default().count_hidden_by_permissions(&processes), 0);
+    }
+
+    #[test]
+    fn test_filter_parent() {
+        let mut tree = vec![process(1, "init", 0.0, "/")];
+        for (pid, ppid) in [(10, 1), (11, 10), (12, 11), (20, 1)] {
+            tree.push(Process {
+                parent_pid: Some(ppid),
+                ..process(pid, "worker", 0.0, "/")
+            });
+        }
+
+        let direct = Filter {
+            parent: Some(10),
+            ..Filter::default()
+        };
+        let pids: Vec<u32> = direct.apply(tree.clone()).iter().map(|p| p.pid).collect();
+        assert_eq!(pids, vec![11]);
+
+        let recursive = Filter {
+            parent: Some(10),
+            parent_recursive: true,
+            sort: Some("pid".to_string()),
+            ..Filter::default()
+        };
+        let pids: Vec<u32> = recursive.apply(tree).iter().map(|p| p.pid).collect();
+        assert_eq!(pids, vec![11, 12]);
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_path_within_follows_symlinks() {
+        let root = std::env::temp_dir().join(format!("proc-path-within-{}", std::process::id()));
+        let real = root.join("real");
+        let link = root.join("link");
+        std::fs::create_dir_all(real.join("app")).unwrap();
+        std::os::unix::fs::symlink(&real, &link).unwrap();
+
+        assert!(path_within(&real.join("app"), &canonical_dir(&link)));
+        assert!(path_within(&link.join("app"), &canonical_dir(&real)));
+        assert!(!path_within(
+            &real.join("app"),
+            &canonical_dir(&root.join("missing"))
+        ));
+        assert!(path_within(Path::new("/gone/app"), Path::new("/gone")));
+
+        std::fs::remove_dir_all(&root).unwrap();
+    }
+
+    #[test]
+    fn test_filter_apply_sorts_and_limits() {
+        let filter = Filter {
+            sort: Some("cpu".to_string()),
+            limit: Some(2),
+            ..Filter::default()
+        };
+        let result = filter.apply(vec![
+            process(1, "a", 1.0, "/"),
+            process(2, "b", 30.0, "/"),
+            process(3, "c", 20.0, "/"),
+        ]);
+        let pids: Vec<u32> = result.iter().map(|p| p.pid).collect();
+        assert_eq!(pids, vec![2, 3]);
+    }
+
+    #[test]
+    fn test_port_filter_pids() {
+        let port = |port, pid| PortInfo {
+            port,
+            protocol: Protocol::Tcp,
+            pid,
+            process_name: "node".to_string(),
+            address: None,
+        };
+        let filter = PortFilter {
+            pids: vec![7, 9],
+            sort: Some("port:desc".to_string()),
+            ..PortFilter::default()
+        };
+        let kept = filter.apply(vec![port(3000, 7), port(4000, 8), port(5000, 9)]);
+        let ports: Vec<u16> = kept.iter().map(|p| p.port).collect();
+        assert_eq!(ports, vec![5000, 3000]);
+    }
+}
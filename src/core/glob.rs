@@ -0,0 +1,131 @@
+//! Shell-style glob matching for process names
+//!
+//! Supports `*` (any run of characters), `?` (any single character), and
+//! `[...]` character classes with ranges and `!` negation. Matching is
+//! case-insensitive and anchored to the whole name.
+
+/// Check whether a glob pattern matches the whole text (case-insensitive)
+pub fn glob_match(pattern: &str, text: &str) -> bool {
+    let pattern: Vec<char> = pattern.to_lowercase().chars().collect();
+    let text: Vec<char> = text.to_lowercase().chars().collect();
+    match_from(&pattern, &text)
+}
+
+fn match_from(pattern: &[char], text: &[char]) -> bool {
+    let (mut p, mut t) = (0, 0);
+    // Position to resume from after the most recent `*`
+    let mut star: Option<(usize, usize)> = None;
+
+    while t < text.len() {
+        if p < pattern.len() {
+            match pattern[p] {
+                '*' => {
+                    star = Some((p, t));
+                    p += 1;
+                    continue;
+                }
+                '?' => {
+                    p += 1;
+                    t += 1;
+                    continue;
+                }
+                '[' => {
+                    if let Some((matched, next)) = match_class(&pattern[p..], text[t]) {
+                        if matched {
+                            p += next;
+                            t += 1;
+                            continue;
+                        }
+                    } else if text[t] == '[' {
+                        // Unterminated class: treat `[` literally
+                        p += 1;
+                        t += 1;
+                        continue;
+                    }
+                }
+                c if c == text[t] => {
+                    p += 1;
+                    t += 1;
+                    continue;
+                }
+                _ => {}
+            }
+        }
+
+        // Mismatch: let the last `*` absorb one more character
+        match star {
+            Some((sp, st)) => {
+                p = sp + 1;
+                t = st + 1;
+                star = Some((sp, st + 1));
+            }
+            None => return false,
+        }
+    }
+
+    pattern[p..].iter().all(|&c| c == '*')
+}
+
+/// Match one character against a `[...]` class at the start of `pattern`
+///
+/// Returns whether it matched and the length of the class, or `None` if the
+/// class is unterminated.
+fn match_class(pattern: &[char], c: char) -> Option<(bool, usize)> {
+    let mut i = 1;
+    let negate = matches!(pattern.get(i), Some('!') | Some('^'));
+    if negate {
+        i += 1;
+    }
+
+    let mut matched = false;
+    let mut first = true;
+    while i < pattern.len() {
+        if pattern[i] == ']' && !first {
+            return Some((matched != negate, i + 1));
+        }
+        if i + 2 < pattern.len() && pattern[i + 1] == '-' && pattern[i + 2] != ']' {
+            if pattern[i] <= c && c <= pattern[i + 2] {
+                matched = true;
+            }
+            i += 3;
+        } else {
+            if pattern[i] == c {
+                matched = true;
+            }
+            i += 1;
+        }
+        first = false;
+    }
+
+    None
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_glob_wildcards() {
+        assert!(glob_match("python*", "python3"));
+        assert!(glob_match("python*", "Python"));
+        assert!(!glob_match("python*", "ipython"));
+        assert!(glob_match("worker-?", "worker-1"));
+        assert!(!glob_match("worker-?", "worker-10"));
+        assert!(glob_match("*sql*", "postgresql"));
+        assert!(glob_match("*", ""));
+    }
+
+    #[test]
+    fn test_glob_is_anchored() {
+        assert!(!glob_match("node", "node-daemon"));
+        assert!(glob_match("node", "node"));
+    }
+
+    #[test]
+    fn test_glob_classes() {
+        assert!(glob_match("worker-[0-9]", "worker-7"));
+        assert!(!glob_match("worker-[!0-9]", "worker-7"));
+        assert!(glob_match("[ab]c", "bc"));
+        assert!(glob_match("a[", "a["));
+    }
+}
@@ -3,13 +3,36 @@
 //! This module provides cross-platform abstractions for working with
 //! system processes and network ports.
 
+pub mod delta;
+pub mod filter;
+pub mod glob;
+pub mod pidfile;
 pub mod port;
 pub mod process;
+pub mod signal;
+pub mod system;
 pub mod target;
 
-pub use port::{parse_port, PortInfo, Protocol};
-pub use process::{Process, ProcessStatus};
+pub use delta::{
+    diff_samples, lifecycle_events, sort_deltas, DeltaChange, LifecycleEvent, LifecycleKind,
+    ProcessDelta,
+};
+pub use filter::{Filter, MatchField, PortFilter, PortSortKey};
+pub use glob::glob_match;
+pub use pidfile::PidFile;
+pub use port::{
+    group_sockets, parse_port, parse_port_range, set_debug_parse, set_port_timeout, Connection,
+    PortCheck, PortInfo, PortOwner, ProcessSockets, Protocol, DEFAULT_PORT_TIMEOUT,
+};
+pub use process::{
+    children_map, sort_by_process, sort_processes, split_threads, terminate_with_escalation,
+    Escalation, EscalationTiming, MatchSpan, MatchedOn, NameMatch, Process, ProcessStatus, SortKey,
+    StatusFilter, StuckReason, MAX_TREE_DEPTH,
+};
+pub use signal::{parse_signal, supported_signals};
+pub use system::{container_runtime, SystemSummary};
 pub use target::{
-    find_ports_for_pid, parse_target, parse_targets, resolve_target, resolve_target_single,
-    resolve_targets, TargetType,
+    find_ports_for_pid, find_ports_in_range, is_local_host, name_regex, parse_target,
+    parse_targets, resolve_exe, resolve_name, resolve_target, resolve_target_single,
+    resolve_targets, resolve_targets_glob, resolve_targets_regex, split_host_port, TargetType,
 };
@@ -0,0 +1,170 @@
+//! Daemon pidfiles
+//!
+//! Reads the PID a service recorded at startup and checks that it still
+//! belongs to that service. A pidfile left behind by a crashed daemon can
+//! name a PID the OS has since handed to something unrelated.
+
+use crate::core::Process;
+use crate::error::{ProcError, Result};
+use std::path::{Path, PathBuf};
+use std::time::UNIX_EPOCH;
+
+/// Seconds a process may appear to start after its pidfile was written
+///
+/// Start times and file times come from different clocks and roundings.
+const START_TIME_SLACK_SECS: u64 = 2;
+
+/// A parsed pidfile
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PidFile {
+    /// Where the file was read from
+    pub path: PathBuf,
+    /// The recorded process ID
+    pub pid: u32,
+    /// Process name recorded after the PID, if any
+    pub name: Option<String>,
+    /// When the file was last written (Unix timestamp)
+    pub written: Option<u64>,
+}
+
+impl PidFile {
+    /// Read and parse a pidfile
+    ///
+    /// The PID comes first; anything after it on the first line, or on the
+    /// next non-empty line, is taken as the process name.
+    pub fn read(path: &Path) -> Result<PidFile> {
+        let contents = std::fs::read_to_string(path).map_err(|e| {
+            ProcError::InvalidInput(format!("Cannot read pidfile {}: {}", path.display(), e))
+        })?;
+        let (pid, name) = parse(&contents).ok_or_else(|| {
+            ProcError::InvalidInput(format!("No PID found in pidfile {}", path.display()))
+        })?;
+        let written = std::fs::metadata(path)
+            .and_then(|m| m.modified())
+            .ok()
+            .and_then(|t| t.duration_since(UNIX_EPOCH).ok())
+            .map(|d| d.as_secs());
+
+        Ok(PidFile {
+            path: path.to_path_buf(),
+            pid,
+            name,
+            written,
+        })
+    }
+
+    /// The running process this pidfile refers to
+    ///
+    /// Fails with [`ProcError::StalePidFile`] when the PID is not running, or
+    /// is now held by a different process.
+    pub fn process(&self) -> Result<Process> {
+        self.check(Process::find_by_pid(self.pid)?)
+    }
+
+    fn check(&self, process: Option<Process>) -> Result<Process> {
+        let stale = |reason: String| {
+            Err(ProcError::StalePidFile(format!(
+                "{}: {}",
+                self.path.display(),
+                reason
+            )))
+        };
+
+        let Some(process) = process else {
+            return stale(format!("PID {} is not running", self.pid));
+        };
+        if let Some(ref name) = self.name {
+            if !process.matches_pattern(name) {
+                return stale(format!(
+                    "PID {} is now '{}', not '{}'",
+                    self.pid, process.name, name
+                ));
+            }
+        }
+        // The daemon writes its pidfile after starting, never before
+        if let (Some(written), Some(started)) = (self.written, process.start_time) {
+            if started > written + START_TIME_SLACK_SECS {
+                return stale(format!(
+                    "PID {} ('{}') started after the pidfile was written",
+                    self.pid, process.name
+                ));
+            }
+        }
+
+        Ok(process)
+    }
+
+    /// Delete the pidfile
+    pub fn remove(&self) -> Result<()> {
+        std::fs::remove_file(&self.path).map_err(|e| {
+            ProcError::SystemError(format!("Cannot remove {}: {}", self.path.display(), e))
+        })
+    }
+}
+
+/// Split pidfile contents into the PID and an optional name
+fn parse(contents: &str) -> Option<(u32, Option<String>)> {
+    let mut lines = contents.lines().map(str::trim).filter(|l| !l.is_empty());
+    let first = lines.next()?;
+    let (pid, rest) = first.split_once(char::is_whitespace).unwrap_or((first, ""));
+    let pid = pid.parse().ok().filter(|&pid| pid > 0)?;
+
+    let name = Some(rest.trim())
+        .filter(|r| !r.is_empty())
+        .or_else(|| lines.next())
+        .map(str::to_string);
+    Some((pid, name))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse() {
+        assert_eq!(parse("1234\n"), Some((1234, None)));
+        assert_eq!(parse("  1234  "), Some((1234, None)));
+        assert_eq!(
+            parse("1234 nginx\n"),
+            Some((1234, Some("nginx".to_string())))
+        );
+        assert_eq!(
+            parse("1234\n\nnginx\n"),
+            Some((1234, Some("nginx".to_string())))
+        );
+        assert_eq!(parse(""), None);
+        assert_eq!(parse("0\n"), None);
+        assert_eq!(parse("nginx\n"), None);
+    }
+
+    #[test]
+    fn test_check_stale() {
+        let pidfile = PidFile {
+            path: PathBuf::from("/run/app.pid"),
+            pid: 42,
+            name: Some("app".to_string()),
+            written: Some(1000),
+        };
+        let running = |name: &str, start_time: u64| Process {
+            pid: 42,
+            name: name.to_string(),
+            start_time: Some(start_time),
+            ..Process::default()
+        };
+
+        assert!(pidfile.check(Some(running("app", 999))).is_ok());
+        assert!(pidfile.check(Some(running("app", 1001))).is_ok());
+        assert!(matches!(
+            pidfile.check(None),
+            Err(ProcError::StalePidFile(msg)) if msg.contains("not running")
+        ));
+        assert!(matches!(
+            pidfile.check(Some(running("bash", 999))),
+            Err(ProcError::StalePidFile(msg)) if msg.contains("now 'bash', not 'app'")
+        ));
+        assert!(matches!(
+            pidfile.check(Some(running("app", 5000))),
+            Err(ProcError::StalePidFile(msg)) if msg.contains("started after")
+        ));
+    }
+}
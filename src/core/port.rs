@@ -6,10 +6,44 @@
 use crate::core::Process;
 use crate::error::{ProcError, Result};
 use serde::{Deserialize, Serialize};
-use std::process::Command;
+use std::collections::{BTreeMap, HashMap, HashSet};
+use std::io::Read;
+use std::process::{Command, Stdio};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::OnceLock;
+use std::thread;
+use std::time::{Duration, Instant};
+
+/// How long lsof/ss/netstat may run before port lookups give up (set by `--port-timeout`)
+static PORT_TIMEOUT: OnceLock<Duration> = OnceLock::new();
+
+/// Default for [`set_port_timeout`]
+pub const DEFAULT_PORT_TIMEOUT: Duration = Duration::from_secs(5);
+
+/// Limit how long each port-enumeration tool may run for the rest of the run
+///
+/// Only the first call has an effect.
+pub fn set_port_timeout(timeout: Duration) {
+    let _ = PORT_TIMEOUT.set(timeout);
+}
+
+/// Whether port parsers report the tool output lines they skip (set by `--debug-parse`)
+static DEBUG_PARSE: AtomicBool = AtomicBool::new(false);
+
+/// Report lsof/ss/netstat lines that fail to parse on stderr
+pub fn set_debug_parse(enabled: bool) {
+    DEBUG_PARSE.store(enabled, Ordering::Relaxed);
+}
+
+/// Note a tool output line the parser couldn't make sense of
+fn report_unparsed(tool: &str, line: &str) {
+    if DEBUG_PARSE.load(Ordering::Relaxed) && !line.trim().is_empty() {
+        eprintln!("debug-parse: skipped {} line: {}", tool, line);
+    }
+}
 
 /// Network protocol
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
 #[serde(rename_all = "lowercase")]
 pub enum Protocol {
     /// Transmission Control Protocol - reliable, ordered delivery
@@ -23,7 +57,7 @@ pub enum Protocol {
 pub struct PortInfo {
     /// Port number
     pub port: u16,
-    /// Protocol (TCP/UDP)
+    /// Protocol, always serialized as lowercase `"tcp"` or `"udp"`
     pub protocol: Protocol,
     /// Process ID using this port
     pub pid: u32,
@@ -34,6 +68,85 @@ pub struct PortInfo {
     pub address: Option<String>,
 }
 
+/// An established TCP connection and the process that owns it
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Connection {
+    /// Process ID owning the socket
+    pub pid: u32,
+    /// Process name
+    pub process_name: String,
+    /// Local endpoint (e.g., "127.0.0.1:3000")
+    pub local: String,
+    /// Remote endpoint (e.g., "10.0.0.5:55012")
+    pub remote: String,
+}
+
+/// Everything one process listens on and is connected to
+#[derive(Debug, Clone, Serialize)]
+pub struct ProcessSockets {
+    /// Process ID
+    pub pid: u32,
+    /// Process name
+    pub process_name: String,
+    /// Ports the process is listening on
+    pub listening: Vec<PortInfo>,
+    /// Established connections the process holds
+    pub connections: Vec<Connection>,
+}
+
+/// Whether a port that should be free has a listener
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct PortCheck {
+    /// The port checked
+    pub port: u16,
+    /// Whether anything listens on it
+    pub occupied: bool,
+    /// The process listening on it, if any
+    pub owner: Option<PortOwner>,
+}
+
+/// The process holding a port
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct PortOwner {
+    /// Process ID
+    pub pid: u32,
+    /// Process name
+    pub name: String,
+}
+
+/// Group listeners and connections by owning process, ordered by PID
+pub fn group_sockets(
+    listening: Vec<PortInfo>,
+    connections: Vec<Connection>,
+) -> Vec<ProcessSockets> {
+    fn entry<'a>(
+        by_pid: &'a mut BTreeMap<u32, ProcessSockets>,
+        pid: u32,
+        name: &str,
+    ) -> &'a mut ProcessSockets {
+        by_pid.entry(pid).or_insert_with(|| ProcessSockets {
+            pid,
+            process_name: name.to_string(),
+            listening: Vec::new(),
+            connections: Vec::new(),
+        })
+    }
+
+    let mut by_pid = BTreeMap::new();
+    for port in listening {
+        entry(&mut by_pid, port.pid, &port.process_name)
+            .listening
+            .push(port);
+    }
+    for conn in connections {
+        entry(&mut by_pid, conn.pid, &conn.process_name)
+            .connections
+            .push(conn);
+    }
+
+    by_pid.into_values().collect()
+}
+
 impl PortInfo {
     /// Get all listening ports on the system
     pub fn get_all_listening() -> Result<Vec<PortInfo>> {
@@ -51,12 +164,160 @@ impl PortInfo {
         }
     }
 
+    /// Count ESTABLISHED TCP connections per local port
+    ///
+    /// Inbound connections to a listener share its local port, so looking up a
+    /// listening port in the result gives the number of connected peers.
+    pub fn established_counts() -> Result<HashMap<u16, usize>> {
+        #[cfg(target_os = "macos")]
+        let (stdout, skip) = (
+            run_tool("lsof", &["-iTCP", "-sTCP:ESTABLISHED", "-P", "-n"])?,
+            1,
+        );
+        #[cfg(target_os = "linux")]
+        let (stdout, skip) = (run_tool("ss", &["-tn"])?, 1);
+        #[cfg(target_os = "windows")]
+        let (stdout, skip) = (run_tool("netstat", &["-ano", "-p", "TCP"])?, 0);
+
+        let mut counts = HashMap::new();
+
+        for line in stdout.lines().skip(skip) {
+            if let Some(port) = Self::parse_established_line(line) {
+                *counts.entry(port).or_insert(0) += 1;
+            }
+        }
+
+        Ok(counts)
+    }
+
+    /// Extract the local port from an established-socket line
+    #[cfg(target_os = "macos")]
+    fn parse_established_line(line: &str) -> Option<u16> {
+        // NAME column: 127.0.0.1:3000->127.0.0.1:55012 (ESTABLISHED)
+        let name_col = line.split_whitespace().find(|p| p.contains("->"))?;
+        let local = name_col.split("->").next()?;
+        local.rsplit(':').next()?.parse().ok()
+    }
+
+    /// Extract the local port from an established-socket line
+    #[cfg(target_os = "linux")]
+    fn parse_established_line(line: &str) -> Option<u16> {
+        // State  Recv-Q  Send-Q  Local:Port  Peer:Port
+        let parts: Vec<&str> = line.split_whitespace().collect();
+        if parts.len() < 5 || parts[0] != "ESTAB" {
+            return None;
+        }
+        parts[3].rsplit(':').next()?.parse().ok()
+    }
+
+    /// Extract the local port from an established-socket line
+    #[cfg(target_os = "windows")]
+    fn parse_established_line(line: &str) -> Option<u16> {
+        // Proto  Local:Port  Foreign:Port  State  PID
+        let parts: Vec<&str> = line.split_whitespace().collect();
+        if parts.len() < 5 || parts[3] != "ESTABLISHED" {
+            return None;
+        }
+        parts[1].rsplit(':').next()?.parse().ok()
+    }
+
+    /// List ESTABLISHED TCP connections along with their owning process
+    ///
+    /// Sockets whose owner can't be seen (another user's, without root) are
+    /// left out.
+    pub fn established_connections() -> Result<Vec<Connection>> {
+        #[cfg(target_os = "macos")]
+        let (stdout, parse) = (
+            run_tool("lsof", &["-iTCP", "-sTCP:ESTABLISHED", "-P", "-n"])?,
+            Self::parse_connection_line,
+        );
+        #[cfg(target_os = "linux")]
+        let (stdout, parse) = (run_tool("ss", &["-tnp"])?, Self::parse_connection_line);
+        #[cfg(target_os = "windows")]
+        let (stdout, names) = (
+            run_tool("netstat", &["-ano", "-p", "TCP"])?,
+            Self::process_names_windows(),
+        );
+        #[cfg(target_os = "windows")]
+        let parse = |line| Self::parse_connection_line(line, &names);
+
+        Ok(stdout.lines().filter_map(parse).collect())
+    }
+
+    /// Parse an established-socket line into a connection
+    #[cfg(target_os = "macos")]
+    fn parse_connection_line(line: &str) -> Option<Connection> {
+        // COMMAND PID USER FD TYPE DEVICE SIZE/OFF NODE NAME
+        // node    123 zee  20u IPv4 0x...  0t0      TCP  127.0.0.1:3000->127.0.0.1:55012 (ESTABLISHED)
+        let parts: Vec<&str> = line.split_whitespace().collect();
+        if parts.len() < 9 {
+            return None;
+        }
+        let pid: u32 = parts[1].parse().ok()?;
+        let (local, remote) = parts.iter().find_map(|p| p.split_once("->"))?;
+        Some(Connection {
+            pid,
+            process_name: parts[0].to_string(),
+            local: local.to_string(),
+            remote: remote.to_string(),
+        })
+    }
+
+    /// Parse an established-socket line into a connection
+    #[cfg(target_os = "linux")]
+    fn parse_connection_line(line: &str) -> Option<Connection> {
+        // State  Recv-Q  Send-Q  Local:Port  Peer:Port  Process
+        let parts: Vec<&str> = line.split_whitespace().collect();
+        if parts.len() < 6 || parts[0] != "ESTAB" {
+            return None;
+        }
+        let pid = Self::extract_pid_from_ss(parts[5])?;
+        Some(Connection {
+            pid,
+            process_name: Self::extract_name_from_ss(parts[5])
+                .unwrap_or_else(|| "unknown".to_string()),
+            local: parts[3].to_string(),
+            remote: parts[4].to_string(),
+        })
+    }
+
+    /// Parse an established-socket line into a connection
+    #[cfg(target_os = "windows")]
+    fn parse_connection_line(line: &str, names: &HashMap<u32, String>) -> Option<Connection> {
+        // Proto  Local:Port  Foreign:Port  State  PID
+        let parts: Vec<&str> = line.split_whitespace().collect();
+        if parts.len() < 5 || parts[3] != "ESTABLISHED" {
+            return None;
+        }
+        let pid: u32 = parts[4].parse().ok()?;
+        Some(Connection {
+            pid,
+            process_name: names
+                .get(&pid)
+                .cloned()
+                .unwrap_or_else(|| "unknown".to_string()),
+            local: parts[1].to_string(),
+            remote: parts[2].to_string(),
+        })
+    }
+
     /// Find which process is listening on a specific port
     pub fn find_by_port(port: u16) -> Result<Option<PortInfo>> {
         let ports = Self::get_all_listening()?;
         Ok(ports.into_iter().find(|p| p.port == port))
     }
 
+    /// Find up to `count` ports in an inclusive range that nothing listens on,
+    /// lowest first
+    pub fn find_free(start: u16, end: u16, count: usize) -> Result<Vec<u16>> {
+        Ok(free_ports(start, end, count, &Self::get_all_listening()?))
+    }
+
+    /// Check each port for a listener, in the order given
+    pub fn check_ports(ports: &[u16]) -> Result<Vec<PortCheck>> {
+        Ok(check_ports(ports, &Self::get_all_listening()?))
+    }
+
     /// Get the full process info for this port's process
     pub fn get_process(&self) -> Result<Option<Process>> {
         Process::find_by_pid(self.pid)
@@ -65,34 +326,33 @@ impl PortInfo {
     #[cfg(target_os = "macos")]
     fn get_listening_macos() -> Result<Vec<PortInfo>> {
         // Use lsof on macOS - only TCP LISTEN sockets
-        let output = Command::new("lsof")
-            .args(["-iTCP", "-sTCP:LISTEN", "-P", "-n"])
-            .output()
-            .map_err(|e| ProcError::SystemError(format!("Failed to run lsof: {}", e)))?;
-
-        let stdout = String::from_utf8_lossy(&output.stdout);
+        let stdout = run_tool("lsof", &["-iTCP", "-sTCP:LISTEN", "-P", "-n"])?;
         let mut ports = Vec::new();
         let mut seen = std::collections::HashSet::new();
 
         for line in stdout.lines().skip(1) {
             // Skip header
-            if let Some(port_info) = Self::parse_lsof_line(line) {
-                // Deduplicate (same port can appear multiple times for IPv4/IPv6)
-                let key = (port_info.port, port_info.pid);
-                if seen.insert(key) {
-                    ports.push(port_info);
+            match Self::parse_lsof_line(line) {
+                Some(port_info) => {
+                    // Deduplicate (same port can appear multiple times for IPv4/IPv6)
+                    let key = (port_info.port, port_info.pid);
+                    if seen.insert(key) {
+                        ports.push(port_info);
+                    }
                 }
+                None => report_unparsed("lsof", line),
             }
         }
 
         Ok(ports)
     }
 
-    #[cfg(target_os = "macos")]
+    #[cfg_attr(not(target_os = "macos"), allow(dead_code))]
     fn parse_lsof_line(line: &str) -> Option<PortInfo> {
         // lsof output format:
         // COMMAND  PID USER  FD  TYPE  DEVICE  SIZE/OFF  NODE  NAME
         // rapportd 643 zee   8u  IPv4  0x...   0t0       TCP   *:52633 (LISTEN)
+        // NAME may also be [::1]:3000, localhost:3000, or local->remote
         let parts: Vec<&str> = line.split_whitespace().collect();
         if parts.len() < 9 {
             return None;
@@ -101,30 +361,32 @@ impl PortInfo {
         let process_name = parts[0].to_string();
         let pid: u32 = parts[1].parse().ok()?;
 
-        // Find the NAME column - it's after the NODE (TCP/UDP) column
-        // The NAME looks like "*:3000" or "127.0.0.1:8080" or "*:52633 (LISTEN)"
-        // Find the column that contains a colon and looks like an address:port
-        let name_col = parts.iter().skip(8).find(|p| p.contains(':'))?;
-
-        // Remove any trailing state like "(LISTEN)" by taking just the address:port part
-        let addr_port =
-            name_col.trim_end_matches(|c: char| c == ')' || c.is_alphabetic() || c == '(');
-
-        // Split address and port
-        let last_colon = addr_port.rfind(':')?;
-        let port_str = &addr_port[last_colon + 1..];
+        // NAME follows the NODE column (TCP/UDP); a trailing "(STATE)" is not part of it
+        let node = 4 + parts[4..].iter().position(|p| *p == "TCP" || *p == "UDP")?;
+        let protocol = if parts[node] == "UDP" {
+            Protocol::Udp
+        } else {
+            Protocol::Tcp
+        };
+        let name = parts[node + 1..]
+            .iter()
+            .find(|p| !(p.starts_with('(') && p.ends_with(')')))?;
+
+        // For a connected socket, the local side comes before "->"
+        let local = name.split("->").next()?;
+        let (addr_part, port_str) = local.rsplit_once(':')?;
         let port: u16 = port_str.parse().ok()?;
 
-        let addr_part = &addr_port[..last_colon];
-        let address = Some(if addr_part == "*" || addr_part.is_empty() {
-            "0.0.0.0".to_string()
-        } else {
-            addr_part.to_string()
+        let addr_part = addr_part.trim_start_matches('[').trim_end_matches(']');
+        let address = Some(match addr_part {
+            "*" | "" => "0.0.0.0".to_string(),
+            "localhost" => "127.0.0.1".to_string(),
+            _ => addr_part.to_string(),
         });
 
         Some(PortInfo {
             port,
-            protocol: Protocol::Tcp,
+            protocol,
             pid,
             process_name,
             address,
@@ -134,17 +396,13 @@ impl PortInfo {
     #[cfg(target_os = "linux")]
     fn get_listening_linux() -> Result<Vec<PortInfo>> {
         // Use ss on Linux (more modern than netstat)
-        let output = Command::new("ss")
-            .args(["-tlnp"])
-            .output()
-            .map_err(|e| ProcError::SystemError(format!("Failed to run ss: {}", e)))?;
-
-        let stdout = String::from_utf8_lossy(&output.stdout);
+        let stdout = run_tool("ss", &["-tlnp"])?;
         let mut ports = Vec::new();
 
         for line in stdout.lines().skip(1) {
-            if let Some(port_info) = Self::parse_ss_line(line) {
-                ports.push(port_info);
+            match Self::parse_ss_line(line) {
+                Some(port_info) => ports.push(port_info),
+                None => report_unparsed("ss", line),
             }
         }
 
@@ -208,18 +466,15 @@ impl PortInfo {
     #[cfg(target_os = "windows")]
     fn get_listening_windows() -> Result<Vec<PortInfo>> {
         // Use netstat on Windows
-        let output = Command::new("netstat")
-            .args(["-ano", "-p", "TCP"])
-            .output()
-            .map_err(|e| ProcError::SystemError(format!("Failed to run netstat: {}", e)))?;
-
-        let stdout = String::from_utf8_lossy(&output.stdout);
+        let stdout = run_tool("netstat", &["-ano", "-p", "TCP"])?;
+        let names = Self::process_names_windows();
         let mut ports = Vec::new();
 
         for line in stdout.lines() {
             if line.contains("LISTENING") {
-                if let Some(port_info) = Self::parse_netstat_line(line) {
-                    ports.push(port_info);
+                match Self::parse_netstat_line(line, &names) {
+                    Some(port_info) => ports.push(port_info),
+                    None => report_unparsed("netstat", line),
                 }
             }
         }
@@ -228,7 +483,7 @@ impl PortInfo {
     }
 
     #[cfg(target_os = "windows")]
-    fn parse_netstat_line(line: &str) -> Option<PortInfo> {
+    fn parse_netstat_line(line: &str, names: &HashMap<u32, String>) -> Option<PortInfo> {
         let parts: Vec<&str> = line.split_whitespace().collect();
         if parts.len() < 5 {
             return None;
@@ -244,9 +499,10 @@ impl PortInfo {
         // PID is the last column
         let pid: u32 = parts.last()?.parse().ok()?;
 
-        // Get process name from PID
-        let process_name =
-            Self::get_process_name_windows(pid).unwrap_or_else(|| "unknown".to_string());
+        let process_name = names
+            .get(&pid)
+            .cloned()
+            .unwrap_or_else(|| "unknown".to_string());
 
         Some(PortInfo {
             port,
@@ -257,18 +513,102 @@ impl PortInfo {
         })
     }
 
+    /// Map every PID to its image name with a single `tasklist` call
+    #[cfg(target_os = "windows")]
+    fn process_names_windows() -> HashMap<u32, String> {
+        run_tool("tasklist", &["/FO", "CSV", "/NH"])
+            .map(|stdout| {
+                stdout
+                    .lines()
+                    .filter_map(Self::parse_tasklist_line)
+                    .collect()
+            })
+            .unwrap_or_default()
+    }
+
+    /// Parse a `tasklist /FO CSV /NH` line: "name.exe","1234","Console","1","12,345 K"
     #[cfg(target_os = "windows")]
-    fn get_process_name_windows(pid: u32) -> Option<String> {
-        let output = Command::new("tasklist")
-            .args(["/FI", &format!("PID eq {}", pid), "/FO", "CSV", "/NH"])
-            .output()
-            .ok()?;
+    fn parse_tasklist_line(line: &str) -> Option<(u32, String)> {
+        // Split on the quoted separators; memory usage has commas of its own
+        let mut fields = line.trim().trim_matches('"').split("\",\"");
+        let name = fields.next()?;
+        let pid = fields.next()?.parse().ok()?;
+        Some((pid, name.to_string()))
+    }
+}
+
+/// Run a port-enumeration tool and return its stdout
+///
+/// Gives up with `ProcError::Timeout` once the port timeout passes, killing
+/// the tool, so a hung lsof (e.g. on a stale network mount) can't freeze proc.
+fn run_tool(program: &str, args: &[&str]) -> Result<String> {
+    let timeout = *PORT_TIMEOUT.get().unwrap_or(&DEFAULT_PORT_TIMEOUT);
+    run_tool_with(program, args, timeout)
+}
 
-        let stdout = String::from_utf8_lossy(&output.stdout);
-        let line = stdout.lines().next()?;
-        let name = line.split(',').next()?;
-        Some(name.trim_matches('"').to_string())
+fn run_tool_with(program: &str, args: &[&str], timeout: Duration) -> Result<String> {
+    let mut child = Command::new(program)
+        .args(args)
+        .stdin(Stdio::null())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::null())
+        .spawn()
+        .map_err(|e| ProcError::SystemError(format!("Failed to run {}: {}", program, e)))?;
+
+    // Drain stdout on another thread so a large listing can't fill the pipe
+    let mut stdout = child.stdout.take().expect("stdout is piped");
+    let reader = thread::spawn(move || {
+        let mut buf = Vec::new();
+        let _ = stdout.read_to_end(&mut buf);
+        buf
+    });
+
+    let deadline = Instant::now() + timeout;
+    while child.try_wait()?.is_none() {
+        if Instant::now() >= deadline {
+            let _ = child.kill();
+            let _ = child.wait();
+            return Err(ProcError::Timeout(format!(
+                "{} did not finish within {}s; try again, or raise --port-timeout",
+                program,
+                timeout.as_secs()
+            )));
+        }
+        thread::sleep(Duration::from_millis(10));
     }
+
+    let stdout = reader.join().unwrap_or_default();
+    Ok(String::from_utf8_lossy(&stdout).into_owned())
+}
+
+/// Ports in `start..=end` not taken by any listener, lowest first, capped at `count`
+fn free_ports(start: u16, end: u16, count: usize, listening: &[PortInfo]) -> Vec<u16> {
+    let used: HashSet<u16> = listening.iter().map(|p| p.port).collect();
+    (start..=end)
+        .filter(|port| !used.contains(port))
+        .take(count)
+        .collect()
+}
+
+/// Pair each port with its first listener, if it has one
+fn check_ports(ports: &[u16], listening: &[PortInfo]) -> Vec<PortCheck> {
+    ports
+        .iter()
+        .map(|&port| {
+            let owner = listening
+                .iter()
+                .find(|p| p.port == port)
+                .map(|p| PortOwner {
+                    pid: p.pid,
+                    name: p.process_name.clone(),
+                });
+            PortCheck {
+                port,
+                occupied: owner.is_some(),
+                owner,
+            }
+        })
+        .collect()
 }
 
 /// Parse a port from various formats (":3000", "3000", etc.)
@@ -279,10 +619,100 @@ pub fn parse_port(input: &str) -> Result<u16> {
         .map_err(|_| ProcError::InvalidInput(format!("Invalid port: '{}'", input)))
 }
 
+/// Parse an inclusive port range (":3000-3999" or "3000-3999")
+pub fn parse_port_range(input: &str) -> Result<(u16, u16)> {
+    let cleaned = input.trim().trim_start_matches(':');
+    let invalid = || ProcError::InvalidInput(format!("Invalid port range: '{}'", input));
+
+    let (start, end) = cleaned.split_once('-').ok_or_else(invalid)?;
+    let start: u16 = start.trim().parse().map_err(|_| invalid())?;
+    let end: u16 = end.trim().parse().map_err(|_| invalid())?;
+    if start > end {
+        return Err(invalid());
+    }
+    Ok((start, end))
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
 
+    #[test]
+    fn test_parse_port_range() {
+        assert_eq!(parse_port_range(":3000-3999").unwrap(), (3000, 3999));
+        assert_eq!(parse_port_range("80-80").unwrap(), (80, 80));
+        assert!(parse_port_range(":3999-3000").is_err());
+        assert!(parse_port_range(":3000").is_err());
+        assert!(parse_port_range(":a-b").is_err());
+    }
+
+    #[test]
+    fn test_free_ports() {
+        let listener = |port| PortInfo {
+            port,
+            protocol: Protocol::Tcp,
+            pid: 1,
+            process_name: "node".to_string(),
+            address: None,
+        };
+        let listening = [listener(3000), listener(3002), listener(4000)];
+        assert_eq!(free_ports(3000, 3005, 1, &listening), vec![3001]);
+        assert_eq!(
+            free_ports(3000, 3005, 3, &listening),
+            vec![3001, 3003, 3004]
+        );
+        assert!(free_ports(3000, 3000, 1, &listening).is_empty());
+        assert_eq!(free_ports(65535, 65535, 5, &[]), vec![65535]);
+    }
+
+    #[test]
+    fn test_check_ports() {
+        let listening = [PortInfo {
+            port: 3000,
+            protocol: Protocol::Tcp,
+            pid: 42,
+            process_name: "node".to_string(),
+            address: None,
+        }];
+        let checks = check_ports(&[8080, 3000], &listening);
+        assert_eq!(checks[0].port, 8080);
+        assert!(!checks[0].occupied);
+        assert_eq!(checks[0].owner, None);
+        assert!(checks[1].occupied);
+        assert_eq!(
+            checks[1].owner,
+            Some(PortOwner {
+                pid: 42,
+                name: "node".to_string()
+            })
+        );
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_run_tool_timeout() {
+        let out = run_tool_with("echo", &["hi"], Duration::from_secs(5)).unwrap();
+        assert_eq!(out, "hi\n");
+
+        let started = Instant::now();
+        let err = run_tool_with("sleep", &["5"], Duration::from_millis(100)).unwrap_err();
+        assert!(matches!(err, ProcError::Timeout(_)));
+        assert!(started.elapsed() < Duration::from_secs(2));
+    }
+
+    #[test]
+    #[cfg(target_os = "windows")]
+    fn test_parse_tasklist_line() {
+        assert_eq!(
+            PortInfo::parse_tasklist_line(r#""node.exe","4120","Console","1","52,340 K""#),
+            Some((4120, "node.exe".to_string()))
+        );
+        assert_eq!(
+            PortInfo::parse_tasklist_line("INFO: No tasks are running"),
+            None
+        );
+    }
+
     #[test]
     fn test_parse_port() {
         assert_eq!(parse_port(":3000").unwrap(), 3000);
@@ -296,6 +726,113 @@ mod tests {
         assert!(parse_port("").is_err());
     }
 
+    #[test]
+    #[cfg(target_os = "linux")]
+    fn test_parse_connection_line() {
+        let conn = PortInfo::parse_connection_line(
+            r#"ESTAB 0 0 10.0.0.2:443 10.0.0.9:51234 users:(("nginx",pid=1234,fd=12))"#,
+        );
+        assert_eq!(
+            conn,
+            Some(Connection {
+                pid: 1234,
+                process_name: "nginx".to_string(),
+                local: "10.0.0.2:443".to_string(),
+                remote: "10.0.0.9:51234".to_string(),
+            })
+        );
+        // No owner visible without root
+        assert_eq!(
+            PortInfo::parse_connection_line("ESTAB 0 0 10.0.0.2:443 10.0.0.9:51234"),
+            None
+        );
+    }
+
+    #[test]
+    fn test_parse_lsof_line() {
+        let parse = |line: &str| {
+            PortInfo::parse_lsof_line(line)
+                .map(|p| (p.port, p.protocol, p.pid, p.address.unwrap_or_default()))
+        };
+        let line = |name: &str| format!("node 643 zee 8u IPv6 0x1a2b 0t0 TCP {}", name);
+
+        assert_eq!(
+            parse(&line("*:52633 (LISTEN)")),
+            Some((52633, Protocol::Tcp, 643, "0.0.0.0".to_string()))
+        );
+        assert_eq!(
+            parse(&line("[::1]:3000 (LISTEN)")),
+            Some((3000, Protocol::Tcp, 643, "::1".to_string()))
+        );
+        assert_eq!(
+            parse(&line("[fe80::1%lo0]:5000")),
+            Some((5000, Protocol::Tcp, 643, "fe80::1%lo0".to_string()))
+        );
+        assert_eq!(
+            parse(&line("localhost:8080 (LISTEN)")),
+            Some((8080, Protocol::Tcp, 643, "127.0.0.1".to_string()))
+        );
+        assert_eq!(
+            parse(&line("127.0.0.1:3000->127.0.0.1:55012 (ESTABLISHED)")),
+            Some((3000, Protocol::Tcp, 643, "127.0.0.1".to_string()))
+        );
+        assert_eq!(
+            parse("mDNSResp 321 root 7u IPv4 0x3c4d 0t0 UDP *:5353"),
+            Some((5353, Protocol::Udp, 321, "0.0.0.0".to_string()))
+        );
+        assert_eq!(parse(&line("*:* (LISTEN)")), None);
+        assert_eq!(
+            parse("COMMAND PID USER FD TYPE DEVICE SIZE/OFF NODE NAME"),
+            None
+        );
+    }
+
+    #[test]
+    fn test_group_sockets() {
+        let port = |port, pid| PortInfo {
+            port,
+            protocol: Protocol::Tcp,
+            pid,
+            process_name: "nginx".to_string(),
+            address: None,
+        };
+        let conn = |pid, name: &str| Connection {
+            pid,
+            process_name: name.to_string(),
+            local: "10.0.0.2:443".to_string(),
+            remote: "10.0.0.9:51234".to_string(),
+        };
+        let grouped = group_sockets(
+            vec![port(443, 20), port(80, 20)],
+            vec![conn(20, "nginx"), conn(20, "nginx"), conn(7, "curl")],
+        );
+        let summary: Vec<(u32, &str, usize, usize)> = grouped
+            .iter()
+            .map(|s| {
+                let name = s.process_name.as_str();
+                (s.pid, name, s.listening.len(), s.connections.len())
+            })
+            .collect();
+        assert_eq!(summary, vec![(7, "curl", 0, 1), (20, "nginx", 2, 2)]);
+    }
+
+    #[test]
+    #[cfg(target_os = "linux")]
+    fn test_parse_established_line() {
+        assert_eq!(
+            PortInfo::parse_established_line("ESTAB 0 0 127.0.0.1:3000 127.0.0.1:55836"),
+            Some(3000)
+        );
+        assert_eq!(
+            PortInfo::parse_established_line("ESTAB 0 0 [::1]:8080 [::1]:40100"),
+            Some(8080)
+        );
+        assert_eq!(
+            PortInfo::parse_established_line("TIME-WAIT 0 0 127.0.0.1:3000 127.0.0.1:55836"),
+            None
+        );
+    }
+
     #[test]
     fn test_get_listening_ports() {
         // This test may or may not find ports depending on the system
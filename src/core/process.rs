@@ -3,13 +3,22 @@
 //! Provides a unified interface for discovering and managing processes
 //! across macOS, Linux, and Windows.
 
+use crate::core::MatchField;
 use crate::error::{ProcError, Result};
 use serde::{Deserialize, Serialize};
-use std::time::Duration;
+use std::collections::{HashMap, HashSet, VecDeque};
+use std::ops::ControlFlow;
+use std::sync::OnceLock;
+use std::time::{Duration, Instant, SystemTime, UNIX_EPOCH};
 use sysinfo::{Pid, ProcessStatus as SysProcessStatus, System};
 
+/// Maximum depth followed when walking a process hierarchy
+///
+/// Guards against parent-pointer cycles in inconsistent snapshots.
+pub const MAX_TREE_DEPTH: usize = 100;
+
 /// Process status
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
 #[serde(rename_all = "lowercase")]
 pub enum ProcessStatus {
     /// Process is actively executing on CPU
@@ -20,9 +29,12 @@ pub enum ProcessStatus {
     Stopped,
     /// Process has terminated but not yet been reaped by parent
     Zombie,
+    /// Process is in uninterruptible sleep, usually blocked on I/O (state D)
+    DiskSleep,
     /// Process is being terminated
     Dead,
     /// Process status could not be determined
+    #[default]
     Unknown,
 }
 
@@ -34,18 +46,74 @@ impl From<SysProcessStatus> for ProcessStatus {
             SysProcessStatus::Stop => ProcessStatus::Stopped,
             SysProcessStatus::Zombie => ProcessStatus::Zombie,
             SysProcessStatus::Dead => ProcessStatus::Dead,
+            SysProcessStatus::UninterruptibleDiskSleep => ProcessStatus::DiskSleep,
             _ => ProcessStatus::Unknown,
         }
     }
 }
 
+/// A status to filter processes by (`--status`)
+#[derive(Debug, Clone, Copy, PartialEq, Eq, clap::ValueEnum)]
+pub enum StatusFilter {
+    /// Running or runnable
+    Running,
+    /// Sleeping
+    #[value(alias = "sleep")]
+    Sleeping,
+    /// Stopped
+    #[value(alias = "stop")]
+    Stopped,
+    /// Zombie (exited, not yet reaped)
+    Zombie,
+    /// Uninterruptible sleep, usually blocked on I/O
+    #[value(alias = "uninterruptible")]
+    Disk,
+}
+
+impl StatusFilter {
+    /// Check whether a process status passes this filter
+    pub fn matches(self, status: ProcessStatus) -> bool {
+        status
+            == match self {
+                StatusFilter::Running => ProcessStatus::Running,
+                StatusFilter::Sleeping => ProcessStatus::Sleeping,
+                StatusFilter::Stopped => ProcessStatus::Stopped,
+                StatusFilter::Zombie => ProcessStatus::Zombie,
+                StatusFilter::Disk => ProcessStatus::DiskSleep,
+            }
+    }
+}
+
+/// Why a process was reported as stuck
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum StuckReason {
+    /// CPU above the threshold for longer than the timeout
+    HighCpu,
+    /// Blocked in uninterruptible sleep (state D), e.g. on a dead NFS mount
+    UninterruptibleSleep,
+}
+
+impl StuckReason {
+    /// Human-readable description
+    pub fn label(self) -> &'static str {
+        match self {
+            StuckReason::HighCpu => "high cpu",
+            StuckReason::UninterruptibleSleep => "uninterruptible sleep",
+        }
+    }
+}
+
 /// Represents a system process with relevant information
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
 pub struct Process {
     /// Process ID
     pub pid: u32,
-    /// Process name (executable name)
+    /// Process name (executable name, or the app bundle name for macOS apps)
     pub name: String,
+    /// Name as reported by the OS, when `name` was derived from an app bundle
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub raw_name: Option<String>,
     /// Path to the executable
     #[serde(skip_serializing_if = "Option::is_none")]
     pub exe_path: Option<String>,
@@ -57,10 +125,22 @@ pub struct Process {
     pub command: Option<String>,
     /// CPU usage percentage (0.0 - 100.0+)
     pub cpu_percent: f32,
+    /// Total CPU time consumed since the process started, in seconds
+    #[serde(default)]
+    pub cpu_time_secs: u64,
     /// Memory usage in megabytes
     pub memory_mb: f64,
+    /// Virtual memory size in megabytes
+    #[serde(default)]
+    pub virtual_memory_mb: f64,
+    /// Swapped-out memory in megabytes (Linux only)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub swap_mb: Option<f64>,
     /// Process status
     pub status: ProcessStatus,
+    /// Scheduling nice value, -20 (highest priority) to 19 (Linux and macOS)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub nice: Option<i32>,
     /// User who owns the process
     #[serde(skip_serializing_if = "Option::is_none")]
     pub user: Option<String>,
@@ -70,10 +150,29 @@ pub struct Process {
     /// Process start time (Unix timestamp)
     #[serde(skip_serializing_if = "Option::is_none")]
     pub start_time: Option<u64>,
+    /// Seconds since the process started, as reported by the OS; `None` when
+    /// that would be longer than the machine has been up
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub uptime_secs: Option<u64>,
+    /// Bytes read from disk since the process started (Linux, macOS, Windows)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub read_bytes: Option<u64>,
+    /// Bytes written to disk since the process started (Linux, macOS, Windows)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub written_bytes: Option<u64>,
+    /// Disk read rate in bytes per second, when sampled over an interval
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub read_rate: Option<f64>,
+    /// Disk write rate in bytes per second, when sampled over an interval
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub write_rate: Option<f64>,
 }
 
 impl Process {
     /// Find all processes matching a name pattern (case-insensitive)
+    ///
+    /// Returns an empty list when nothing matches; whether that is an error
+    /// is up to the caller.
     pub fn find_by_name(pattern: &str) -> Result<Vec<Process>> {
         let mut sys = System::new_all();
         sys.refresh_all();
@@ -102,10 +201,6 @@ impl Process {
             })
             .collect();
 
-        if processes.is_empty() {
-            return Err(ProcError::ProcessNotFound(pattern.to_string()));
-        }
-
         Ok(processes)
     }
 
@@ -122,6 +217,35 @@ impl Process {
             .map(|proc| Process::from_sysinfo(sysinfo_pid, proc)))
     }
 
+    /// Find several processes by PID with a single targeted refresh
+    ///
+    /// Cheaper than calling [`Process::find_by_pid`] per PID, which takes a
+    /// full snapshot each time. PIDs with no running process are left out;
+    /// the rest come back in the order given, without duplicates.
+    pub fn find_by_pids(pids: &[u32]) -> Result<Vec<Process>> {
+        let mut seen = HashSet::new();
+        let targets: Vec<Pid> = pids
+            .iter()
+            .filter(|pid| seen.insert(**pid))
+            .map(|&pid| Pid::from_u32(pid))
+            .collect();
+
+        let mut sys = System::new();
+        sys.refresh_processes_specifics(
+            sysinfo::ProcessesToUpdate::Some(&targets),
+            true,
+            sysinfo::ProcessRefreshKind::everything(),
+        );
+
+        Ok(targets
+            .iter()
+            .filter_map(|pid| {
+                sys.process(*pid)
+                    .map(|proc| Process::from_sysinfo(*pid, proc))
+            })
+            .collect())
+    }
+
     /// Get all running processes
     pub fn find_all() -> Result<Vec<Process>> {
         let mut sys = System::new_all();
@@ -136,37 +260,288 @@ impl Process {
         Ok(processes)
     }
 
+    /// Get all running processes, with CPU usage measured over a short interval
+    ///
+    /// A lone snapshot has no earlier reading to compare against, so its CPU
+    /// figures can read as zero. This takes two, sysinfo's minimum update
+    /// interval apart.
+    pub fn find_all_sampled() -> Result<Vec<Process>> {
+        let mut sys = System::new_all();
+        sys.refresh_all();
+        std::thread::sleep(sysinfo::MINIMUM_CPU_UPDATE_INTERVAL);
+        sys.refresh_all();
+
+        Ok(sys
+            .processes()
+            .iter()
+            .map(|(pid, proc)| Process::from_sysinfo(*pid, proc))
+            .collect())
+    }
+
+    /// Get all running processes, with disk I/O rates measured over `interval`
+    ///
+    /// CPU usage is measured over the same interval. Processes whose I/O
+    /// counters can't be read (on Linux, other users' processes without root)
+    /// have no I/O figures rather than zeros.
+    pub fn find_all_io_sampled(interval: Duration) -> Result<Vec<Process>> {
+        let mut sys = System::new_all();
+        sys.refresh_all();
+        let started = Instant::now();
+        std::thread::sleep(interval.max(sysinfo::MINIMUM_CPU_UPDATE_INTERVAL));
+        sys.refresh_all();
+
+        let secs = started.elapsed().as_secs_f64();
+        Ok(sys
+            .processes()
+            .iter()
+            .map(|(pid, proc)| {
+                let mut process = Process::from_sysinfo(*pid, proc);
+                // sysinfo reports zeros for counters it could not read
+                if process.read_bytes == Some(0)
+                    && process.written_bytes == Some(0)
+                    && !io_readable(process.pid)
+                {
+                    process.read_bytes = None;
+                    process.written_bytes = None;
+                }
+                if process.read_bytes.is_some() {
+                    let usage = proc.disk_usage();
+                    process.read_rate = Some(usage.read_bytes as f64 / secs);
+                    process.write_rate = Some(usage.written_bytes as f64 / secs);
+                }
+                process
+            })
+            .collect())
+    }
+
+    /// Disk throughput used by the `io` sort key: the combined read and
+    /// write rate when sampled, otherwise total bytes moved
+    pub fn io_activity(&self) -> Option<f64> {
+        match (self.read_rate, self.write_rate) {
+            (Some(read), Some(write)) => Some(read + write),
+            _ => Some((self.read_bytes? + self.written_bytes?) as f64),
+        }
+    }
+
+    /// Visit each running process in turn, stopping early on `ControlFlow::Break`
+    ///
+    /// Processes are converted one at a time as they are visited, so a caller
+    /// looking for a single match never builds the full list.
+    pub fn for_each(mut f: impl FnMut(Process) -> ControlFlow<()>) -> Result<()> {
+        let mut sys = System::new_all();
+        sys.refresh_all();
+
+        for (pid, proc) in sys.processes() {
+            if f(Process::from_sysinfo(*pid, proc)).is_break() {
+                break;
+            }
+        }
+
+        Ok(())
+    }
+
     /// Find processes that appear to be stuck (high CPU, no progress)
-    /// This is a heuristic-based detection
-    pub fn find_stuck(timeout: Duration) -> Result<Vec<Process>> {
+    /// This is a heuristic-based detection: CPU above `cpu_threshold` percent
+    /// for longer than `timeout`
+    pub fn find_stuck(timeout: Duration, cpu_threshold: f32) -> Result<Vec<Process>> {
+        Ok(
+            Self::find_stuck_with_reasons(timeout, cpu_threshold, false)?
+                .into_iter()
+                .map(|(proc, _)| proc)
+                .collect(),
+        )
+    }
+
+    /// Like [`Process::find_stuck`], with the reason each process was flagged
+    ///
+    /// With `include_sleeping`, also flags processes running longer than
+    /// `timeout` that were in uninterruptible sleep in both samples, whatever
+    /// their CPU usage. There is no record of how long a process has been in
+    /// that state, so a long-lived process caught briefly in D-state twice
+    /// in a row is flagged too.
+    pub fn find_stuck_with_reasons(
+        timeout: Duration,
+        cpu_threshold: f32,
+        include_sleeping: bool,
+    ) -> Result<Vec<(Process, StuckReason)>> {
+        let in_disk_sleep = |proc: &sysinfo::Process| {
+            ProcessStatus::from(proc.status()) == ProcessStatus::DiskSleep
+        };
+
         let mut sys = System::new_all();
         sys.refresh_all();
+        let sleeping_before: HashSet<Pid> = sys
+            .processes()
+            .iter()
+            .filter(|(_, proc)| in_disk_sleep(proc))
+            .map(|(pid, _)| *pid)
+            .collect();
 
         // Wait a bit and refresh to compare
         std::thread::sleep(Duration::from_millis(500));
         sys.refresh_all();
 
         let timeout_secs = timeout.as_secs();
-        let processes: Vec<Process> = sys
+        let processes = sys
             .processes()
             .iter()
+            .filter(|(_, proc)| proc.run_time() > timeout_secs)
             .filter_map(|(pid, proc)| {
-                let cpu = proc.cpu_usage();
-                let run_time = proc.run_time();
-
                 // Heuristic: Process using significant CPU for longer than timeout
                 // and in a potentially stuck state
-                if run_time > timeout_secs && cpu > 50.0 {
-                    Some(Process::from_sysinfo(*pid, proc))
+                let reason = if proc.cpu_usage() > cpu_threshold {
+                    StuckReason::HighCpu
+                } else if include_sleeping && in_disk_sleep(proc) && sleeping_before.contains(pid) {
+                    StuckReason::UninterruptibleSleep
                 } else {
-                    None
-                }
+                    return None;
+                };
+                Some((Process::from_sysinfo(*pid, proc), reason))
             })
             .collect();
 
         Ok(processes)
     }
 
+    /// Find processes whose resident memory grew by more than `threshold_mb`
+    /// over a sampling window
+    ///
+    /// Returns each process (as seen at the end of the window) with its growth
+    /// in megabytes, largest growth first.
+    pub fn find_memory_growth(window: Duration, threshold_mb: f64) -> Result<Vec<(Process, f64)>> {
+        let mut sys = System::new_all();
+        sys.refresh_all();
+
+        let before: HashMap<Pid, u64> = sys
+            .processes()
+            .iter()
+            .map(|(pid, proc)| (*pid, proc.memory()))
+            .collect();
+
+        std::thread::sleep(window);
+        sys.refresh_all();
+
+        let mut grown: Vec<(Process, f64)> = sys
+            .processes()
+            .iter()
+            .filter_map(|(pid, proc)| {
+                let start = *before.get(pid)?;
+                let growth_mb = (proc.memory() as f64 - start as f64) / 1024.0 / 1024.0;
+                (growth_mb > threshold_mb).then(|| (Process::from_sysinfo(*pid, proc), growth_mb))
+            })
+            .collect();
+
+        grown.sort_by(|a, b| b.1.total_cmp(&a.1));
+        Ok(grown)
+    }
+
+    /// Take two snapshots `interval` apart
+    ///
+    /// CPU usage in each snapshot is measured since the previous refresh, so
+    /// the first is primed with a short warm-up sample.
+    pub fn sample_pair(interval: Duration) -> Result<(Vec<Process>, Vec<Process>)> {
+        let mut sys = System::new_all();
+        sys.refresh_all();
+        std::thread::sleep(sysinfo::MINIMUM_CPU_UPDATE_INTERVAL);
+        sys.refresh_all();
+        let snapshot = |sys: &System| -> Vec<Process> {
+            sys.processes()
+                .iter()
+                .map(|(pid, proc)| Process::from_sysinfo(*pid, proc))
+                .collect()
+        };
+
+        let before = snapshot(&sys);
+        std::thread::sleep(interval);
+        sys.refresh_all();
+        Ok((before, snapshot(&sys)))
+    }
+
+    /// Check whether the process has been reparented to init (PID 1 or 0)
+    ///
+    /// Kernel threads have no command line and are never reported.
+    pub fn is_orphan(&self) -> bool {
+        self.pid > 1 && matches!(self.parent_pid, Some(0) | Some(1)) && self.command.is_some()
+    }
+
+    /// Check whether this is a kernel thread rather than a userspace process
+    ///
+    /// On Linux that is kthreadd (PID 2) and everything it spawns. Inside a
+    /// container PID 2 is an ordinary process, so that lineage only counts
+    /// without a command line or executable, as kernel threads have. Names
+    /// shown in brackets, as `ps` does for kernel threads, count on any platform.
+    pub fn is_kernel_thread(&self) -> bool {
+        let bracketed = self.name.starts_with('[') && self.name.ends_with(']');
+        if bracketed || !cfg!(target_os = "linux") {
+            return bracketed;
+        }
+        let kthreadd_lineage = self.pid == 2 || self.parent_pid == Some(2);
+        let no_userspace = self.command.is_none() && self.exe_path.is_none();
+        (self.pid == 2 && self.name == "kthreadd") || (kthreadd_lineage && no_userspace)
+    }
+
+    /// Check whether the name or command line contains a pattern (case-insensitive)
+    pub fn matches_pattern(&self, pattern: &str) -> bool {
+        self.matches_name(pattern) || self.matches_command(pattern)
+    }
+
+    /// Check whether the executable name contains a pattern (case-insensitive)
+    ///
+    /// The OS-reported name is checked too when it differs from `name`.
+    pub fn matches_name(&self, pattern: &str) -> bool {
+        let pattern = pattern.to_lowercase();
+        std::iter::once(&self.name)
+            .chain(&self.raw_name)
+            .any(|name| name.to_lowercase().contains(&pattern))
+    }
+
+    /// Report where a name pattern matches, preferring the executable name
+    ///
+    /// `field` limits which fields are checked, as in
+    /// [`Filter::match_field`](crate::core::Filter::match_field).
+    pub fn name_match(&self, pattern: &str, field: MatchField) -> Option<NameMatch> {
+        if field != MatchField::Command && self.matches_name(pattern) {
+            return Some(NameMatch::Name(self.name.clone()));
+        }
+        if field == MatchField::Name {
+            return None;
+        }
+        let command = self.command.as_deref()?;
+        match_snippet(command, pattern).map(NameMatch::Command)
+    }
+
+    /// Locate a name pattern, preferring the executable name, as in
+    /// [`Process::name_match`]
+    pub fn match_span(&self, pattern: &str, field: MatchField) -> Option<MatchSpan> {
+        let fields = [
+            (MatchedOn::Name, Some(&self.name)),
+            (MatchedOn::RawName, self.raw_name.as_ref()),
+            (MatchedOn::Command, self.command.as_ref()),
+        ];
+        fields
+            .into_iter()
+            .filter(|(on, _)| match field {
+                MatchField::Both => true,
+                MatchField::Name => *on != MatchedOn::Command,
+                MatchField::Command => *on == MatchedOn::Command,
+            })
+            .find_map(|(matched_on, text)| {
+                let (match_start, match_end) = find_chars(text?, pattern)?;
+                Some(MatchSpan {
+                    matched_on,
+                    match_start,
+                    match_end,
+                })
+            })
+    }
+
+    /// Check whether the command line contains a pattern (case-insensitive)
+    pub fn matches_command(&self, pattern: &str) -> bool {
+        self.command
+            .as_ref()
+            .is_some_and(|c| c.to_lowercase().contains(&pattern.to_lowercase()))
+    }
+
     /// Force kill the process (SIGKILL on Unix, taskkill /F on Windows)
     pub fn kill(&self) -> Result<()> {
         let mut sys = System::new();
@@ -230,6 +605,28 @@ impl Process {
         Ok(())
     }
 
+    /// Send a signal by number (see [`crate::core::parse_signal`])
+    #[cfg(unix)]
+    pub fn send_signal(&self, signal: i32) -> Result<()> {
+        use nix::sys::signal::{kill, Signal};
+        use nix::unistd::Pid as NixPid;
+
+        let signal =
+            Signal::try_from(signal).map_err(|e| ProcError::InvalidInput(e.to_string()))?;
+        kill(NixPid::from_raw(self.pid as i32), signal)
+            .map_err(|e| ProcError::SignalError(e.to_string()))
+    }
+
+    /// Send a signal by number (Windows supports only KILL and TERM)
+    #[cfg(windows)]
+    pub fn send_signal(&self, signal: i32) -> Result<()> {
+        match signal {
+            9 => self.kill(),
+            15 => self.terminate(),
+            _ => Err(ProcError::NotSupported(format!("signal {}", signal))),
+        }
+    }
+
     /// Check if the process still exists
     pub fn exists(&self) -> bool {
         let mut sys = System::new();
@@ -240,11 +637,167 @@ impl Process {
         sys.process(Pid::from_u32(self.pid)).is_some()
     }
 
+    /// Check that the PID still refers to this process
+    ///
+    /// Compares start times so a PID recycled by a newer process is not
+    /// mistaken for the original. Returns false if the process is gone.
+    pub fn is_same_process(&self) -> bool {
+        let mut sys = System::new();
+        sys.refresh_processes(
+            sysinfo::ProcessesToUpdate::Some(&[Pid::from_u32(self.pid)]),
+            true,
+        );
+        match sys.process(Pid::from_u32(self.pid)) {
+            Some(proc) => self.start_time.is_none_or(|t| t == proc.start_time()),
+            None => false,
+        }
+    }
+
     /// Check if the process is still running (alias for exists for compatibility)
     pub fn is_running(&self) -> bool {
         self.exists()
     }
 
+    /// Poll until the process disappears or the timeout elapses
+    ///
+    /// Polling starts at 10ms and backs off to 250ms. Returns true if the
+    /// process is gone. A zombie counts as gone: it holds no resources and
+    /// only awaits reaping by its parent.
+    pub fn wait_for_exit(&self, timeout: Duration) -> bool {
+        let start = Instant::now();
+        let mut delay = Duration::from_millis(10);
+        let pid = Pid::from_u32(self.pid);
+        let mut sys = System::new();
+
+        loop {
+            sys.refresh_processes(sysinfo::ProcessesToUpdate::Some(&[pid]), true);
+            match sys.process(pid) {
+                None => return true,
+                Some(p) if p.status() == SysProcessStatus::Zombie => return true,
+                Some(_) => {}
+            }
+            let elapsed = start.elapsed();
+            if elapsed >= timeout {
+                return false;
+            }
+            std::thread::sleep(delay.min(timeout - elapsed));
+            delay = (delay * 2).min(Duration::from_millis(250));
+        }
+    }
+
+    /// Thread IDs of this process, excluding its main thread
+    ///
+    /// Empty when this PID is itself a thread rather than a process.
+    #[cfg(target_os = "linux")]
+    pub fn thread_ids(&self) -> Result<Vec<u32>> {
+        let status = std::fs::read_to_string(format!("/proc/{}/status", self.pid))
+            .map_err(|_| ProcError::ProcessGone(self.pid))?;
+        let tgid = status
+            .lines()
+            .find_map(|l| l.strip_prefix("Tgid:"))
+            .and_then(|v| v.trim().parse::<u32>().ok());
+        if tgid != Some(self.pid) {
+            return Ok(Vec::new());
+        }
+
+        let entries = std::fs::read_dir(format!("/proc/{}/task", self.pid))
+            .map_err(|_| ProcError::ProcessGone(self.pid))?;
+        Ok(entries
+            .filter_map(|e| e.ok()?.file_name().to_str()?.parse().ok())
+            .filter(|&tid| tid != self.pid)
+            .collect())
+    }
+
+    /// Thread IDs of this process, excluding its main thread
+    #[cfg(not(target_os = "linux"))]
+    pub fn thread_ids(&self) -> Result<Vec<u32>> {
+        Err(ProcError::NotSupported(
+            "listing threads requires Linux".to_string(),
+        ))
+    }
+
+    /// The process's cgroup v2 path, e.g. `/system.slice/nginx.service`
+    ///
+    /// `None` off Linux, on cgroup v1-only hosts, or when the process is gone.
+    pub fn cgroup(&self) -> Option<String> {
+        #[cfg(target_os = "linux")]
+        {
+            let content = std::fs::read_to_string(format!("/proc/{}/cgroup", self.pid)).ok()?;
+            parse_cgroup_v2(&content)
+        }
+        #[cfg(not(target_os = "linux"))]
+        {
+            None
+        }
+    }
+
+    /// Whether the process has already exited and is only waiting to be reaped
+    ///
+    /// Signals have no effect on such a process; only its parent can clear it.
+    pub fn is_defunct(&self) -> bool {
+        matches!(self.status, ProcessStatus::Zombie | ProcessStatus::Dead)
+    }
+
+    /// Direct children of this process within a snapshot
+    pub fn children(&self, snapshot: &[Process]) -> Vec<Process> {
+        snapshot
+            .iter()
+            .filter(|p| p.parent_pid == Some(self.pid) && p.pid != self.pid)
+            .cloned()
+            .collect()
+    }
+
+    /// All descendants of this process within a snapshot, breadth-first
+    ///
+    /// Stops at [`MAX_TREE_DEPTH`] and never visits a PID twice, so cycles in
+    /// the parent map cannot loop forever.
+    pub fn descendants(&self, snapshot: &[Process]) -> Vec<Process> {
+        let children = children_map(snapshot);
+        let mut seen: HashSet<u32> = HashSet::from([self.pid]);
+        let mut queue: VecDeque<(u32, usize)> = VecDeque::from([(self.pid, 0)]);
+        let mut result = Vec::new();
+
+        while let Some((pid, depth)) = queue.pop_front() {
+            if depth >= MAX_TREE_DEPTH {
+                continue;
+            }
+            for child in children.get(&pid).into_iter().flatten() {
+                if seen.insert(child.pid) {
+                    result.push((*child).clone());
+                    queue.push_back((child.pid, depth + 1));
+                }
+            }
+        }
+
+        result
+    }
+
+    /// Ancestors of this process within a snapshot, nearest parent first
+    ///
+    /// Stops at the first parent missing from the snapshot, on a repeated PID,
+    /// or after [`MAX_TREE_DEPTH`] steps.
+    pub fn ancestors(&self, snapshot: &[Process]) -> Vec<Process> {
+        let pid_map: HashMap<u32, &Process> = snapshot.iter().map(|p| (p.pid, p)).collect();
+        let mut seen: HashSet<u32> = HashSet::from([self.pid]);
+        let mut chain = Vec::new();
+        let mut current = self.parent_pid;
+
+        while let Some(pid) = current {
+            if chain.len() >= MAX_TREE_DEPTH || !seen.insert(pid) {
+                break;
+            }
+            match pid_map.get(&pid) {
+                Some(parent) => {
+                    chain.push((*parent).clone());
+                    current = parent.parent_pid;
+                }
+                None => break,
+            }
+        }
+
+        chain
+    }
+
     /// Wait for the process to terminate
     /// Returns the exit status if available
     pub fn wait(&self) -> Option<std::process::ExitStatus> {
@@ -276,26 +829,741 @@ impl Process {
         let exe_path = proc.exe().map(|p| p.to_string_lossy().to_string());
         let cwd = proc.cwd().map(|p| p.to_string_lossy().to_string());
 
+        // sysinfo reports zeros where per-process disk usage isn't available
+        let disk = cfg!(any(
+            target_os = "linux",
+            target_os = "macos",
+            target_os = "windows"
+        ))
+        .then(|| proc.disk_usage());
+
+        let mut name = proc.name().to_string_lossy().to_string();
+        let mut raw_name = None;
+        // macOS truncates GUI app names; the bundle holds the real one
+        if cfg!(target_os = "macos") {
+            if let Some(bundle) = exe_path.as_deref().and_then(app_bundle_name) {
+                if bundle != name {
+                    raw_name = Some(std::mem::replace(&mut name, bundle));
+                }
+            }
+        }
+
         Process {
             pid: pid.as_u32(),
-            name: proc.name().to_string_lossy().to_string(),
+            name,
+            raw_name,
             exe_path,
             cwd,
             command,
             cpu_percent: proc.cpu_usage(),
+            cpu_time_secs: proc.accumulated_cpu_time() / 1000,
             memory_mb: proc.memory() as f64 / 1024.0 / 1024.0,
+            virtual_memory_mb: proc.virtual_memory() as f64 / 1024.0 / 1024.0,
+            swap_mb: read_swap_mb(pid.as_u32()),
             status: ProcessStatus::from(proc.status()),
+            nice: read_nice(pid.as_u32()),
             user: proc.user_id().map(|u| u.to_string()),
             parent_pid: proc.parent().map(|p| p.as_u32()),
             start_time: Some(proc.start_time()),
+            uptime_secs: checked_uptime(
+                proc.run_time(),
+                proc.start_time(),
+                unix_now(),
+                boot_time(),
+            ),
+            read_bytes: disk.map(|d| d.total_read_bytes),
+            written_bytes: disk.map(|d| d.total_written_bytes),
+            read_rate: None,
+            write_rate: None,
+        }
+    }
+}
+
+/// Name of the innermost `.app` bundle an executable lives in
+///
+/// `/Applications/Slack.app/Contents/Frameworks/Slack Helper (GPU).app/Contents/MacOS/Slack Helper (GPU)`
+/// gives `Slack Helper (GPU)`.
+fn app_bundle_name(exe_path: &str) -> Option<String> {
+    let bundle = &exe_path[..exe_path.rfind(".app/Contents/MacOS/")?];
+    let name = bundle.rsplit('/').next()?;
+    (!name.is_empty()).then(|| name.to_string())
+}
+
+/// When the machine booted (Unix timestamp), read once per run
+fn boot_time() -> u64 {
+    static BOOT_TIME: OnceLock<u64> = OnceLock::new();
+    *BOOT_TIME.get_or_init(System::boot_time)
+}
+
+fn unix_now() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_secs())
+        .unwrap_or(0)
+}
+
+/// Seconds a process has been running, or `None` when the OS figures are nonsense
+///
+/// Some macOS versions report start times in other units, which turns into
+/// uptimes of decades. No process can have run longer than the machine has
+/// been up, so `run_time` is used only when it fits since boot, then the
+/// uptime implied by `start_time`. An unknown boot time (0) skips the check.
+fn checked_uptime(run_time: u64, start_time: u64, now: u64, boot_time: u64) -> Option<u64> {
+    if boot_time == 0 {
+        return Some(run_time);
+    }
+    // Boot and start times are rounded separately
+    let since_boot = now.saturating_sub(boot_time) + 1;
+    if run_time <= since_boot {
+        return Some(run_time);
+    }
+    (start_time.saturating_add(1) >= boot_time && start_time <= now).then(|| now - start_time)
+}
+
+/// Read a process's swapped-out memory from `/proc/<pid>/status`
+#[cfg(target_os = "linux")]
+fn read_swap_mb(pid: u32) -> Option<f64> {
+    let status = std::fs::read_to_string(format!("/proc/{}/status", pid)).ok()?;
+    parse_vm_swap(&status)
+}
+
+#[cfg(not(target_os = "linux"))]
+fn read_swap_mb(_pid: u32) -> Option<f64> {
+    None
+}
+
+/// Check whether a process's I/O counters in `/proc/<pid>/io` can be read
+#[cfg(target_os = "linux")]
+fn io_readable(pid: u32) -> bool {
+    std::fs::File::open(format!("/proc/{}/io", pid)).is_ok()
+}
+
+#[cfg(not(target_os = "linux"))]
+fn io_readable(_pid: u32) -> bool {
+    true
+}
+
+/// Read a process's nice value from `/proc/<pid>/stat`
+#[cfg(target_os = "linux")]
+fn read_nice(pid: u32) -> Option<i32> {
+    let stat = std::fs::read_to_string(format!("/proc/{}/stat", pid)).ok()?;
+    parse_stat_nice(&stat)
+}
+
+/// Read a process's nice value with `getpriority`
+#[cfg(target_os = "macos")]
+fn read_nice(pid: u32) -> Option<i32> {
+    // -1 is both a valid nice value and the error return, so check errno
+    // SAFETY: __error returns this thread's errno location, and getpriority
+    // takes plain integers
+    unsafe {
+        *libc::__error() = 0;
+        let nice = libc::getpriority(libc::PRIO_PROCESS, pid as libc::id_t);
+        (*libc::__error() == 0).then_some(nice)
+    }
+}
+
+#[cfg(not(any(target_os = "linux", target_os = "macos")))]
+fn read_nice(_pid: u32) -> Option<i32> {
+    None
+}
+
+/// Extract the nice value (field 19) from a `/proc/<pid>/stat` line
+///
+/// Fields are counted after the parenthesized command name, which may itself
+/// contain spaces or parentheses.
+#[cfg_attr(not(target_os = "linux"), allow(dead_code))]
+fn parse_stat_nice(stat: &str) -> Option<i32> {
+    let (_, rest) = stat.rsplit_once(')')?;
+    // rest starts at field 3 (state)
+    rest.split_whitespace().nth(19 - 3)?.parse().ok()
+}
+
+/// Extract the unified (`0::`) hierarchy path from a `/proc/<pid>/cgroup` body
+#[cfg_attr(not(target_os = "linux"), allow(dead_code))]
+fn parse_cgroup_v2(content: &str) -> Option<String> {
+    content
+        .lines()
+        .find_map(|l| l.strip_prefix("0::"))
+        .map(|path| path.trim().to_string())
+        .filter(|path| !path.is_empty())
+}
+
+/// Extract the `VmSwap:` line (in kB) from a `/proc/<pid>/status` body, as MB
+#[cfg_attr(not(target_os = "linux"), allow(dead_code))]
+fn parse_vm_swap(status: &str) -> Option<f64> {
+    let line = status.lines().find(|l| l.starts_with("VmSwap:"))?;
+    let kb: f64 = line.split_whitespace().nth(1)?.parse().ok()?;
+    Some(kb / 1024.0)
+}
+
+/// Separate thread entries from a snapshot
+///
+/// The OS may report threads alongside real processes. Returns the processes
+/// that remain and a map from each owning PID to its threads. Where threads
+/// cannot be listed, the snapshot is returned untouched.
+pub fn split_threads(snapshot: Vec<Process>) -> (Vec<Process>, HashMap<u32, Vec<Process>>) {
+    split_threads_with(snapshot, |p| p.thread_ids().unwrap_or_default())
+}
+
+fn split_threads_with(
+    snapshot: Vec<Process>,
+    thread_ids: impl Fn(&Process) -> Vec<u32>,
+) -> (Vec<Process>, HashMap<u32, Vec<Process>>) {
+    let owners: HashMap<u32, u32> = snapshot
+        .iter()
+        .flat_map(|p| thread_ids(p).into_iter().map(move |tid| (tid, p.pid)))
+        .collect();
+
+    let mut threads: HashMap<u32, Vec<Process>> = HashMap::new();
+    let mut processes = Vec::with_capacity(snapshot.len());
+    for proc in snapshot {
+        match owners.get(&proc.pid) {
+            Some(&owner) => threads.entry(owner).or_default().push(proc),
+            None => processes.push(proc),
+        }
+    }
+    (processes, threads)
+}
+
+/// Characters of context kept either side of a command-line match
+const MATCH_CONTEXT: usize = 12;
+
+/// Where a name pattern matched a process
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum NameMatch {
+    /// The executable name contains the pattern
+    Name(String),
+    /// The command line contains the pattern; holds the match with a little
+    /// context either side, e.g. `...g /etc/server.conf --po...`
+    Command(String),
+}
+
+/// Field a name pattern was found in
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum MatchedOn {
+    /// The process name
+    Name,
+    /// The OS-reported name, where it differs from the name
+    RawName,
+    /// The command line
+    Command,
+}
+
+/// Where a name pattern matched a process
+///
+/// Offsets count characters, not bytes, into the field named by `matched_on`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+pub struct MatchSpan {
+    /// Field the pattern was found in
+    pub matched_on: MatchedOn,
+    /// Offset of the first matched character
+    pub match_start: usize,
+    /// Offset just past the last matched character
+    pub match_end: usize,
+}
+
+/// Character offsets of the first case-insensitive occurrence of `pattern`
+fn find_chars(text: &str, pattern: &str) -> Option<(usize, usize)> {
+    let pattern = pattern.to_lowercase();
+    // Lowercase one character at a time so offsets still count original characters
+    let lowered: Vec<String> = text.chars().map(|c| c.to_lowercase().collect()).collect();
+    (0..=lowered.len()).find_map(|start| {
+        let mut matched = String::new();
+        for (end, lower) in lowered.iter().enumerate().skip(start) {
+            if matched.len() >= pattern.len() {
+                return (matched == pattern).then_some((start, end));
+            }
+            matched.push_str(lower);
+        }
+        (matched == pattern).then_some((start, lowered.len()))
+    })
+}
+
+/// Cut `text` down to the first case-insensitive occurrence of `pattern`,
+/// keeping [`MATCH_CONTEXT`] characters either side, on one line
+fn match_snippet(text: &str, pattern: &str) -> Option<String> {
+    let lower = text.to_lowercase();
+    let pattern = pattern.to_lowercase();
+    let start = lower.find(&pattern)?;
+    // Lowercasing changed byte offsets, so they don't line up with `text`
+    if lower.len() != text.len() {
+        return Some(text.to_string());
+    }
+
+    let end = start + pattern.len();
+    let from = text[..start]
+        .char_indices()
+        .rev()
+        .take(MATCH_CONTEXT)
+        .last()
+        .map_or(start, |(i, _)| i);
+    let to = text[end..]
+        .char_indices()
+        .nth(MATCH_CONTEXT)
+        .map_or(text.len(), |(i, _)| end + i);
+
+    let excerpt: Vec<&str> = text[from..to].split_whitespace().collect();
+    Some(format!(
+        "{}{}{}",
+        if from > 0 { "..." } else { "" },
+        excerpt.join(" "),
+        if to < text.len() { "..." } else { "" }
+    ))
+}
+
+/// How long a SIGKILLed process gets to disappear before it counts as a failure
+const KILL_WAIT: Duration = Duration::from_secs(5);
+
+/// How long to wait between signals, and how closely to watch for exits meanwhile
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct EscalationTiming {
+    /// How long processes get to exit after SIGTERM before SIGKILL
+    pub grace_period: Duration,
+    /// Longest gap between checks on whether the processes have exited
+    pub poll_interval: Duration,
+}
+
+impl EscalationTiming {
+    /// Grace period used when none is given
+    pub const DEFAULT_GRACE_PERIOD: Duration = Duration::from_secs(10);
+    /// Poll interval used when none is given
+    pub const DEFAULT_POLL_INTERVAL: Duration = Duration::from_millis(250);
+
+    /// Default timing with the given grace period
+    pub fn with_grace(grace_period: Duration) -> Self {
+        Self {
+            grace_period,
+            ..Self::default()
+        }
+    }
+}
+
+impl Default for EscalationTiming {
+    fn default() -> Self {
+        Self {
+            grace_period: Self::DEFAULT_GRACE_PERIOD,
+            poll_interval: Self::DEFAULT_POLL_INTERVAL,
+        }
+    }
+}
+
+/// Outcome of [`terminate_with_escalation`]
+#[derive(Debug, Default)]
+pub struct Escalation {
+    /// Processes that exited after SIGTERM
+    pub terminated: Vec<Process>,
+    /// Processes that ignored SIGTERM and were killed with SIGKILL
+    pub escalated: Vec<Process>,
+    /// Processes that could not be stopped, with the reason
+    pub failed: Vec<(Process, ProcError)>,
+}
+
+/// Send SIGTERM to each process, then SIGKILL any still running after the
+/// grace period
+///
+/// Every process shares one grace period, and the SIGKILLed stragglers share
+/// one wait after it, so the worst case is about the grace period however
+/// many processes there are. Callers should drop recycled PIDs and zombies
+/// beforehand.
+pub fn terminate_with_escalation(processes: Vec<Process>, timing: EscalationTiming) -> Escalation {
+    let mut report = Escalation::default();
+    let mut pending = Vec::new();
+    for proc in processes {
+        match proc.terminate() {
+            Ok(()) => pending.push(proc),
+            Err(e) => report.failed.push((proc, e)),
+        }
+    }
+
+    let (exited, stragglers) = wait_for_exits(pending, timing.grace_period, timing.poll_interval);
+    report.terminated = exited;
+
+    let mut killed = Vec::new();
+    for proc in stragglers {
+        match proc.kill() {
+            Ok(()) => killed.push(proc),
+            Err(e) => report.failed.push((proc, e)),
+        }
+    }
+
+    let (exited, survivors) = wait_for_exits(killed, KILL_WAIT, timing.poll_interval);
+    report.escalated = exited;
+    for proc in survivors {
+        let err = ProcError::Timeout(format!("PID {} still running after SIGKILL", proc.pid));
+        report.failed.push((proc, err));
+    }
+
+    report
+}
+
+/// Poll a set of processes together until all are gone or `timeout` elapses
+///
+/// Polling starts at 10ms and backs off to `poll_interval`. Returns the
+/// processes that exited and those still running, each in their original
+/// order. Zombies and recycled PIDs count as exited, as in
+/// [`Process::wait_for_exit`].
+pub(crate) fn wait_for_exits(
+    processes: Vec<Process>,
+    timeout: Duration,
+    poll_interval: Duration,
+) -> (Vec<Process>, Vec<Process>) {
+    let start = Instant::now();
+    let mut delay = Duration::from_millis(10).min(poll_interval);
+    let mut sys = System::new();
+    let mut running = vec![true; processes.len()];
+
+    loop {
+        let pids: Vec<Pid> = processes
+            .iter()
+            .zip(&running)
+            .filter(|(_, &alive)| alive)
+            .map(|(p, _)| Pid::from_u32(p.pid))
+            .collect();
+        sys.refresh_processes(sysinfo::ProcessesToUpdate::Some(&pids), true);
+
+        for (proc, alive) in processes.iter().zip(running.iter_mut()) {
+            *alive = *alive
+                && match sys.process(Pid::from_u32(proc.pid)) {
+                    None => false,
+                    Some(p) if p.status() == SysProcessStatus::Zombie => false,
+                    Some(p) => proc.start_time.is_none_or(|t| t == p.start_time()),
+                };
+        }
+
+        let elapsed = start.elapsed();
+        if !running.contains(&true) || elapsed >= timeout {
+            break;
         }
+        std::thread::sleep(delay.min(timeout - elapsed));
+        delay = (delay * 2).min(poll_interval);
     }
+
+    let (exited, still_running): (Vec<_>, Vec<_>) = processes
+        .into_iter()
+        .zip(running)
+        .partition(|(_, alive)| !alive);
+    (
+        exited.into_iter().map(|(p, _)| p).collect(),
+        still_running.into_iter().map(|(p, _)| p).collect(),
+    )
+}
+
+/// Build a parent PID -> children map from a snapshot
+///
+/// Processes that list themselves as their own parent are left out.
+pub fn children_map(snapshot: &[Process]) -> HashMap<u32, Vec<&Process>> {
+    let mut map: HashMap<u32, Vec<&Process>> = HashMap::new();
+    for proc in snapshot {
+        if let Some(ppid) = proc.parent_pid.filter(|&ppid| ppid != proc.pid) {
+            map.entry(ppid).or_default().push(proc);
+        }
+    }
+    map
+}
+
+/// A key processes can be sorted by
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SortKey {
+    /// CPU usage
+    Cpu,
+    /// Total CPU time consumed
+    CpuTime,
+    /// Resident memory
+    Mem,
+    /// Virtual memory
+    Vmem,
+    /// Disk I/O (see [`Process::io_activity`])
+    Io,
+    /// Nice value
+    Nice,
+    /// Process ID
+    Pid,
+    /// Process name
+    Name,
+}
+
+impl SortKey {
+    /// Every key, in the order help text and errors list them
+    pub const ALL: &'static [SortKey] = &[
+        SortKey::Cpu,
+        SortKey::CpuTime,
+        SortKey::Mem,
+        SortKey::Vmem,
+        SortKey::Io,
+        SortKey::Nice,
+        SortKey::Pid,
+        SortKey::Name,
+    ];
+
+    /// Parse a key name, case-insensitively; `time`, `memory`, and `virt`
+    /// are accepted as aliases
+    pub fn parse(key: &str) -> Option<Self> {
+        match key.to_lowercase().as_str() {
+            "cpu" => Some(SortKey::Cpu),
+            "cputime" | "time" => Some(SortKey::CpuTime),
+            "mem" | "memory" => Some(SortKey::Mem),
+            "vmem" | "virt" => Some(SortKey::Vmem),
+            "io" => Some(SortKey::Io),
+            "nice" => Some(SortKey::Nice),
+            "pid" => Some(SortKey::Pid),
+            "name" => Some(SortKey::Name),
+            _ => None,
+        }
+    }
+
+    /// The key as written on the command line
+    pub fn name(self) -> &'static str {
+        match self {
+            SortKey::Cpu => "cpu",
+            SortKey::CpuTime => "cputime",
+            SortKey::Mem => "mem",
+            SortKey::Vmem => "vmem",
+            SortKey::Io => "io",
+            SortKey::Nice => "nice",
+            SortKey::Pid => "pid",
+            SortKey::Name => "name",
+        }
+    }
+
+    /// Whether the key naturally sorts largest first
+    fn descending(self) -> bool {
+        matches!(
+            self,
+            SortKey::Cpu | SortKey::CpuTime | SortKey::Mem | SortKey::Vmem | SortKey::Io
+        )
+    }
+}
+
+/// Sort items that carry a process by a [`SortKey`] spec: cpu, cputime, mem,
+/// vmem, io, nice, pid, name
+///
+/// CPU, CPU time, memory, and I/O sort descending; nice, PID, and name ascending. A
+/// `:asc` or `:desc` suffix (e.g. `cpu:asc`) overrides the direction. Unknown
+/// keys keep the existing order.
+pub fn sort_by_process<T>(items: &mut [T], spec: &str, process: impl Fn(&T) -> &Process) {
+    let (key, descending) = split_sort_spec(spec);
+    let Some(key) = SortKey::parse(key) else {
+        return; // Keep default order
+    };
+
+    match key {
+        SortKey::Cpu => {
+            items.sort_by(|a, b| process(b).cpu_percent.total_cmp(&process(a).cpu_percent))
+        }
+        SortKey::CpuTime => {
+            items.sort_by_key(|item| std::cmp::Reverse(process(item).cpu_time_secs))
+        }
+        SortKey::Mem => items.sort_by(|a, b| process(b).memory_mb.total_cmp(&process(a).memory_mb)),
+        SortKey::Vmem => items.sort_by(|a, b| {
+            process(b)
+                .virtual_memory_mb
+                .total_cmp(&process(a).virtual_memory_mb)
+        }),
+        // Busiest first; unknown values last
+        SortKey::Io => {
+            items.sort_by(
+                |a, b| match (process(a).io_activity(), process(b).io_activity()) {
+                    (Some(a), Some(b)) => b.total_cmp(&a),
+                    (a, b) => b.is_some().cmp(&a.is_some()),
+                },
+            )
+        }
+        // Highest priority first; unknown values last
+        SortKey::Nice => items.sort_by_key(|item| {
+            let nice = process(item).nice;
+            (nice.is_none(), nice)
+        }),
+        SortKey::Pid => items.sort_by_key(|item| process(item).pid),
+        SortKey::Name => items.sort_by_key(|item| process(item).name.to_lowercase()),
+    }
+
+    if descending.is_some_and(|desc| desc != key.descending()) {
+        items.reverse();
+    }
+}
+
+/// Split a sort spec like `cpu:asc` into its key and requested direction
+///
+/// The direction is `Some(true)` for `:desc`, `Some(false)` for `:asc`, and
+/// `None` when absent or unrecognized, leaving the key's natural order.
+pub(crate) fn split_sort_spec(spec: &str) -> (&str, Option<bool>) {
+    match spec.rsplit_once(':') {
+        Some((key, dir)) => match dir.to_lowercase().as_str() {
+            "asc" => (key, Some(false)),
+            "desc" => (key, Some(true)),
+            _ => (key, None),
+        },
+        None => (spec, None),
+    }
+}
+
+/// Sort processes by a [`SortKey`] spec (optionally `:asc`/`:desc`)
+pub fn sort_processes(processes: &mut [Process], key: &str) {
+    sort_by_process(processes, key, |p| p)
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
 
+    #[test]
+    fn test_sort_by_cputime() {
+        let mut procs: Vec<Process> = [(1, 5), (2, 300), (3, 40)]
+            .into_iter()
+            .map(|(pid, cpu_time_secs)| Process {
+                pid,
+                cpu_time_secs,
+                ..Process::default()
+            })
+            .collect();
+        sort_processes(&mut procs, "cputime");
+        let pids: Vec<u32> = procs.iter().map(|p| p.pid).collect();
+        assert_eq!(pids, vec![2, 3, 1]);
+    }
+
+    #[test]
+    fn test_sort_direction_suffix() {
+        let mut procs: Vec<Process> = [(1, 5.0), (2, 50.0), (3, 20.0)]
+            .into_iter()
+            .map(|(pid, cpu_percent)| Process {
+                pid,
+                cpu_percent,
+                ..Process::default()
+            })
+            .collect();
+        let pids = |procs: &[Process]| procs.iter().map(|p| p.pid).collect::<Vec<_>>();
+
+        sort_processes(&mut procs, "cpu:asc");
+        assert_eq!(pids(&procs), vec![1, 3, 2]);
+        sort_processes(&mut procs, "CPU:DESC");
+        assert_eq!(pids(&procs), vec![2, 3, 1]);
+        sort_processes(&mut procs, "pid:desc");
+        assert_eq!(pids(&procs), vec![3, 2, 1]);
+        sort_processes(&mut procs, "pid:asc");
+        assert_eq!(pids(&procs), vec![1, 2, 3]);
+    }
+
+    #[test]
+    fn test_split_threads() {
+        let snapshot = vec![
+            child_of(10, 1),
+            child_of(11, 10),
+            child_of(12, 10),
+            child_of(20, 10),
+        ];
+        let (processes, threads) = split_threads_with(snapshot, |p| match p.pid {
+            10 => vec![11, 12],
+            _ => vec![],
+        });
+        let pids: Vec<u32> = processes.iter().map(|p| p.pid).collect();
+        assert_eq!(pids, vec![10, 20]);
+        let tids: Vec<u32> = threads[&10].iter().map(|p| p.pid).collect();
+        assert_eq!(tids, vec![11, 12]);
+    }
+
+    #[test]
+    fn test_app_bundle_name() {
+        assert_eq!(
+            app_bundle_name("/Applications/Slack.app/Contents/Frameworks/Slack Helper (GPU).app/Contents/MacOS/Slack Helper (GPU)").as_deref(),
+            Some("Slack Helper (GPU)")
+        );
+        assert_eq!(
+            app_bundle_name("/Applications/Visual Studio Code.app/Contents/MacOS/Electron")
+                .as_deref(),
+            Some("Visual Studio Code")
+        );
+        assert_eq!(app_bundle_name("/usr/bin/python3"), None);
+    }
+
+    #[test]
+    fn test_matches_name_checks_raw_name() {
+        let proc = Process {
+            name: "Visual Studio Code".to_string(),
+            raw_name: Some("Electron".to_string()),
+            ..Process::default()
+        };
+        assert!(proc.matches_name("visual"));
+        assert!(proc.matches_name("electron"));
+        assert!(!proc.matches_name("slack"));
+    }
+
+    #[test]
+    fn test_parse_cgroup_v2() {
+        assert_eq!(
+            parse_cgroup_v2("0::/system.slice/nginx.service\n").as_deref(),
+            Some("/system.slice/nginx.service")
+        );
+        let hybrid = "12:cpu,cpuacct:/user.slice\n1:name=systemd:/user.slice\n0::/user.slice/session-3.scope\n";
+        assert_eq!(
+            parse_cgroup_v2(hybrid).as_deref(),
+            Some("/user.slice/session-3.scope")
+        );
+        assert_eq!(parse_cgroup_v2("4:memory:/docker/abc\n"), None);
+    }
+
+    #[test]
+    fn test_parse_vm_swap() {
+        let status = "Name:\tbash\nVmRSS:\t    4096 kB\nVmSwap:\t    2048 kB\n";
+        assert_eq!(parse_vm_swap(status), Some(2.0));
+        assert_eq!(parse_vm_swap("Name:\tkthreadd\n"), None);
+    }
+
+    #[test]
+    fn test_is_kernel_thread() {
+        let proc = |pid, parent_pid, name: &str| Process {
+            pid,
+            parent_pid,
+            name: name.to_string(),
+            ..Process::default()
+        };
+        assert!(proc(40, Some(7), "[kworker/0:1]").is_kernel_thread());
+        assert!(!proc(500, Some(1), "sshd").is_kernel_thread());
+        assert!(!proc(1, Some(0), "systemd").is_kernel_thread());
+        if cfg!(target_os = "linux") {
+            assert!(proc(2, Some(0), "kthreadd").is_kernel_thread());
+            assert!(proc(40, Some(2), "kworker/0:1").is_kernel_thread());
+
+            // In a container PID 2 and its children are ordinary processes
+            let userspace = |pid, parent_pid, name: &str| Process {
+                command: Some(format!("/usr/bin/{}", name)),
+                exe_path: Some(format!("/usr/bin/{}", name)),
+                ..proc(pid, parent_pid, name)
+            };
+            assert!(!userspace(2, Some(1), "node").is_kernel_thread());
+            assert!(!userspace(40, Some(2), "node").is_kernel_thread());
+        }
+    }
+
+    #[test]
+    fn test_checked_uptime() {
+        let boot = 1_700_000_000;
+        let now = boot + 3600;
+
+        // Plausible run time is kept
+        assert_eq!(checked_uptime(600, now - 600, now, boot), Some(600));
+        // Run time longer than the machine has been up: fall back to start time
+        assert_eq!(checked_uptime(1_700_000_000, now - 60, now, boot), Some(60));
+        // Epoch-zero and future start times with a bogus run time: unknown
+        assert_eq!(checked_uptime(now, 0, now, boot), None);
+        assert_eq!(checked_uptime(u64::MAX / 2, now + 86400, now, boot), None);
+        // Garbage start time at the top of the range must not overflow
+        assert_eq!(checked_uptime(u64::MAX / 2, u64::MAX, now, boot), None);
+        // Unknown boot time: trust the OS
+        assert_eq!(checked_uptime(600, 0, now, 0), Some(600));
+    }
+
+    #[test]
+    fn test_parse_stat_nice() {
+        let stat = "4242 (tmux: server (1)) S 1 4242 4242 0 -1 4194560 1203 0 0 0 \
+                    35 12 0 0 20 5 1 0 8123 9158656 1024 18446744073709551615";
+        assert_eq!(parse_stat_nice(stat), Some(5));
+        let stat = "7 (kworker) I 2 0 0 0 -1 69238880 0 0 0 0 0 0 0 0 0 -20 1 0 3 0 0";
+        assert_eq!(parse_stat_nice(stat), Some(-20));
+        assert_eq!(parse_stat_nice("7 (short) S 1"), None);
+    }
+
     #[test]
     fn test_find_all_processes() {
         let processes = Process::find_all().unwrap();
@@ -309,9 +1577,244 @@ mod tests {
         assert!(process.is_some(), "Should find own process");
     }
 
+    #[test]
+    fn test_for_each_stops_early() {
+        let mut seen = 0;
+        Process::for_each(|_| {
+            seen += 1;
+            ControlFlow::Break(())
+        })
+        .unwrap();
+        assert_eq!(seen, 1);
+    }
+
+    #[test]
+    fn test_for_each_finds_self() {
+        let own_pid = std::process::id();
+        let mut found = None;
+        Process::for_each(|proc| {
+            if proc.pid == own_pid {
+                found = Some(proc);
+                return ControlFlow::Break(());
+            }
+            ControlFlow::Continue(())
+        })
+        .unwrap();
+        assert!(found.is_some(), "Should visit own process");
+    }
+
+    #[test]
+    fn test_is_same_process_detects_reuse() {
+        let own = Process::find_by_pid(std::process::id())
+            .unwrap()
+            .expect("Should find own process");
+        assert!(own.is_same_process());
+
+        let recycled = Process {
+            start_time: own.start_time.map(|t| t + 1),
+            ..own
+        };
+        assert!(!recycled.is_same_process());
+    }
+
+    #[test]
+    fn test_stuck_reason_serializes_snake_case() {
+        assert_eq!(
+            serde_json::to_string(&StuckReason::UninterruptibleSleep).unwrap(),
+            "\"uninterruptible_sleep\""
+        );
+        assert_eq!(StuckReason::HighCpu.label(), "high cpu");
+    }
+
+    fn child_of(pid: u32, ppid: u32) -> Process {
+        Process {
+            pid,
+            name: format!("p{}", pid),
+            parent_pid: Some(ppid),
+            ..Process::default()
+        }
+    }
+
+    #[test]
+    fn test_find_by_pids() {
+        let own = std::process::id();
+        let found = Process::find_by_pids(&[own, u32::MAX - 1, own]).unwrap();
+        assert_eq!(found.iter().map(|p| p.pid).collect::<Vec<_>>(), vec![own]);
+        assert!(found[0].command.is_some());
+    }
+
+    #[cfg(target_os = "linux")]
+    #[test]
+    fn test_long_command_line_is_complete() {
+        let long_arg = "a".repeat(4096);
+        // The trailing `:` keeps sh from exec'ing sleep and dropping its arguments
+        let mut child = std::process::Command::new("sh")
+            .args(["-c", "sleep 30; :", "sh", &long_arg, "tail"])
+            .spawn()
+            .unwrap();
+        let proc = Process::find_by_pid(child.id()).unwrap().unwrap();
+        child.kill().unwrap();
+        child.wait().unwrap();
+
+        let command = proc.command.unwrap();
+        assert!(command.ends_with(&format!(" {} tail", long_arg)));
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_wait_for_exits() {
+        let mut done = std::process::Command::new("sleep")
+            .arg("30")
+            .spawn()
+            .unwrap();
+        let mut lingering = std::process::Command::new("sleep")
+            .arg("30")
+            .spawn()
+            .unwrap();
+        let done_proc = Process::find_by_pid(done.id()).unwrap().unwrap();
+        let lingering_proc = Process::find_by_pid(lingering.id()).unwrap().unwrap();
+
+        done.kill().unwrap();
+        done.wait().unwrap();
+        let (exited, running) = wait_for_exits(
+            vec![done_proc, lingering_proc],
+            Duration::from_millis(200),
+            EscalationTiming::DEFAULT_POLL_INTERVAL,
+        );
+
+        assert_eq!(
+            exited.iter().map(|p| p.pid).collect::<Vec<_>>(),
+            vec![done.id()]
+        );
+        assert_eq!(
+            running.iter().map(|p| p.pid).collect::<Vec<_>>(),
+            vec![lingering.id()]
+        );
+        lingering.kill().unwrap();
+        lingering.wait().unwrap();
+    }
+
+    #[test]
+    fn test_children_and_descendants() {
+        let snapshot = vec![
+            child_of(1, 0),
+            child_of(2, 1),
+            child_of(3, 1),
+            child_of(4, 2),
+            child_of(5, 4),
+            child_of(6, 9),
+        ];
+        let root = &snapshot[0];
+
+        let children: Vec<u32> = root.children(&snapshot).iter().map(|p| p.pid).collect();
+        assert_eq!(children, vec![2, 3]);
+
+        let descendants: Vec<u32> = root.descendants(&snapshot).iter().map(|p| p.pid).collect();
+        assert_eq!(descendants, vec![2, 3, 4, 5]);
+    }
+
+    #[test]
+    fn test_ancestors() {
+        let snapshot = vec![child_of(1, 0), child_of(2, 1), child_of(4, 2)];
+        let ancestors: Vec<u32> = snapshot[2]
+            .ancestors(&snapshot)
+            .iter()
+            .map(|p| p.pid)
+            .collect();
+        assert_eq!(ancestors, vec![2, 1]);
+    }
+
+    #[test]
+    fn test_descendants_survives_cycle() {
+        let snapshot = vec![child_of(1, 2), child_of(2, 1)];
+        let descendants: Vec<u32> = snapshot[0]
+            .descendants(&snapshot)
+            .iter()
+            .map(|p| p.pid)
+            .collect();
+        assert_eq!(descendants, vec![2]);
+    }
+
+    #[test]
+    fn test_status_filter() {
+        use clap::ValueEnum;
+        let parse = |s| StatusFilter::from_str(s, true);
+        assert_eq!(parse("Uninterruptible"), Ok(StatusFilter::Disk));
+        assert_eq!(parse("sleep"), Ok(StatusFilter::Sleeping));
+        assert!(parse("runnning").is_err());
+
+        assert!(StatusFilter::Disk.matches(ProcessStatus::DiskSleep));
+        assert!(!StatusFilter::Disk.matches(ProcessStatus::Sleeping));
+        assert!(!StatusFilter::Running.matches(ProcessStatus::Unknown));
+    }
+
+    #[test]
+    fn test_matches_pattern() {
+        let proc = Process {
+            pid: 1,
+            name: "node".to_string(),
+            command: Some("node /srv/my-safe-daemon.js".to_string()),
+            ..Process::default()
+        };
+        assert!(proc.matches_pattern("NODE"));
+        assert!(proc.matches_pattern("safe-daemon"));
+        assert!(!proc.matches_pattern("python"));
+    }
+
+    #[test]
+    fn test_name_match() {
+        let proc = Process {
+            pid: 1,
+            name: "python".to_string(),
+            command: Some("python app.py --config /etc/server.conf --port 80".to_string()),
+            ..Process::default()
+        };
+        assert_eq!(
+            proc.name_match("PYTH", MatchField::Both),
+            Some(NameMatch::Name("python".to_string()))
+        );
+        assert_eq!(
+            proc.name_match("server", MatchField::Both),
+            Some(NameMatch::Command(
+                "...config /etc/server.conf --port...".to_string()
+            ))
+        );
+        assert_eq!(proc.name_match("server", MatchField::Name), None);
+        assert_eq!(
+            proc.name_match("python app", MatchField::Command),
+            Some(NameMatch::Command("python app.py --config...".to_string()))
+        );
+    }
+
+    #[test]
+    fn test_match_span() {
+        let proc = Process {
+            name: "Python".to_string(),
+            command: Some("python app.py --config /etc/Server.conf".to_string()),
+            ..Process::default()
+        };
+        let span = |pattern, field| {
+            proc.match_span(pattern, field)
+                .map(|s| (s.matched_on, s.match_start, s.match_end))
+        };
+
+        assert_eq!(span("yth", MatchField::Both), Some((MatchedOn::Name, 1, 4)));
+        assert_eq!(
+            span("server", MatchField::Both),
+            Some((MatchedOn::Command, 28, 34))
+        );
+        assert_eq!(
+            span("python", MatchField::Command),
+            Some((MatchedOn::Command, 0, 6))
+        );
+        assert_eq!(span("server", MatchField::Name), None);
+        assert_eq!(find_chars("çafé app", "APP"), Some((5, 8)));
+        assert_eq!(find_chars("abc", ""), Some((0, 0)));
+    }
+
     #[test]
     fn test_find_nonexistent_process() {
         let result = Process::find_by_name("nonexistent_process_12345");
-        assert!(result.is_err());
+        assert!(result.unwrap().is_empty());
     }
 }
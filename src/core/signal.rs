@@ -0,0 +1,74 @@
+//! Signal names and numbers
+//!
+//! Lists the signals `proc` can send on the current platform and parses the
+//! forms users type: `TERM`, `SIGTERM`, or `15`.
+
+use crate::error::{ProcError, Result};
+
+/// Signals supported on this platform, as (name, number) pairs
+#[cfg(unix)]
+pub fn supported_signals() -> Vec<(&'static str, i32)> {
+    use nix::sys::signal::Signal;
+
+    Signal::iterator().map(|s| (s.as_str(), s as i32)).collect()
+}
+
+/// Signals supported on this platform, as (name, number) pairs
+///
+/// Windows has no signals; termination is emulated with `taskkill`.
+#[cfg(not(unix))]
+pub fn supported_signals() -> Vec<(&'static str, i32)> {
+    vec![("SIGKILL", 9), ("SIGTERM", 15)]
+}
+
+/// Parse a signal name or number into its number
+///
+/// Accepts `TERM`, `SIGTERM`, `sigterm`, or `15`. Unknown signals produce an
+/// error listing the valid names.
+pub fn parse_signal(input: &str) -> Result<i32> {
+    let signals = supported_signals();
+    let trimmed = input.trim();
+
+    let found = if let Ok(num) = trimmed.parse::<i32>() {
+        signals.iter().find(|(_, n)| *n == num)
+    } else {
+        let upper = trimmed.to_uppercase();
+        let name = if upper.starts_with("SIG") {
+            upper
+        } else {
+            format!("SIG{}", upper)
+        };
+        signals.iter().find(|(n, _)| *n == name)
+    };
+
+    found.map(|(_, n)| *n).ok_or_else(|| {
+        let names: Vec<&str> = signals
+            .iter()
+            .map(|(n, _)| n.trim_start_matches("SIG"))
+            .collect();
+        ProcError::InvalidInput(format!(
+            "Unknown signal '{}'. Valid signals: {}",
+            input,
+            names.join(", ")
+        ))
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_signal_forms() {
+        assert_eq!(parse_signal("TERM").unwrap(), 15);
+        assert_eq!(parse_signal("sigterm").unwrap(), 15);
+        assert_eq!(parse_signal("9").unwrap(), 9);
+    }
+
+    #[test]
+    fn test_parse_signal_invalid_lists_names() {
+        let err = parse_signal("BOGUS").unwrap_err().to_string();
+        assert!(err.contains("BOGUS"));
+        assert!(err.contains("KILL"));
+    }
+}
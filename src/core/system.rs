@@ -0,0 +1,144 @@
+//! Machine-wide resource totals
+//!
+//! Gives per-process numbers a frame of reference: how many cores there are,
+//! how busy they are overall, how much memory is in use, and whether proc is
+//! confined to a container's view of the machine.
+
+use serde::Serialize;
+use sysinfo::System;
+
+/// CPU and memory totals for the whole machine
+#[derive(Debug, Clone, Serialize)]
+pub struct SystemSummary {
+    /// Number of logical CPU cores
+    pub cores: usize,
+    /// CPU usage averaged across all cores (0-100)
+    pub cpu_percent: f32,
+    /// Physical memory in megabytes
+    pub total_memory_mb: f64,
+    /// Memory in use in megabytes
+    pub used_memory_mb: f64,
+}
+
+impl SystemSummary {
+    /// Measure the machine's current CPU load and memory use
+    ///
+    /// CPU load is measured between two readings, so this blocks for
+    /// sysinfo's minimum update interval (a few hundred milliseconds).
+    pub fn sample() -> Self {
+        let mut sys = System::new();
+        sys.refresh_cpu_usage();
+        sys.refresh_memory();
+        std::thread::sleep(sysinfo::MINIMUM_CPU_UPDATE_INTERVAL);
+        sys.refresh_cpu_usage();
+
+        Self {
+            cores: sys.cpus().len(),
+            cpu_percent: sys.global_cpu_usage(),
+            total_memory_mb: sys.total_memory() as f64 / 1024.0 / 1024.0,
+            used_memory_mb: sys.used_memory() as f64 / 1024.0 / 1024.0,
+        }
+    }
+
+    /// Share of physical memory in use (0-100)
+    pub fn memory_percent(&self) -> f64 {
+        if self.total_memory_mb > 0.0 {
+            self.used_memory_mb / self.total_memory_mb * 100.0
+        } else {
+            0.0
+        }
+    }
+}
+
+/// The container runtime proc is running under, if any
+///
+/// Looks for the marker files Docker (`/.dockerenv`) and Podman
+/// (`/run/.containerenv`) create, then for runtime names in this process's
+/// cgroups. Inside a container only processes in its PID namespace are
+/// visible. Always `None` outside Linux.
+pub fn container_runtime() -> Option<&'static str> {
+    #[cfg(target_os = "linux")]
+    {
+        if std::path::Path::new("/.dockerenv").exists() {
+            return Some("docker");
+        }
+        if std::path::Path::new("/run/.containerenv").exists() {
+            return Some("podman");
+        }
+        std::fs::read_to_string("/proc/self/cgroup")
+            .ok()
+            .and_then(|text| runtime_from_cgroup(&text))
+    }
+    #[cfg(not(target_os = "linux"))]
+    {
+        None
+    }
+}
+
+/// Recognize a container runtime from the contents of `/proc/<pid>/cgroup`
+#[cfg_attr(not(target_os = "linux"), allow(dead_code))]
+fn runtime_from_cgroup(text: &str) -> Option<&'static str> {
+    const HINTS: &[(&str, &str)] = &[
+        ("kubepods", "kubernetes"),
+        ("docker", "docker"),
+        ("libpod", "podman"),
+        ("containerd", "containerd"),
+        ("lxc", "lxc"),
+    ];
+    text.lines()
+        .filter_map(|line| line.splitn(3, ':').nth(2))
+        .find_map(|path| {
+            HINTS
+                .iter()
+                .find(|(hint, _)| path.contains(hint))
+                .map(|&(_, runtime)| runtime)
+        })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_memory_percent() {
+        let summary = SystemSummary {
+            cores: 8,
+            cpu_percent: 12.5,
+            total_memory_mb: 16384.0,
+            used_memory_mb: 4096.0,
+        };
+        assert_eq!(summary.memory_percent(), 25.0);
+        assert_eq!(
+            SystemSummary {
+                total_memory_mb: 0.0,
+                ..summary
+            }
+            .memory_percent(),
+            0.0
+        );
+    }
+
+    #[test]
+    fn test_runtime_from_cgroup() {
+        assert_eq!(
+            runtime_from_cgroup("0::/kubepods/besteffort/pod1234/abcd\n"),
+            Some("kubernetes")
+        );
+        assert_eq!(
+            runtime_from_cgroup("12:memory:/docker/0123abcd\n0::/\n"),
+            Some("docker")
+        );
+        assert_eq!(
+            runtime_from_cgroup("0::/user.slice/user-1000.slice\n"),
+            None
+        );
+        assert_eq!(runtime_from_cgroup("0::/\n"), None);
+    }
+
+    #[test]
+    fn test_sample() {
+        let summary = SystemSummary::sample();
+        assert!(summary.cores > 0);
+        assert!(summary.used_memory_mb <= summary.total_memory_mb);
+    }
+}
@@ -2,33 +2,73 @@
 //!
 //! Targets can be:
 //! - `:port` - Process listening on this port
+//! - `host:port` - The same, for a local host such as `localhost:3000` (a
+//!   `scheme://` prefix and trailing path are ignored, so pasted URLs work)
+//! - `:start-end` - Processes listening on any port in this range
 //! - `pid` - Process with this PID (numeric)
 //! - `name` - Processes matching this name
+//! - `exe:/path` - Processes whose executable is this file or lies under this directory
+//!
+//! A `pid:` or `name:` prefix forces the interpretation, so a process
+//! literally named `1234` can be targeted as `name:1234`.
 
-use crate::core::port::{parse_port, PortInfo};
-use crate::core::Process;
+use crate::core::filter::{canonical_dir, path_within};
+use crate::core::port::{parse_port, parse_port_range, PortInfo};
+use crate::core::{glob_match, Process};
 use crate::error::{ProcError, Result};
+use regex::{Regex, RegexBuilder};
+use std::collections::HashMap;
+use std::path::Path;
 
 /// Resolved target type
 #[derive(Debug, Clone)]
 pub enum TargetType {
     /// Target a process by the port it listens on (e.g., `:3000`)
     Port(u16),
+    /// Target processes listening on any port in an inclusive range (e.g., `:3000-3999`)
+    PortRange(u16, u16),
     /// Target a process by its process ID (e.g., `1234`)
     Pid(u32),
     /// Target processes by name pattern (e.g., `node`)
     Name(String),
+    /// Target processes by executable path or directory (e.g., `exe:/opt/app/bin/python`)
+    ExePath(String),
 }
 
 /// Parse a target string and determine its type
+///
+/// `pid:N` and `name:X` force the type; otherwise a leading `:` or a
+/// `host:port` form means a port, a pure number means a PID, and anything
+/// else is a name. The host is not checked here; see [`split_host_port`].
 pub fn parse_target(target: &str) -> TargetType {
     let target = target.trim();
 
+    // Explicit type prefixes
+    if let Some(name) = target.strip_prefix("name:") {
+        return TargetType::Name(name.to_string());
+    }
+    if let Some(path) = target.strip_prefix("exe:") {
+        return TargetType::ExePath(path.to_string());
+    }
+    if let Some(pid) = target.strip_prefix("pid:") {
+        if let Ok(pid) = pid.parse::<u32>() {
+            return TargetType::Pid(pid);
+        }
+    }
+
     // Explicit port prefix
     if target.starts_with(':') {
         if let Ok(port) = parse_port(target) {
             return TargetType::Port(port);
         }
+        if let Ok((start, end)) = parse_port_range(target) {
+            return TargetType::PortRange(start, end);
+        }
+    }
+
+    // host:port, typically copied from a URL
+    if let Some((_, port)) = split_host_port(target) {
+        return TargetType::Port(port);
     }
 
     // Pure number - treat as PID
@@ -40,29 +80,89 @@ pub fn parse_target(target: &str) -> TargetType {
     TargetType::Name(target.to_string())
 }
 
+/// Split a `host:port` target, ignoring a `scheme://` prefix and any path
+///
+/// IPv6 hosts must be bracketed (`[::1]:3000`). Returns `None` for anything
+/// else, including the bare `:port` form.
+pub fn split_host_port(target: &str) -> Option<(&str, u16)> {
+    let rest = target.split_once("://").map_or(target, |(_, rest)| rest);
+    let authority = rest.split('/').next()?;
+    let (host, port) = authority.rsplit_once(':')?;
+    if host.is_empty() || (host.contains(':') && !(host.starts_with('[') && host.ends_with(']'))) {
+        return None;
+    }
+    Some((host, port.parse().ok()?))
+}
+
+/// Check whether a host names this machine's loopback or wildcard address
+pub fn is_local_host(host: &str) -> bool {
+    let host = host.trim_start_matches('[').trim_end_matches(']');
+    host.eq_ignore_ascii_case("localhost")
+        || host
+            .parse::<std::net::IpAddr>()
+            .is_ok_and(|ip| ip.is_loopback() || ip.is_unspecified())
+}
+
 /// Resolve a target to processes
+///
+/// A `host:port` target naming another machine is rejected rather than
+/// resolved to whatever listens on that port locally.
 pub fn resolve_target(target: &str) -> Result<Vec<Process>> {
     match parse_target(target) {
-        TargetType::Port(port) => resolve_port(port),
+        TargetType::Port(port) => {
+            if let Some((host, _)) = split_host_port(target).filter(|(h, _)| !is_local_host(h)) {
+                return Err(ProcError::InvalidInput(format!(
+                    "'{}' is not a local host; only local ports can be targeted (use :{})",
+                    host, port
+                )));
+            }
+            resolve_port(port)
+        }
+        TargetType::PortRange(start, end) => resolve_port_range(start, end),
         TargetType::Pid(pid) => resolve_pid(pid),
-        TargetType::Name(name) => Process::find_by_name(&name),
+        TargetType::ExePath(path) => resolve_exe(&path),
+        TargetType::Name(name) => resolve_name(&name),
     }
 }
 
+/// Resolve a target to processes, matching names as anchored globs
+pub fn resolve_target_glob(target: &str) -> Result<Vec<Process>> {
+    match parse_target(target) {
+        TargetType::Name(pattern) => {
+            let processes: Vec<Process> = Process::find_all()?
+                .into_iter()
+                .filter(|p| glob_match(&pattern, &p.name))
+                .collect();
+            if processes.is_empty() {
+                return Err(ProcError::ProcessNotFound(pattern));
+            }
+            Ok(processes)
+        }
+        _ => resolve_target(target),
+    }
+}
+
+/// Compile a name pattern as a case-insensitive regular expression
+pub fn name_regex(pattern: &str) -> Result<Regex> {
+    Ok(RegexBuilder::new(pattern).case_insensitive(true).build()?)
+}
+
 /// Resolve a single target to exactly one process
+///
+/// Several matches give [`ProcError::AmbiguousTarget`], listing them by PID.
 pub fn resolve_target_single(target: &str) -> Result<Process> {
-    let processes = resolve_target(target)?;
+    let mut processes = resolve_target(target)?;
 
     if processes.is_empty() {
         return Err(ProcError::ProcessNotFound(target.to_string()));
     }
 
     if processes.len() > 1 {
-        return Err(ProcError::InvalidInput(format!(
-            "Target '{}' matches {} processes. Be more specific.",
-            target,
-            processes.len()
-        )));
+        processes.sort_by_key(|p| p.pid);
+        return Err(ProcError::AmbiguousTarget {
+            target: target.to_string(),
+            candidates: processes.into_iter().map(|p| (p.pid, p.name)).collect(),
+        });
     }
 
     Ok(processes.into_iter().next().unwrap())
@@ -79,6 +179,22 @@ fn resolve_port(port: u16) -> Result<Vec<Process>> {
     }
 }
 
+/// Resolve a port range to every process listening in it, deduplicated by PID
+fn resolve_port_range(start: u16, end: u16) -> Result<Vec<Process>> {
+    let mut pids: Vec<u32> = find_ports_in_range(start, end)?
+        .iter()
+        .map(|p| p.pid)
+        .collect();
+    pids.sort_unstable();
+
+    let processes = Process::find_by_pids(&pids)?;
+
+    if processes.is_empty() {
+        return Err(ProcError::ProcessNotFound(format!(":{}-{}", start, end)));
+    }
+    Ok(processes)
+}
+
 /// Resolve PID to process
 fn resolve_pid(pid: u32) -> Result<Vec<Process>> {
     match Process::find_by_pid(pid)? {
@@ -87,12 +203,48 @@ fn resolve_pid(pid: u32) -> Result<Vec<Process>> {
     }
 }
 
+/// Resolve a process name to every process it matches
+pub fn resolve_name(name: &str) -> Result<Vec<Process>> {
+    let processes = Process::find_by_name(name)?;
+    if processes.is_empty() {
+        return Err(ProcError::ProcessNotFound(name.to_string()));
+    }
+    Ok(processes)
+}
+
+/// Resolve an executable path or directory to every process running from it
+pub fn resolve_exe(path: &str) -> Result<Vec<Process>> {
+    let dir = canonical_dir(Path::new(path));
+    let processes: Vec<Process> = Process::find_all()?
+        .into_iter()
+        .filter(|p| {
+            p.exe_path
+                .as_deref()
+                .is_some_and(|exe| path_within(Path::new(exe), &dir))
+        })
+        .collect();
+
+    if processes.is_empty() {
+        return Err(ProcError::ProcessNotFound(format!("exe:{}", path)));
+    }
+    Ok(processes)
+}
+
 /// Find all ports a process is listening on
 pub fn find_ports_for_pid(pid: u32) -> Result<Vec<PortInfo>> {
     let all_ports = PortInfo::get_all_listening()?;
     Ok(all_ports.into_iter().filter(|p| p.pid == pid).collect())
 }
 
+/// Find all listening ports within an inclusive range
+pub fn find_ports_in_range(start: u16, end: u16) -> Result<Vec<PortInfo>> {
+    let all_ports = PortInfo::get_all_listening()?;
+    Ok(all_ports
+        .into_iter()
+        .filter(|p| (start..=end).contains(&p.port))
+        .collect())
+}
+
 /// Split comma-separated targets into individual target strings
 ///
 /// Examples:
@@ -111,6 +263,45 @@ pub fn parse_targets(targets_str: &str) -> Vec<String> {
 ///
 /// Returns a tuple of (found processes, not found target strings)
 pub fn resolve_targets(targets: &[String]) -> (Vec<Process>, Vec<String>) {
+    resolve_targets_with(targets, resolve_target)
+}
+
+/// Resolve multiple targets like [`resolve_targets`], matching names as globs
+pub fn resolve_targets_glob(targets: &[String]) -> (Vec<Process>, Vec<String>) {
+    resolve_targets_with(targets, resolve_target_glob)
+}
+
+/// Resolve multiple targets like [`resolve_targets`], matching names as
+/// case-insensitive regular expressions against the process name
+///
+/// Fails before resolving anything if a name target is not a valid regex.
+pub fn resolve_targets_regex(targets: &[String]) -> Result<(Vec<Process>, Vec<String>)> {
+    let mut patterns = HashMap::new();
+    for target in targets {
+        if let TargetType::Name(pattern) = parse_target(target) {
+            patterns.insert(target.as_str(), name_regex(&pattern)?);
+        }
+    }
+
+    Ok(resolve_targets_with(targets, |target| {
+        let Some(re) = patterns.get(target) else {
+            return resolve_target(target);
+        };
+        let processes: Vec<Process> = Process::find_all()?
+            .into_iter()
+            .filter(|p| re.is_match(&p.name))
+            .collect();
+        if processes.is_empty() {
+            return Err(ProcError::ProcessNotFound(target.to_string()));
+        }
+        Ok(processes)
+    }))
+}
+
+fn resolve_targets_with(
+    targets: &[String],
+    resolve: impl Fn(&str) -> Result<Vec<Process>>,
+) -> (Vec<Process>, Vec<String>) {
     use std::collections::HashSet;
 
     let mut all_processes = Vec::new();
@@ -118,7 +309,7 @@ pub fn resolve_targets(targets: &[String]) -> (Vec<Process>, Vec<String>) {
     let mut not_found = Vec::new();
 
     for target in targets {
-        match resolve_target(target) {
+        match resolve(target) {
             Ok(processes) => {
                 for proc in processes {
                     if seen_pids.insert(proc.pid) {
@@ -137,6 +328,17 @@ pub fn resolve_targets(targets: &[String]) -> (Vec<Process>, Vec<String>) {
 mod tests {
     use super::*;
 
+    #[test]
+    fn test_name_regex() {
+        let re = name_regex("^chrome.*helper$").unwrap();
+        assert!(re.is_match("Chrome Helper"));
+        assert!(!re.is_match("chrome"));
+        assert!(matches!(
+            name_regex("(unclosed"),
+            Err(ProcError::InvalidInput(_))
+        ));
+    }
+
     #[test]
     fn test_parse_targets_single() {
         assert_eq!(parse_targets(":3000"), vec![":3000"]);
@@ -175,12 +377,58 @@ mod tests {
         assert!(matches!(parse_target(":8080"), TargetType::Port(8080)));
     }
 
+    #[test]
+    fn test_parse_target_port_range() {
+        assert!(matches!(
+            parse_target(":3000-3999"),
+            TargetType::PortRange(3000, 3999)
+        ));
+        assert!(matches!(parse_target(":3999-3000"), TargetType::Name(_)));
+    }
+
+    #[test]
+    fn test_parse_target_host_port() {
+        assert!(matches!(
+            parse_target("localhost:3000"),
+            TargetType::Port(3000)
+        ));
+        assert!(matches!(parse_target("[::1]:8080"), TargetType::Port(8080)));
+        assert!(matches!(
+            parse_target("http://127.0.0.1:5173/app?x=1"),
+            TargetType::Port(5173)
+        ));
+        assert_eq!(split_host_port("fe80::1"), None);
+        assert!(matches!(parse_target("node:app"), TargetType::Name(_)));
+
+        assert_eq!(
+            split_host_port("example.com:443"),
+            Some(("example.com", 443))
+        );
+        assert_eq!(split_host_port(":3000"), None);
+        assert!(is_local_host("LOCALHOST"));
+        assert!(is_local_host("127.0.1.1"));
+        assert!(is_local_host("[::1]"));
+        assert!(is_local_host("0.0.0.0"));
+        assert!(!is_local_host("example.com"));
+        assert!(!is_local_host("10.0.0.5"));
+    }
+
     #[test]
     fn test_parse_target_pid() {
         assert!(matches!(parse_target("1234"), TargetType::Pid(1234)));
         assert!(matches!(parse_target("99999"), TargetType::Pid(99999)));
     }
 
+    #[test]
+    fn test_parse_target_prefixes() {
+        assert!(matches!(parse_target("name:1234"), TargetType::Name(ref n) if n == "1234"));
+        assert!(matches!(parse_target("pid:1234"), TargetType::Pid(1234)));
+        assert!(matches!(parse_target("name::3000"), TargetType::Name(ref n) if n == ":3000"));
+        assert!(
+            matches!(parse_target("exe:/opt/app/bin/python"), TargetType::ExePath(ref p) if p == "/opt/app/bin/python")
+        );
+    }
+
     #[test]
     fn test_parse_target_name() {
         assert!(matches!(parse_target("node"), TargetType::Name(_)));
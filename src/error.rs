@@ -5,7 +5,10 @@
 use thiserror::Error;
 
 /// Main error type for proc operations
+///
+/// Variants may be added in minor releases, so matches need a wildcard arm.
 #[derive(Error, Debug)]
+#[non_exhaustive]
 pub enum ProcError {
     /// No process found matching the given target
     #[error("No process found matching '{0}'\n  Try: proc list to list all processes")]
@@ -46,6 +49,54 @@ pub enum ProcError {
     /// Failed to send a signal to the process
     #[error("Signal failed: {0}")]
     SignalError(String),
+
+    /// A requested assertion about the results did not hold
+    #[error("Check failed: {0}")]
+    CheckFailed(String),
+
+    /// Ports that were required to be free have listeners
+    #[error("Port conflict: {0}")]
+    PortConflict(String),
+
+    /// A pidfile names a process that is gone or has been replaced
+    #[error("Stale pidfile {0}\n  Try: remove it if the service is stopped")]
+    StalePidFile(String),
+
+    /// Host-wide results were required, but proc only sees a container
+    #[error("Running inside a {0} container, so only its own processes are visible\n  Try: run proc on the host, or from a container sharing the host PID namespace: nsenter -t 1 -m -p proc <command>")]
+    ContainerScoped(String),
+
+    /// A target that must name one process matched several
+    #[error(
+        "Target '{target}' matches {} processes:{}\n  Try: pid:<PID> to pick one",
+        candidates.len(),
+        list_candidates(candidates)
+    )]
+    AmbiguousTarget {
+        /// The target as given
+        target: String,
+        /// PID and name of every matching process
+        candidates: Vec<(u32, String)>,
+    },
+}
+
+/// Candidates shown in a [`ProcError::AmbiguousTarget`] message
+const SHOWN_CANDIDATES: usize = 5;
+
+/// Render the first few candidates, one per line
+fn list_candidates(candidates: &[(u32, String)]) -> String {
+    let mut out: String = candidates
+        .iter()
+        .take(SHOWN_CANDIDATES)
+        .map(|(pid, name)| format!("\n    {} {}", pid, name))
+        .collect();
+    if candidates.len() > SHOWN_CANDIDATES {
+        out.push_str(&format!(
+            "\n    ... and {} more",
+            candidates.len() - SHOWN_CANDIDATES
+        ));
+    }
+    out
 }
 
 impl From<std::io::Error> for ProcError {
@@ -80,7 +131,18 @@ impl From<dialoguer::Error> for ProcError {
 pub type Result<T> = std::result::Result<T, ProcError>;
 
 /// Exit codes for CLI
-#[derive(Debug, Clone, Copy)]
+///
+/// | Code | Meaning |
+/// |------|---------|
+/// | 0 | Success |
+/// | 1 | General error (including failed `--expect` style checks) |
+/// | 2 | Process or port not found |
+/// | 3 | Permission denied |
+/// | 4 | Invalid input |
+/// | 5 | Timed out |
+/// | 6 | Not supported on this platform |
+/// | 7 | Port conflict (`ports --conflicts`) |
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum ExitCode {
     /// Operation completed successfully
     Success = 0,
@@ -92,15 +154,60 @@ pub enum ExitCode {
     PermissionDenied = 3,
     /// Invalid arguments or input provided
     InvalidInput = 4,
+    /// The operation exceeded its time limit
+    Timeout = 5,
+    /// The feature is not available on this platform
+    NotSupported = 6,
+    /// A port that should be free is in use
+    Conflict = 7,
 }
 
 impl From<&ProcError> for ExitCode {
     fn from(err: &ProcError) -> Self {
         match err {
-            ProcError::ProcessNotFound(_) | ProcError::PortNotFound(_) => ExitCode::NotFound,
+            ProcError::ProcessNotFound(_)
+            | ProcError::PortNotFound(_)
+            | ProcError::StalePidFile(_) => ExitCode::NotFound,
             ProcError::PermissionDenied(_) => ExitCode::PermissionDenied,
-            ProcError::InvalidInput(_) => ExitCode::InvalidInput,
+            ProcError::InvalidInput(_) | ProcError::AmbiguousTarget { .. } => {
+                ExitCode::InvalidInput
+            }
+            ProcError::Timeout(_) => ExitCode::Timeout,
+            ProcError::NotSupported(_) | ProcError::ContainerScoped(_) => ExitCode::NotSupported,
+            ProcError::PortConflict(_) => ExitCode::Conflict,
             _ => ExitCode::GeneralError,
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_exit_codes() {
+        let code = |err: ProcError| ExitCode::from(&err) as i32;
+        assert_eq!(code(ProcError::PortNotFound(3000)), 2);
+        assert_eq!(code(ProcError::StalePidFile(String::new())), 2);
+        assert_eq!(code(ProcError::InvalidInput(String::new())), 4);
+        assert_eq!(code(ProcError::Timeout(String::new())), 5);
+        assert_eq!(code(ProcError::NotSupported(String::new())), 6);
+        assert_eq!(code(ProcError::PortConflict(String::new())), 7);
+        assert_eq!(code(ProcError::CheckFailed(String::new())), 1);
+    }
+
+    #[test]
+    fn test_ambiguous_target_message() {
+        let candidates: Vec<(u32, String)> =
+            (1..=7).map(|pid| (pid, format!("node{}", pid))).collect();
+        let err = ProcError::AmbiguousTarget {
+            target: "node".to_string(),
+            candidates,
+        };
+        let message = err.to_string();
+        assert!(message.starts_with("Target 'node' matches 7 processes:\n    1 node1\n"));
+        assert!(message.contains("    5 node5\n    ... and 2 more\n"));
+        assert!(!message.contains("node6"));
+        assert_eq!(ExitCode::from(&err), ExitCode::InvalidInput);
+    }
+}
@@ -29,12 +29,25 @@
 //! # Find stuck processes
 //! proc stuck
 //! ```
+//!
+//! ## Library Usage
+//!
+//! The [`list`], [`ports`], and [`kill`] functions return data without
+//! printing anything. See the [`api`] module for details.
+//!
+//! [`KillCommand::run`](commands::KillCommand::run),
+//! [`StopCommand::run`](commands::StopCommand::run), and
+//! [`UnstickCommand::run`](commands::UnstickCommand::run) act like the CLI
+//! commands without prompting or printing, and return what happened.
 
+pub mod api;
 pub mod commands;
 pub mod core;
 pub mod error;
 pub mod ui;
 
+pub use api::{kill, list, ports, KillOptions, KillReport};
+pub use core::{Filter, PortFilter};
 pub use error::{ProcError, Result};
 
 /// Library version
@@ -4,11 +4,16 @@
 
 use clap::{Parser, Subcommand};
 use proc_cli::commands::{
-    ByCommand, InCommand, InfoCommand, KillCommand, ListCommand, OnCommand, PortsCommand,
-    StopCommand, StuckCommand, TreeCommand, UnstickCommand,
+    ByCommand, ExportCommand, HogsCommand, InCommand, InfoCommand, KillCommand, ListCommand,
+    MonitorCommand, OnCommand, OrphansCommand, PortsCommand, Resource, SignalsCommand, StopCommand,
+    StuckCommand, TreeCommand, UnstickCommand,
 };
-use proc_cli::error::ExitCode;
+use proc_cli::core::{container_runtime, DEFAULT_PORT_TIMEOUT};
+use proc_cli::error::{ExitCode, ProcError, Result};
+use proc_cli::ui::ColorChoice;
+use std::path::PathBuf;
 use std::process;
+use std::time::Duration;
 
 const VERSION_INFO: &str = concat!(
     env!("CARGO_PKG_VERSION"),
@@ -23,6 +28,7 @@ const VERSION_INFO: &str = concat!(
 #[command(propagate_version = true)]
 #[command(
     after_help = "Targets: :port, PID, or process name. Comma-separate for multiple.
+Force a type with pid:1234 or name:1234; match an executable with exe:/path.
 Run 'proc --help' for examples or visit https://github.com/yazeed/proc"
 )]
 #[command(after_long_help = "EXAMPLES:
@@ -44,23 +50,60 @@ Run 'proc --help' for examples or visit https://github.com/yazeed/proc"
   List All:
     proc list                      All processes
     proc list --min-cpu 10         Processes using >10% CPU
+    proc list --delta -s mem       Who grew the most over 2 seconds
+    proc list --io -s io           Disk read/write rates, busiest first
+    proc cpu-hogs                  Ten busiest processes (mem-hogs for memory)
 
   Info/Kill/Stop (multi-target):
     proc info :3000,:8080          Info for multiple targets
     proc kill :3000,node -y        Kill port 3000 and node processes
+    proc kill --pid-file app.pid   Kill the PID a daemon recorded
+    proc kill node --tree --dry-run  Preview node and its child processes
     proc stop :3000,:8080          Stop multiple targets gracefully
 
   Other:
     proc ports                     List all listening ports
+    proc ports --free :3000-3100   First free port in a range
+    proc ports --listen-and-conn   Listeners and connections per process
+    proc ports --conflicts :3000,:8080  Exit 7 if a reserved port is taken
     proc tree --min-cpu 5          Process tree filtered by CPU
+    proc tree :3000 --context      Ancestors and descendants of a target
     proc stuck                     Find hung processes
     proc unstick --force           Recover or terminate stuck processes
+    proc unstick --output run.json Watch progress, keep a JSON record
+    proc orphans --in .            Leaked workers reparented to init
+    proc signals                   Signals accepted by kill --signal
+    proc export --filter node      Prometheus metrics for node processes
+    proc monitor --events          Stream process start/exit events as NDJSON
 
 Targets: :port, PID, or process name. Comma-separate for multiple.
+Force a type with pid:1234 or name:1234 (e.g. a process literally named 1234).
+Target by executable path or directory with exe:/opt/app/bin/python.
+Pass - to read targets from stdin (e.g. cat pids.txt | proc kill - -y).
 For more information, visit: https://github.com/yazeed/proc")]
 struct Cli {
     #[command(subcommand)]
     command: Commands,
+
+    /// Write the JSON result to this file; human output stays on the terminal
+    #[arg(long, global = true, value_name = "PATH")]
+    output: Option<PathBuf>,
+
+    /// Give up on port lookups (lsof/ss/netstat) after this many seconds
+    #[arg(long, global = true, value_name = "SECS", default_value_t = DEFAULT_PORT_TIMEOUT.as_secs())]
+    port_timeout: u64,
+
+    /// Report lsof/ss/netstat lines that port lookups could not parse (on stderr)
+    #[arg(long, global = true)]
+    debug_parse: bool,
+
+    /// When to use color: auto (terminal only, honoring NO_COLOR), always, or never
+    #[arg(long, global = true, value_enum, default_value_t = ColorChoice::Auto)]
+    color: ColorChoice,
+
+    /// Require host-wide results: fail with a hint when proc runs inside a container
+    #[arg(long, global = true)]
+    host: bool,
 }
 
 #[derive(Subcommand)]
@@ -80,6 +123,12 @@ enum Commands {
     #[command(visible_aliases = ["l", "ps"])]
     List(ListCommand),
 
+    /// Top processes by CPU usage (list --sort cpu -n 10)
+    CpuHogs(HogsCommand),
+
+    /// Top processes by memory (list --sort mem -n 10)
+    MemHogs(HogsCommand),
+
     /// Show detailed process information
     #[command(visible_alias = "i")]
     Info(InfoCommand),
@@ -107,16 +156,65 @@ enum Commands {
     /// Attempt to recover stuck processes
     #[command(visible_alias = "u")]
     Unstick(UnstickCommand),
+
+    /// Find orphaned processes reparented to init
+    Orphans(OrphansCommand),
+
+    /// List signals accepted by kill --signal
+    Signals(SignalsCommand),
+
+    /// Emit process and port metrics (Prometheus format)
+    Export(ExportCommand),
+
+    /// Watch processes start and exit
+    Monitor(MonitorCommand),
 }
 
 fn main() {
     let cli = Cli::parse();
 
-    let result = match cli.command {
+    proc_cli::core::set_port_timeout(Duration::from_secs(cli.port_timeout));
+    proc_cli::core::set_debug_parse(cli.debug_parse);
+    proc_cli::ui::set_color(cli.color);
+
+    let result = check_output(&cli.command, cli.output.is_some())
+        .and_then(|()| cli.output.map_or(Ok(()), proc_cli::ui::set_json_output))
+        .and_then(|()| match container_runtime() {
+            Some(runtime) if cli.host => Err(ProcError::ContainerScoped(runtime.to_string())),
+            _ => Ok(()),
+        })
+        .and_then(|()| proc_cli::ui::finish_json_output(run(cli.command)));
+
+    if let Err(e) = result {
+        eprintln!("{}", e);
+        let exit_code = ExitCode::from(&e);
+        process::exit(exit_code as i32);
+    }
+}
+
+/// Reject `--output` for commands whose output is not a single JSON result
+fn check_output(command: &Commands, output: bool) -> Result<()> {
+    let reason = match command {
+        Commands::Export(_) => "export writes Prometheus text; redirect stdout instead",
+        _ => return Ok(()),
+    };
+    if output {
+        return Err(ProcError::InvalidInput(format!(
+            "--output is not supported here: {}",
+            reason
+        )));
+    }
+    Ok(())
+}
+
+fn run(command: Commands) -> Result<()> {
+    match command {
         Commands::On(cmd) => cmd.execute(),
         Commands::By(cmd) => cmd.execute(),
         Commands::In(cmd) => cmd.execute(),
         Commands::List(cmd) => cmd.execute(),
+        Commands::CpuHogs(cmd) => cmd.execute(Resource::Cpu),
+        Commands::MemHogs(cmd) => cmd.execute(Resource::Memory),
         Commands::Info(cmd) => cmd.execute(),
         Commands::Ports(cmd) => cmd.execute(),
         Commands::Kill(cmd) => cmd.execute(),
@@ -124,11 +222,9 @@ fn main() {
         Commands::Tree(cmd) => cmd.execute(),
         Commands::Stuck(cmd) => cmd.execute(),
         Commands::Unstick(cmd) => cmd.execute(),
-    };
-
-    if let Err(e) = result {
-        eprintln!("{}", e);
-        let exit_code = ExitCode::from(&e);
-        process::exit(exit_code as i32);
+        Commands::Orphans(cmd) => cmd.execute(),
+        Commands::Signals(cmd) => cmd.execute(),
+        Commands::Export(cmd) => cmd.execute(),
+        Commands::Monitor(cmd) => cmd.execute(),
     }
 }
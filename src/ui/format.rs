@@ -0,0 +1,157 @@
+//! Shared formatting helpers for human-readable output
+
+use std::time::Duration;
+
+/// Format a duration in seconds with its two most significant units
+///
+/// `45s`, `3m 12s`, `2h 5m`, `3d 4h`.
+pub fn format_duration(secs: u64) -> String {
+    if secs < 60 {
+        format!("{}s", secs)
+    } else if secs < 3600 {
+        format!("{}m {}s", secs / 60, secs % 60)
+    } else if secs < 86400 {
+        format!("{}h {}m", secs / 3600, (secs % 3600) / 60)
+    } else {
+        format!("{}d {}h", secs / 86400, (secs % 86400) / 3600)
+    }
+}
+
+/// Format a wait the way [`parse_wait`] reads it
+///
+/// `500ms` and `1500ms` below a minute when there are leftover milliseconds,
+/// otherwise as [`format_duration`]: `10s`, `2m 0s`.
+pub fn format_wait(wait: Duration) -> String {
+    if wait.subsec_millis() != 0 && wait < Duration::from_secs(60) {
+        format!("{}ms", wait.as_millis())
+    } else {
+        format_duration(wait.as_secs())
+    }
+}
+
+/// Format a byte count with a binary unit: `512B`, `1.5KB`, `3.2MB`, `1.1GB`
+pub fn format_bytes(bytes: f64) -> String {
+    const UNITS: [&str; 4] = ["KB", "MB", "GB", "TB"];
+    if bytes < 1024.0 {
+        return format!("{:.0}B", bytes);
+    }
+    let mut value = bytes / 1024.0;
+    let mut unit = UNITS[0];
+    for next in &UNITS[1..] {
+        if value < 1024.0 {
+            break;
+        }
+        value /= 1024.0;
+        unit = next;
+    }
+    format!("{:.1}{}", value, unit)
+}
+
+/// Parse a human duration into seconds
+///
+/// Accepts a bare number of seconds or unit-suffixed parts, which may be
+/// combined: `90`, `45s`, `20m`, `1h30m`, `2d`.
+pub fn parse_duration(input: &str) -> Result<u64, String> {
+    let input = input.trim();
+    if let Ok(secs) = input.parse::<u64>() {
+        return Ok(secs);
+    }
+
+    let invalid = || format!("invalid duration '{}' (e.g. 45s, 20m, 1h30m, 2d)", input);
+    let mut total: u64 = 0;
+    let mut digits = String::new();
+    for c in input.chars() {
+        if c.is_ascii_digit() {
+            digits.push(c);
+            continue;
+        }
+        let unit = match c.to_ascii_lowercase() {
+            's' => 1,
+            'm' => 60,
+            'h' => 3600,
+            'd' => 86400,
+            'w' => 7 * 86400,
+            _ => return Err(invalid()),
+        };
+        let value: u64 = digits.parse().map_err(|_| invalid())?;
+        total = value
+            .checked_mul(unit)
+            .and_then(|v| total.checked_add(v))
+            .ok_or_else(invalid)?;
+        digits.clear();
+    }
+
+    if !digits.is_empty() || input.is_empty() {
+        return Err(invalid());
+    }
+    Ok(total)
+}
+
+/// Parse a short wait: milliseconds (`250ms`) or anything [`parse_duration`] accepts
+pub fn parse_wait(input: &str) -> Result<Duration, String> {
+    let input = input.trim();
+    match input.strip_suffix("ms") {
+        Some(millis) => millis
+            .parse::<u64>()
+            .map(Duration::from_millis)
+            .map_err(|_| format!("invalid duration '{}' (e.g. 250ms, 2s, 1m)", input)),
+        None => parse_duration(input).map(Duration::from_secs),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_format_wait() {
+        assert_eq!(format_wait(Duration::from_millis(500)), "500ms");
+        assert_eq!(format_wait(Duration::from_millis(1500)), "1500ms");
+        assert_eq!(format_wait(Duration::from_secs(10)), "10s");
+        assert_eq!(format_wait(Duration::from_secs(120)), "2m 0s");
+        assert_eq!(format_wait(parse_wait("250ms").unwrap()), "250ms");
+    }
+
+    #[test]
+    fn test_parse_duration() {
+        assert_eq!(parse_duration("90"), Ok(90));
+        assert_eq!(parse_duration("45s"), Ok(45));
+        assert_eq!(parse_duration("20m"), Ok(1200));
+        assert_eq!(parse_duration("1h30m"), Ok(5400));
+        assert_eq!(parse_duration("2D"), Ok(172800));
+        assert!(parse_duration("").is_err());
+        assert!(parse_duration("m").is_err());
+        assert!(parse_duration("10x").is_err());
+        assert!(parse_duration("1h30").is_err());
+    }
+
+    #[test]
+    fn test_parse_wait() {
+        assert_eq!(parse_wait("250ms"), Ok(Duration::from_millis(250)));
+        assert_eq!(parse_wait("2s"), Ok(Duration::from_secs(2)));
+        assert_eq!(parse_wait("3"), Ok(Duration::from_secs(3)));
+        assert!(parse_wait("1.5ms").is_err());
+        assert!(parse_wait("ms").is_err());
+    }
+
+    #[test]
+    fn test_format_bytes() {
+        assert_eq!(format_bytes(0.0), "0B");
+        assert_eq!(format_bytes(1023.0), "1023B");
+        assert_eq!(format_bytes(1536.0), "1.5KB");
+        assert_eq!(format_bytes(3.0 * 1024.0 * 1024.0), "3.0MB");
+        assert_eq!(format_bytes(2.0 * 1024f64.powi(5)), "2048.0TB");
+    }
+
+    #[test]
+    fn test_format_duration_boundaries() {
+        assert_eq!(format_duration(0), "0s");
+        assert_eq!(format_duration(59), "59s");
+        assert_eq!(format_duration(60), "1m 0s");
+        assert_eq!(format_duration(3599), "59m 59s");
+        assert_eq!(format_duration(3600), "1h 0m");
+        assert_eq!(format_duration(86399), "23h 59m");
+        assert_eq!(format_duration(86400), "1d 0h");
+        assert_eq!(format_duration(3 * 86400 + 4 * 3600 + 59), "3d 4h");
+    }
+}
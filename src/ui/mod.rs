@@ -2,6 +2,16 @@
 //!
 //! Handles output formatting, colors, and interactive prompts.
 
+pub mod format;
 pub mod output;
+pub mod picker;
+pub mod prometheus;
+pub mod tree;
 
-pub use output::{OutputFormat, Printer};
+pub use format::{format_bytes, format_duration, format_wait, parse_duration, parse_wait};
+pub use output::{
+    finish_json_output, has_json_output, set_color, set_json_output, set_thresholds,
+    stream_json_output, ColorChoice, Column, OutputFormat, Printer, Thresholds,
+    JSON_SCHEMA_VERSION,
+};
+pub use tree::TreePrinter;
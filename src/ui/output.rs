@@ -2,30 +2,713 @@
 //!
 //! Provides colored terminal output and JSON formatting.
 
-use crate::core::{PortInfo, Process};
+use crate::core::{
+    children_map, DeltaChange, MatchSpan, PortInfo, Process, ProcessDelta, ProcessStatus,
+    SystemSummary, MAX_TREE_DEPTH,
+};
+use crate::error::{ProcError, Result};
+use crate::ui::{format_bytes, TreePrinter};
 use colored::*;
 use serde::Serialize;
+use std::collections::{HashMap, HashSet};
+use std::fs::File;
+use std::io::{IsTerminal, Write};
+use std::path::PathBuf;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Mutex, OnceLock};
+
+/// Version of the JSON output shape, reported as `schema_version` in every result
+///
+/// Bumped only for breaking changes: a field removed, renamed, or retyped, or
+/// its meaning changed. New fields may appear without a bump, so consumers
+/// should ignore keys they do not recognize.
+pub const JSON_SCHEMA_VERSION: u32 = 1;
+
+/// File that receives JSON results instead of stdout (set by `--output`)
+static JSON_OUTPUT: OnceLock<JsonOutput> = OnceLock::new();
+
+/// The `--output` file, opened up front; `file` is taken by the first result
+///
+/// A streaming command instead keeps `file` and appends one line per result.
+struct JsonOutput {
+    path: PathBuf,
+    file: Mutex<Option<File>>,
+    streaming: AtomicBool,
+}
+
+/// Write the JSON result to `path` for the rest of the run
+///
+/// The file is created at once, so an unwritable path fails before the
+/// command does anything. Human output still goes to the terminal; the
+/// printer's JSON result is written to this file, and a second result is an
+/// error rather than overwriting the first. Only the first call has an effect.
+pub fn set_json_output(path: PathBuf) -> Result<()> {
+    if JSON_OUTPUT.get().is_some() {
+        return Ok(());
+    }
+    let file = File::create(&path)
+        .map_err(|e| ProcError::SystemError(format!("cannot create {}: {}", path.display(), e)))?;
+    let _ = JSON_OUTPUT.set(JsonOutput {
+        path,
+        file: Mutex::new(Some(file)),
+        streaming: AtomicBool::new(false),
+    });
+    Ok(())
+}
+
+/// Treat the `--output` file as a stream of NDJSON lines for the rest of the run
+///
+/// Each [`Printer::print_json_line`] is appended to the file, and a stream
+/// with no lines is a complete result rather than a missing one.
+pub fn stream_json_output() {
+    if let Some(output) = JSON_OUTPUT.get() {
+        output.streaming.store(true, Ordering::Relaxed);
+    }
+}
+
+/// Close out the `--output` file once the command has run
+///
+/// A run that fails, or that ends without writing its JSON result, records
+/// `success: false` and the error in the file instead of leaving it empty.
+/// A run that produced no result is reported as an error. A failed stream
+/// gets the error as its last line.
+pub fn finish_json_output(result: Result<()>) -> Result<()> {
+    let Some(output) = JSON_OUTPUT.get() else {
+        return result;
+    };
+    let streaming = output.streaming.load(Ordering::Relaxed);
+    if streaming && result.is_ok() {
+        return result;
+    }
+    let Some(mut file) = output.file.lock().unwrap_or_else(|e| e.into_inner()).take() else {
+        return result;
+    };
+    let error = result.err().unwrap_or_else(|| {
+        ProcError::SystemError(format!(
+            "no JSON result was produced for {}",
+            output.path.display()
+        ))
+    });
+    let record = Versioned {
+        schema_version: JSON_SCHEMA_VERSION,
+        version: crate::VERSION,
+        data: &ErrorOutput {
+            success: false,
+            error: error.to_string(),
+        },
+    };
+    let json = if streaming {
+        serde_json::to_string(&record)
+    } else {
+        serde_json::to_string_pretty(&record)
+    };
+    if let Ok(json) = json {
+        let _ = writeln!(file, "{}", json);
+    }
+    Err(error)
+}
+
+/// Whether JSON results go to an `--output` file rather than stdout
+pub fn has_json_output() -> bool {
+    JSON_OUTPUT.get().is_some()
+}
+
+/// When to color terminal output
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, clap::ValueEnum)]
+pub enum ColorChoice {
+    /// Color when writing to a terminal, unless `NO_COLOR` is set
+    #[default]
+    Auto,
+    /// Always color, even when piped
+    Always,
+    /// Never color
+    Never,
+}
+
+/// Apply a color choice for the rest of the run
+pub fn set_color(choice: ColorChoice) {
+    match choice {
+        ColorChoice::Auto => {}
+        ColorChoice::Always => colored::control::set_override(true),
+        ColorChoice::Never => colored::control::set_override(false),
+    }
+}
+
+/// CPU and memory levels at which process table cells turn yellow, then red
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Thresholds {
+    /// CPU percentage shown in yellow
+    pub cpu_warn: f32,
+    /// CPU percentage shown in red
+    pub cpu_crit: f32,
+    /// Resident memory in megabytes shown in yellow
+    pub mem_warn_mb: f64,
+    /// Resident memory in megabytes shown in red
+    pub mem_crit_mb: f64,
+}
+
+impl Default for Thresholds {
+    fn default() -> Self {
+        Self {
+            cpu_warn: 50.0,
+            cpu_crit: 80.0,
+            mem_warn_mb: 1024.0,
+            mem_crit_mb: 4096.0,
+        }
+    }
+}
+
+impl Thresholds {
+    /// Build thresholds from the levels given, filling the rest from the defaults
+    ///
+    /// A level given without its partner moves the default partner out of
+    /// the way, so `--cpu-warn 90` alone also raises the red level to 90.
+    pub fn from_levels(
+        cpu_warn: Option<f32>,
+        cpu_crit: Option<f32>,
+        mem_warn_mb: Option<f64>,
+        mem_crit_mb: Option<f64>,
+    ) -> Result<Self> {
+        let default = Self::default();
+        let thresholds = Self {
+            cpu_warn: cpu_warn.unwrap_or(default.cpu_warn.min(cpu_crit.unwrap_or(f32::MAX))),
+            cpu_crit: cpu_crit.unwrap_or(default.cpu_crit.max(cpu_warn.unwrap_or(0.0))),
+            mem_warn_mb: mem_warn_mb
+                .unwrap_or(default.mem_warn_mb.min(mem_crit_mb.unwrap_or(f64::MAX))),
+            mem_crit_mb: mem_crit_mb.unwrap_or(default.mem_crit_mb.max(mem_warn_mb.unwrap_or(0.0))),
+        };
+        thresholds.validate()?;
+        Ok(thresholds)
+    }
+
+    /// Check that each level is a non-negative number and each warning level
+    /// is no higher than its critical level
+    pub fn validate(&self) -> Result<()> {
+        let levels = [
+            ("--cpu-warn", f64::from(self.cpu_warn)),
+            ("--cpu-crit", f64::from(self.cpu_crit)),
+            ("--mem-warn", self.mem_warn_mb),
+            ("--mem-crit", self.mem_crit_mb),
+        ];
+        if let Some((flag, level)) = levels
+            .iter()
+            .find(|(_, level)| !level.is_finite() || *level < 0.0)
+        {
+            return Err(ProcError::InvalidInput(format!(
+                "{} ({}) must be a non-negative number",
+                flag, level
+            )));
+        }
+        if self.cpu_warn > self.cpu_crit {
+            return Err(ProcError::InvalidInput(format!(
+                "--cpu-warn ({}) must not exceed --cpu-crit ({})",
+                self.cpu_warn, self.cpu_crit
+            )));
+        }
+        if self.mem_warn_mb > self.mem_crit_mb {
+            return Err(ProcError::InvalidInput(format!(
+                "--mem-warn ({}) must not exceed --mem-crit ({})",
+                self.mem_warn_mb, self.mem_crit_mb
+            )));
+        }
+        Ok(())
+    }
+}
+
+/// Heat thresholds for the process table (set by `--cpu-warn` and friends)
+static THRESHOLDS: OnceLock<Thresholds> = OnceLock::new();
+
+/// Use these thresholds for the rest of the run. Only the first call has an effect.
+pub fn set_thresholds(thresholds: Thresholds) {
+    let _ = THRESHOLDS.set(thresholds);
+}
+
+/// The thresholds in effect: those set for this run, or the defaults
+fn thresholds() -> Thresholds {
+    THRESHOLDS.get().copied().unwrap_or_default()
+}
+
+/// Color a cell yellow or red once its value reaches a threshold
+fn heat(value: f64, warn: f64, crit: f64, text: &str) -> ColoredString {
+    if value >= crit {
+        text.red().bold()
+    } else if value >= warn {
+        text.yellow()
+    } else {
+        text.normal()
+    }
+}
 
 /// Output format selection
-#[derive(Debug, Clone, Copy, Default)]
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, clap::ValueEnum)]
 pub enum OutputFormat {
     /// Colored, human-readable terminal output
     #[default]
     Human,
     /// Machine-readable JSON output for scripting
     Json,
+    /// Tab-separated fields with no color, header, or banners
+    Plain,
+}
+
+/// A column in the process table
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Column {
+    /// Process ID
+    Pid,
+    /// Directory of the executable
+    Path,
+    /// Process name
+    Name,
+    /// Command arguments (executable omitted, paths shortened)
+    Args,
+    /// CPU usage percentage
+    Cpu,
+    /// Resident memory
+    Mem,
+    /// Virtual memory size
+    Vmem,
+    /// Swapped-out memory
+    Swap,
+    /// Disk read rate, or total bytes read when not sampled
+    Read,
+    /// Disk write rate, or total bytes written when not sampled
+    Write,
+    /// Process status
+    Status,
+    /// Owning user
+    User,
+    /// Parent process ID
+    Ppid,
+    /// Full command line
+    Cmd,
+}
+
+impl Column {
+    /// All column names accepted by `--columns`
+    pub const NAMES: &'static [&'static str] = &[
+        "pid", "path", "name", "args", "cpu", "mem", "vmem", "swap", "read", "write", "status",
+        "user", "ppid", "cmd",
+    ];
+
+    /// Columns shown in plain output when none are selected
+    pub const PLAIN_DEFAULT: &'static [Column] = &[
+        Column::Pid,
+        Column::Name,
+        Column::Cpu,
+        Column::Mem,
+        Column::Status,
+    ];
+
+    /// Columns shown when none are selected
+    pub const DEFAULT: &'static [Column] = &[
+        Column::Pid,
+        Column::Path,
+        Column::Name,
+        Column::Args,
+        Column::Cpu,
+        Column::Mem,
+        Column::Status,
+    ];
+
+    /// Columns shown by `list --io` when none are selected
+    pub const IO_DEFAULT: &'static [Column] = &[
+        Column::Pid,
+        Column::Name,
+        Column::Args,
+        Column::Cpu,
+        Column::Mem,
+        Column::Read,
+        Column::Write,
+        Column::Status,
+    ];
+
+    /// Parse a comma-separated column list (e.g., "pid,name,cpu")
+    pub fn parse_list(input: &str) -> Result<Vec<Column>> {
+        let columns: Vec<Column> = input
+            .split(',')
+            .map(|s| s.trim())
+            .filter(|s| !s.is_empty())
+            .map(|name| {
+                Self::from_name(name).ok_or_else(|| {
+                    ProcError::InvalidInput(format!(
+                        "Unknown column '{}'. Valid columns: {}",
+                        name,
+                        Self::NAMES.join(", ")
+                    ))
+                })
+            })
+            .collect::<Result<_>>()?;
+
+        if columns.is_empty() {
+            return Err(ProcError::InvalidInput(format!(
+                "No columns given. Valid columns: {}",
+                Self::NAMES.join(", ")
+            )));
+        }
+
+        Ok(columns)
+    }
+
+    fn from_name(name: &str) -> Option<Column> {
+        match name.to_lowercase().as_str() {
+            "pid" => Some(Column::Pid),
+            "path" => Some(Column::Path),
+            "name" => Some(Column::Name),
+            "args" => Some(Column::Args),
+            "cpu" => Some(Column::Cpu),
+            "mem" | "memory" => Some(Column::Mem),
+            "vmem" | "virt" => Some(Column::Vmem),
+            "swap" => Some(Column::Swap),
+            "read" => Some(Column::Read),
+            "write" => Some(Column::Write),
+            "status" => Some(Column::Status),
+            "user" => Some(Column::User),
+            "ppid" | "parent" => Some(Column::Ppid),
+            "cmd" | "command" => Some(Column::Cmd),
+            _ => None,
+        }
+    }
+
+    fn header(&self) -> &'static str {
+        match self {
+            Column::Pid => "PID",
+            Column::Path => "PATH",
+            Column::Name => "NAME",
+            Column::Args => "ARGS",
+            Column::Cpu => "CPU%",
+            Column::Mem => "MEM",
+            Column::Vmem => "VMEM",
+            Column::Swap => "SWAP",
+            Column::Read => "READ",
+            Column::Write => "WRITE",
+            Column::Status => "STATUS",
+            Column::User => "USER",
+            Column::Ppid => "PPID",
+            Column::Cmd => "COMMAND",
+        }
+    }
+
+    /// Default width, used as-is for fixed columns and as the share weight
+    /// for flexible ones
+    fn width(&self) -> usize {
+        match self {
+            Column::Pid | Column::Ppid => 7,
+            Column::Path => 20,
+            Column::Name => 12,
+            Column::Args => 35,
+            Column::Cpu => 5,
+            Column::Mem | Column::Swap | Column::Status => 8,
+            Column::Vmem | Column::Read | Column::Write => 10,
+            Column::User => 10,
+            Column::Cmd => 50,
+        }
+    }
+
+    /// Narrowest a flexible column may get; `None` for fixed-width columns
+    fn min_width(&self) -> Option<usize> {
+        match self {
+            Column::Path => Some(10),
+            Column::Name => Some(12),
+            Column::Args => Some(10),
+            Column::Cmd => Some(15),
+            _ => None,
+        }
+    }
+
+    fn right_aligned(&self) -> bool {
+        matches!(
+            self,
+            Column::Cpu
+                | Column::Mem
+                | Column::Vmem
+                | Column::Swap
+                | Column::Read
+                | Column::Write
+                | Column::Status
+        )
+    }
+
+    fn pad(&self, text: &str, width: usize) -> String {
+        if self.right_aligned() {
+            format!("{:>width$}", text, width = width)
+        } else {
+            format!("{:<width$}", text, width = width)
+        }
+    }
+
+    /// Cell text for a process, truncated to fit a column `width` wide
+    fn text(&self, proc: &Process, width: usize) -> String {
+        let max = width - 1;
+        match self {
+            Column::Pid => proc.pid.to_string(),
+            Column::Path => {
+                // Show directory of executable
+                proc.exe_path
+                    .as_ref()
+                    .and_then(|p| {
+                        std::path::Path::new(p)
+                            .parent()
+                            .map(|parent| truncate_path(&parent.to_string_lossy(), max))
+                    })
+                    .unwrap_or_else(|| "-".to_string())
+            }
+            Column::Name => truncate_string(&proc.name, max),
+            Column::Args => {
+                // Show command args (skip executable, simplify paths to filenames)
+                proc.command
+                    .as_ref()
+                    .map(|c| {
+                        let parts: Vec<&str> = c.split_whitespace().collect();
+                        if parts.len() > 1 {
+                            let args: Vec<String> = parts[1..]
+                                .iter()
+                                .map(|arg| {
+                                    if arg.contains('/') && !arg.starts_with('-') {
+                                        // It's a path - extract filename
+                                        std::path::Path::new(arg)
+                                            .file_name()
+                                            .map(|f| f.to_string_lossy().to_string())
+                                            .unwrap_or_else(|| arg.to_string())
+                                    } else {
+                                        arg.to_string()
+                                    }
+                                })
+                                .collect();
+                            truncate_string(&args.join(" "), max)
+                        } else {
+                            truncate_string(c, max)
+                        }
+                    })
+                    .unwrap_or_else(|| "-".to_string())
+            }
+            Column::Cpu => format!("{:.1}", proc.cpu_percent),
+            Column::Mem => format!("{:.1}MB", proc.memory_mb),
+            Column::Vmem => format!("{:.1}MB", proc.virtual_memory_mb),
+            Column::Swap => proc
+                .swap_mb
+                .map(|mb| format!("{:.1}MB", mb))
+                .unwrap_or_else(|| "-".to_string()),
+            Column::Read => io_text(proc.read_rate, proc.read_bytes),
+            Column::Write => io_text(proc.write_rate, proc.written_bytes),
+            Column::Status => format!("{:?}", proc.status),
+            Column::User => truncate_string(proc.user.as_deref().unwrap_or("-"), max),
+            Column::Ppid => proc
+                .parent_pid
+                .map(|p| p.to_string())
+                .unwrap_or_else(|| "-".to_string()),
+            Column::Cmd => truncate_string(proc.command.as_deref().unwrap_or("-"), max),
+        }
+    }
+
+    /// Untruncated cell value for plain output
+    fn plain_text(&self, proc: &Process) -> String {
+        let text = match self {
+            Column::Path => proc
+                .exe_path
+                .as_ref()
+                .and_then(|p| std::path::Path::new(p).parent())
+                .map(|parent| parent.to_string_lossy().to_string())
+                .unwrap_or_else(|| "-".to_string()),
+            Column::Name => proc.name.clone(),
+            Column::Args => proc
+                .command
+                .as_ref()
+                .map(|c| c.split_whitespace().skip(1).collect::<Vec<_>>().join(" "))
+                .filter(|args| !args.is_empty())
+                .unwrap_or_else(|| "-".to_string()),
+            Column::Mem => format!("{:.1}", proc.memory_mb),
+            Column::Vmem => format!("{:.1}", proc.virtual_memory_mb),
+            Column::Swap => proc
+                .swap_mb
+                .map(|mb| format!("{:.1}", mb))
+                .unwrap_or_else(|| "-".to_string()),
+            // Bytes per second when sampled, total bytes otherwise
+            Column::Read => io_plain(proc.read_rate, proc.read_bytes),
+            Column::Write => io_plain(proc.write_rate, proc.written_bytes),
+            Column::Status => format!("{:?}", proc.status).to_lowercase(),
+            Column::User => proc.user.clone().unwrap_or_else(|| "-".to_string()),
+            Column::Cmd => proc.command.clone().unwrap_or_else(|| "-".to_string()),
+            Column::Pid | Column::Cpu | Column::Ppid => self.text(proc, self.width()),
+        };
+        // Keep one record per line and one field per tab
+        text.replace(['\t', '\n'], " ")
+    }
+
+    /// Apply the column's color to already-padded cell text
+    fn colorize(&self, proc: &Process, text: &str) -> ColoredString {
+        match self {
+            Column::Pid | Column::Ppid => text.cyan(),
+            Column::Name => text.white(),
+            Column::Path | Column::Args | Column::User | Column::Cmd => text.bright_black(),
+            Column::Cpu => {
+                let limits = thresholds();
+                heat(
+                    proc.cpu_percent as f64,
+                    limits.cpu_warn as f64,
+                    limits.cpu_crit as f64,
+                    text,
+                )
+            }
+            Column::Mem => {
+                let limits = thresholds();
+                heat(proc.memory_mb, limits.mem_warn_mb, limits.mem_crit_mb, text)
+            }
+            Column::Vmem | Column::Swap | Column::Read | Column::Write => text.normal(),
+            Column::Status => colorize_status(&proc.status, text),
+        }
+    }
+}
+
+/// Disk I/O cell: the rate when sampled, else the running total
+fn io_text(rate: Option<f64>, total: Option<u64>) -> String {
+    match (rate, total) {
+        (Some(rate), _) => format!("{}/s", format_bytes(rate)),
+        (None, Some(total)) => format_bytes(total as f64),
+        (None, None) => "-".to_string(),
+    }
+}
+
+/// Disk I/O value for plain output, in bytes (per second when sampled)
+fn io_plain(rate: Option<f64>, total: Option<u64>) -> String {
+    match (rate, total) {
+        (Some(rate), _) => format!("{:.0}", rate),
+        (None, Some(total)) => total.to_string(),
+        (None, None) => "-".to_string(),
+    }
 }
 
 /// Main printer for CLI output
 pub struct Printer {
     format: OutputFormat,
     verbose: bool,
+    columns: Option<Vec<Column>>,
+    header: bool,
+    system: Option<SystemSummary>,
+    notes: HashMap<u32, String>,
+    spans: HashMap<u32, MatchSpan>,
 }
 
 impl Printer {
     /// Creates a new printer with the specified format and verbosity.
     pub fn new(format: OutputFormat, verbose: bool) -> Self {
-        Self { format, verbose }
+        Self {
+            format,
+            verbose,
+            columns: None,
+            header: true,
+            system: None,
+            notes: HashMap::new(),
+            spans: HashMap::new(),
+        }
+    }
+
+    /// Sets the columns used for the process table
+    pub fn with_columns(mut self, columns: Vec<Column>) -> Self {
+        self.columns = Some(columns);
+        self
+    }
+
+    /// Shows or hides the column header and separator above tables
+    pub fn with_header(mut self, header: bool) -> Self {
+        self.header = header;
+        self
+    }
+
+    /// Whether tables get a column header and separator
+    pub fn shows_header(&self) -> bool {
+        self.header
+    }
+
+    /// Shows a machine-wide CPU and memory summary with process lists
+    pub fn with_system(mut self, system: SystemSummary) -> Self {
+        self.system = Some(system);
+        self
+    }
+
+    /// Adds an extra line under these PIDs in verbose process lists
+    pub fn with_notes(mut self, notes: HashMap<u32, String>) -> Self {
+        self.notes = notes;
+        self
+    }
+
+    /// Adds where a name pattern matched to these PIDs in JSON process lists
+    pub fn with_match_spans(mut self, spans: HashMap<u32, MatchSpan>) -> Self {
+        self.spans = spans;
+        self
+    }
+
+    /// Print the one-line machine summary, if one was attached
+    fn print_system_header(&self) {
+        let Some(ref system) = self.system else {
+            return;
+        };
+        println!(
+            "{} {} core{}, CPU {:.1}%, memory {:.1} / {:.1} GB ({:.0}%)",
+            "System:".bright_black(),
+            system.cores.to_string().cyan(),
+            if system.cores == 1 { "" } else { "s" },
+            system.cpu_percent,
+            system.used_memory_mb / 1024.0,
+            system.total_memory_mb / 1024.0,
+            system.memory_percent()
+        );
+    }
+
+    /// Note processes a directory or path filter couldn't check for lack of permission
+    pub fn print_hidden_by_permissions(&self, hidden: usize) {
+        if hidden == 0 || self.format != OutputFormat::Human {
+            return;
+        }
+        // Root already sees everything sudo would
+        #[cfg(unix)]
+        let hint = if nix::unistd::geteuid().is_root() {
+            ""
+        } else {
+            "; try sudo"
+        };
+        #[cfg(not(unix))]
+        let hint = "; try sudo";
+        println!(
+            "{} {} process{} hidden due to permissions (working directory or path unreadable){}",
+            "ℹ".blue().bold(),
+            hidden,
+            if hidden == 1 { "" } else { "es" },
+            hint
+        );
+    }
+
+    /// In verbose mode, note that results only cover the container proc runs in
+    pub fn print_container_scope(&self, runtime: Option<&str>) {
+        let Some(runtime) = runtime else {
+            return;
+        };
+        if !self.verbose || self.format != OutputFormat::Human {
+            return;
+        }
+        println!(
+            "{} Inside a {} container: only processes in its PID namespace are shown (host processes are not)",
+            "ℹ".blue().bold(),
+            runtime
+        );
+    }
+
+    /// Whether a JSON result should be produced
+    ///
+    /// True in JSON mode, and in any mode when `--output` captures the result.
+    pub fn wants_json(&self) -> bool {
+        self.format == OutputFormat::Json || JSON_OUTPUT.get().is_some()
+    }
+
+    /// Print a blank spacing line, only when stdout is a terminal
+    ///
+    /// Blank lines around headers and at the end of output are decoration.
+    /// Leaving them out when output is piped or captured keeps it stable
+    /// for diffs and exact-match tests.
+    pub fn blank_line() {
+        if std::io::stdout().is_terminal() {
+            println!();
+        }
     }
 
     /// Print a success message
@@ -34,8 +717,8 @@ impl Printer {
             OutputFormat::Human => {
                 println!("{} {}", "✓".green().bold(), message.green());
             }
-            OutputFormat::Json => {
-                // JSON output handled separately
+            OutputFormat::Json | OutputFormat::Plain => {
+                // Machine-readable output handled separately
             }
         }
     }
@@ -46,8 +729,8 @@ impl Printer {
             OutputFormat::Human => {
                 eprintln!("{} {}", "✗".red().bold(), message.red());
             }
-            OutputFormat::Json => {
-                // JSON output handled separately
+            OutputFormat::Json | OutputFormat::Plain => {
+                // Machine-readable output handled separately
             }
         }
     }
@@ -58,31 +741,135 @@ impl Printer {
             OutputFormat::Human => {
                 println!("{} {}", "⚠".yellow().bold(), message.yellow());
             }
-            OutputFormat::Json => {
-                // JSON output handled separately
+            OutputFormat::Json | OutputFormat::Plain => {
+                // Machine-readable output handled separately
             }
         }
     }
 
     /// Print a list of processes with optional context (e.g., "in /path/to/dir")
-    pub fn print_processes_with_context(&self, processes: &[Process], context: Option<&str>) {
+    pub fn print_processes_with_context(
+        &self,
+        processes: &[Process],
+        context: Option<&str>,
+    ) -> Result<()> {
+        if self.wants_json() {
+            self.print_json(&ProcessListOutput {
+                action: "list",
+                success: true,
+                count: processes.len(),
+                system: self.system.as_ref(),
+                processes: processes
+                    .iter()
+                    .map(|process| ListedProcess {
+                        process,
+                        span: self.spans.get(&process.pid),
+                    })
+                    .collect(),
+            })?;
+        }
         match self.format {
             OutputFormat::Human => self.print_processes_human(processes, context),
-            OutputFormat::Json => self.print_json(&ProcessListOutput {
+            OutputFormat::Plain => self.print_processes_plain(processes),
+            OutputFormat::Json => {}
+        }
+        Ok(())
+    }
+
+    /// Print one tab-separated line per process, with no header or color
+    fn print_processes_plain(&self, processes: &[Process]) {
+        let columns = self.columns.as_deref().unwrap_or(Column::PLAIN_DEFAULT);
+        for proc in processes {
+            let fields: Vec<String> = columns.iter().map(|col| col.plain_text(proc)).collect();
+            println!("{}", fields.join("\t"));
+        }
+    }
+
+    /// Print just the number of matching processes
+    pub fn print_count(&self, count: usize) -> Result<()> {
+        if self.wants_json() {
+            self.print_json(&CountOutput {
                 action: "list",
                 success: true,
-                count: processes.len(),
-                processes,
-            }),
+                count,
+            })?;
+        }
+        if self.format != OutputFormat::Json {
+            println!("{}", count);
+        }
+        Ok(())
+    }
+
+    /// Compare a process count against expected bounds and report the outcome
+    ///
+    /// Returns `ProcError::CheckFailed` when the count is outside the bounds.
+    pub fn check_count(
+        &self,
+        found: usize,
+        expected: Option<usize>,
+        min: Option<usize>,
+        max: Option<usize>,
+    ) -> Result<()> {
+        let ok = expected.is_none_or(|n| found == n)
+            && min.is_none_or(|n| found >= n)
+            && max.is_none_or(|n| found <= n);
+
+        let mut bounds = Vec::new();
+        if let Some(n) = expected {
+            bounds.push(n.to_string());
+        }
+        if let Some(n) = min {
+            bounds.push(format!("at least {}", n));
+        }
+        if let Some(n) = max {
+            bounds.push(format!("at most {}", n));
+        }
+        let bounds = bounds.join(", ");
+
+        if self.wants_json() {
+            self.print_json(&CountCheckOutput {
+                action: "list",
+                success: ok,
+                expected,
+                expected_min: min,
+                expected_max: max,
+                found,
+                ok,
+            })?;
+        }
+        match self.format {
+            OutputFormat::Human => {
+                if ok {
+                    println!(
+                        "{} Found {} process{} (expected {})",
+                        "✓".green().bold(),
+                        found.to_string().cyan().bold(),
+                        if found == 1 { "" } else { "es" },
+                        bounds
+                    );
+                }
+            }
+            OutputFormat::Plain => println!("{}", found),
+            OutputFormat::Json => {}
+        }
+
+        if ok {
+            Ok(())
+        } else {
+            Err(ProcError::CheckFailed(format!(
+                "expected {}, found {}",
+                bounds, found
+            )))
         }
     }
 
     /// Print a list of processes
-    pub fn print_processes(&self, processes: &[Process]) {
+    pub fn print_processes(&self, processes: &[Process]) -> Result<()> {
         self.print_processes_with_context(processes, None)
     }
 
-    fn print_processes_human(&self, processes: &[Process], context: Option<&str>) {
+    pub(crate) fn print_processes_human(&self, processes: &[Process], context: Option<&str>) {
+        self.print_system_header();
         if processes.is_empty() {
             let msg = match context {
                 Some(ctx) => format!("No processes found {}", ctx),
@@ -100,11 +887,14 @@ impl Printer {
             if processes.len() == 1 { "" } else { "es" },
             context_str.bright_black()
         );
-        println!();
+        Self::blank_line();
 
         if self.verbose {
             // Verbose: full details, nothing truncated
-            for proc in processes {
+            for (i, proc) in processes.iter().enumerate() {
+                if i > 0 {
+                    println!();
+                }
                 let status_str = format!("{:?}", proc.status);
                 let status_colored = colorize_status(&proc.status, &status_str);
 
@@ -134,95 +924,229 @@ impl Printer {
                         ppid.to_string().bright_black()
                     );
                 }
-                println!();
+                if let Some(note) = self.notes.get(&proc.pid) {
+                    println!("    {}", note);
+                }
             }
         } else {
-            // Normal: compact table with all key columns
+            // Normal: compact table driven by the selected columns
+            let columns = self.columns.as_deref().unwrap_or(Column::DEFAULT);
+            let widths = fit_widths(columns, terminal_width());
+            let last = columns.len() - 1;
+            let header: Vec<String> = columns
+                .iter()
+                .zip(&widths)
+                .enumerate()
+                .map(|(i, (col, &width))| {
+                    let text = if i == last && !col.right_aligned() {
+                        col.header().to_string()
+                    } else {
+                        col.pad(col.header(), width)
+                    };
+                    text.bright_blue().bold().to_string()
+                })
+                .collect();
+            if self.header {
+                println!("{}", header.join(" "));
+                let width: usize = widths.iter().map(|w| w + 1).sum();
+                println!("{}", "─".repeat(width.saturating_sub(1)).bright_black());
+            }
+
+            for proc in processes {
+                let row: Vec<String> = columns
+                    .iter()
+                    .zip(&widths)
+                    .enumerate()
+                    .map(|(i, (col, &width))| {
+                        // Don't pad a trailing left-aligned column
+                        let text = if i == last && !col.right_aligned() {
+                            col.text(proc, width)
+                        } else {
+                            col.pad(&col.text(proc, width), width)
+                        };
+                        col.colorize(proc, &text).to_string()
+                    })
+                    .collect();
+                println!("{}", row.join(" "));
+            }
+        }
+        Self::blank_line();
+    }
+
+    /// Print how processes changed between two samples `interval_secs` apart
+    pub fn print_process_deltas(&self, deltas: &[ProcessDelta], interval_secs: u64) -> Result<()> {
+        if self.wants_json() {
+            self.print_json(&DeltaOutput {
+                action: "delta",
+                success: true,
+                interval_secs,
+                count: deltas.len(),
+                processes: deltas,
+            })?;
+        }
+        match self.format {
+            OutputFormat::Human => self.print_process_deltas_human(deltas, interval_secs),
+            OutputFormat::Plain => {
+                for delta in deltas {
+                    println!(
+                        "{}\t{}\t{:.1}\t{:+.1}\t{:.1}\t{:+.1}\t{}",
+                        delta.process.pid,
+                        delta.process.name.replace(['\t', '\n'], " "),
+                        delta.process.cpu_percent,
+                        delta.cpu_delta,
+                        delta.process.memory_mb,
+                        delta.memory_delta_mb,
+                        change_label(delta.change)
+                    );
+                }
+            }
+            OutputFormat::Json => {}
+        }
+        Ok(())
+    }
+
+    fn print_process_deltas_human(&self, deltas: &[ProcessDelta], interval_secs: u64) {
+        if deltas.is_empty() {
+            self.warning("No processes found");
+            return;
+        }
+
+        println!(
+            "{} {} process{} over {}s",
+            "✓".green().bold(),
+            deltas.len().to_string().cyan().bold(),
+            if deltas.len() == 1 { "" } else { "es" },
+            interval_secs
+        );
+        Self::blank_line();
+        if self.header {
             println!(
-                "{:<7} {:<20} {:<12} {:<35} {:>5} {:>8} {:>8}",
-                "PID".bright_blue().bold(),
-                "PATH".bright_blue().bold(),
-                "NAME".bright_blue().bold(),
-                "ARGS".bright_blue().bold(),
-                "CPU%".bright_blue().bold(),
-                "MEM".bright_blue().bold(),
-                "STATUS".bright_blue().bold(),
+                "{} {} {} {} {} {} {}",
+                format!("{:<7}", "PID").bright_blue().bold(),
+                format!("{:<20}", "NAME").bright_blue().bold(),
+                format!("{:>6}", "CPU%").bright_blue().bold(),
+                format!("{:>7}", "ΔCPU").bright_blue().bold(),
+                format!("{:>10}", "MEM").bright_blue().bold(),
+                format!("{:>10}", "ΔMEM").bright_blue().bold(),
+                "CHANGE".bright_blue().bold()
             );
-            println!("{}", "─".repeat(100).bright_black());
+            println!("{}", "─".repeat(72).bright_black());
+        }
 
-            for proc in processes {
-                let name = truncate_string(&proc.name, 11);
-                let status_str = format!("{:?}", proc.status);
-                let status_colored = colorize_status(&proc.status, &status_str);
+        for delta in deltas {
+            let proc = &delta.process;
+            let signed = |text: String, value: f64| {
+                if value > 0.0 {
+                    text.yellow()
+                } else if value < 0.0 {
+                    text.green()
+                } else {
+                    text.normal()
+                }
+            };
+            let change = match delta.change {
+                DeltaChange::Changed => "".normal(),
+                DeltaChange::New => "new".cyan(),
+                DeltaChange::Gone => "gone".bright_black(),
+            };
+            println!(
+                "{} {} {:>6.1} {} {:>10} {} {}",
+                format!("{:<7}", proc.pid).cyan(),
+                format!("{:<20}", truncate_string(&proc.name, 19)).white(),
+                proc.cpu_percent,
+                signed(format!("{:>+7.1}", delta.cpu_delta), delta.cpu_delta as f64),
+                format!("{:.1}MB", proc.memory_mb),
+                signed(
+                    format!("{:>+10}", format!("{:+.1}MB", delta.memory_delta_mb)),
+                    delta.memory_delta_mb
+                ),
+                change
+            );
+        }
+        Self::blank_line();
+    }
 
-                // Show directory of executable
-                let path_display = proc
-                    .exe_path
-                    .as_ref()
-                    .map(|p| {
-                        std::path::Path::new(p)
-                            .parent()
-                            .map(|parent| truncate_path(&parent.to_string_lossy(), 19))
-                            .unwrap_or_else(|| "-".to_string())
-                    })
-                    .unwrap_or_else(|| "-".to_string());
+    /// Print processes as a tree, showing each match within its ancestry
+    ///
+    /// `all` is the full process table used to look up ancestors. Ancestors that
+    /// are not themselves matches are dimmed. JSON and plain output stay flat.
+    pub fn print_processes_as_tree(
+        &self,
+        processes: &[Process],
+        all: &[Process],
+        context: Option<&str>,
+    ) -> Result<()> {
+        if self.format != OutputFormat::Human || processes.is_empty() {
+            return self.print_processes_with_context(processes, context);
+        }
 
-                // Show command args (skip executable, simplify paths to filenames)
-                let cmd_display = proc
-                    .command
-                    .as_ref()
-                    .map(|c| {
-                        // Skip the first element (executable) to show just the args
-                        let parts: Vec<&str> = c.split_whitespace().collect();
-                        if parts.len() > 1 {
-                            // Simplify paths to just filenames where possible
-                            let args: Vec<String> = parts[1..]
-                                .iter()
-                                .map(|arg| {
-                                    if arg.contains('/') && !arg.starts_with('-') {
-                                        // It's a path - extract filename
-                                        std::path::Path::new(arg)
-                                            .file_name()
-                                            .map(|f| f.to_string_lossy().to_string())
-                                            .unwrap_or_else(|| arg.to_string())
-                                    } else {
-                                        arg.to_string()
-                                    }
-                                })
-                                .collect();
-                            truncate_string(&args.join(" "), 34)
-                        } else {
-                            truncate_string(c, 34)
-                        }
-                    })
-                    .unwrap_or_else(|| "-".to_string());
+        let pid_map: HashMap<u32, &Process> = all.iter().map(|p| (p.pid, p)).collect();
+        let matches: HashSet<u32> = processes.iter().map(|p| p.pid).collect();
 
-                println!(
-                    "{:<7} {:<20} {:<12} {:<35} {:>5.1} {:>6.1}MB {:>8}",
-                    proc.pid.to_string().cyan(),
-                    path_display.bright_black(),
-                    name.white(),
-                    cmd_display.bright_black(),
-                    proc.cpu_percent,
-                    proc.memory_mb,
-                    status_colored,
-                );
+        // Collect matches plus every ancestor on the way up to the root
+        let mut included: HashSet<u32> = HashSet::new();
+        for proc in processes {
+            let mut current = Some(proc.pid);
+            let mut steps = 0;
+            while let Some(pid) = current {
+                if !included.insert(pid) || steps > MAX_TREE_DEPTH {
+                    break;
+                }
+                current = pid_map
+                    .get(&pid)
+                    .and_then(|p| p.parent_pid)
+                    .filter(|ppid| pid_map.contains_key(ppid));
+                steps += 1;
+            }
+        }
+
+        let mut nodes: Vec<&Process> = included
+            .iter()
+            .filter_map(|pid| pid_map.get(pid).copied())
+            .collect();
+        nodes.sort_by_key(|p| p.pid);
+
+        let mut children_map: HashMap<u32, Vec<&Process>> = HashMap::new();
+        let mut roots: Vec<&Process> = Vec::new();
+        for proc in nodes {
+            match proc.parent_pid.filter(|ppid| included.contains(ppid)) {
+                Some(ppid) if ppid != proc.pid => children_map.entry(ppid).or_default().push(proc),
+                _ => roots.push(proc),
             }
         }
-        println!();
+
+        self.print_system_header();
+        let context_str = context.map(|c| format!(" {}", c)).unwrap_or_default();
+        println!(
+            "{} Found {} process{}{}",
+            "✓".green().bold(),
+            processes.len().to_string().cyan().bold(),
+            if processes.len() == 1 { "" } else { "es" },
+            context_str.bright_black()
+        );
+        Self::blank_line();
+
+        TreePrinter::new(&children_map)
+            .matches(&matches)
+            .print_roots(&roots);
+        Self::blank_line();
+        Ok(())
     }
 
     /// Print port information
-    pub fn print_ports(&self, ports: &[PortInfo]) {
-        match self.format {
-            OutputFormat::Human => self.print_ports_human(ports),
-            OutputFormat::Json => self.print_json(&PortListOutput {
+    pub fn print_ports(&self, ports: &[PortInfo]) -> Result<()> {
+        if self.wants_json() {
+            self.print_json(&PortListOutput {
                 action: "ports",
                 success: true,
                 count: ports.len(),
                 ports,
-            }),
+            })?;
         }
+        if self.format != OutputFormat::Json {
+            self.print_ports_human(ports);
+        }
+        Ok(())
     }
 
     fn print_ports_human(&self, ports: &[PortInfo]) {
@@ -237,18 +1161,20 @@ impl Printer {
             ports.len().to_string().cyan().bold(),
             if ports.len() == 1 { "" } else { "s" }
         );
-        println!();
+        Self::blank_line();
 
         // Header
-        println!(
-            "{:<8} {:<10} {:<8} {:<20} {:<15}",
-            "PORT".bright_blue().bold(),
-            "PROTO".bright_blue().bold(),
-            "PID".bright_blue().bold(),
-            "PROCESS".bright_blue().bold(),
-            "ADDRESS".bright_blue().bold()
-        );
-        println!("{}", "─".repeat(65).bright_black());
+        if self.header {
+            println!(
+                "{:<8} {:<10} {:<8} {:<20} {:<15}",
+                "PORT".bright_blue().bold(),
+                "PROTO".bright_blue().bold(),
+                "PID".bright_blue().bold(),
+                "PROCESS".bright_blue().bold(),
+                "ADDRESS".bright_blue().bold()
+            );
+            println!("{}", "─".repeat(65).bright_black());
+        }
 
         for port in ports {
             let addr = port.address.as_deref().unwrap_or("*");
@@ -263,19 +1189,26 @@ impl Printer {
                 addr.bright_black()
             );
         }
-        println!();
+        Self::blank_line();
     }
 
     /// Print a single port info (for `proc on :port`)
-    pub fn print_port_info(&self, port_info: &PortInfo) {
+    pub fn print_port_info(&self, port_info: &PortInfo) -> Result<()> {
+        if self.wants_json() {
+            self.print_json(&SinglePortOutput {
+                action: "on",
+                success: true,
+                port: port_info,
+            })?;
+        }
         match self.format {
-            OutputFormat::Human => {
+            OutputFormat::Human | OutputFormat::Plain => {
                 println!(
                     "{} Process on port {}:",
                     "✓".green().bold(),
                     port_info.port.to_string().cyan().bold()
                 );
-                println!();
+                Self::blank_line();
                 println!(
                     "  {} {}",
                     "Name:".bright_black(),
@@ -290,36 +1223,243 @@ impl Printer {
                 if let Some(ref addr) = port_info.address {
                     println!("  {} {}", "Address:".bright_black(), addr);
                 }
-                println!();
+                Self::blank_line();
             }
-            OutputFormat::Json => self.print_json(&SinglePortOutput {
-                action: "on",
+            OutputFormat::Json => {}
+        }
+        Ok(())
+    }
+
+    /// Print JSON output for any serializable struct
+    ///
+    /// The result is prefixed with `schema_version` and the crate `version`.
+    /// Goes to the `--output` file when one is set, otherwise to stdout. The
+    /// file holds a single result, so writing a second one fails.
+    pub fn print_json<T: Serialize>(&self, data: &T) -> Result<()> {
+        let versioned = Versioned {
+            schema_version: JSON_SCHEMA_VERSION,
+            version: crate::VERSION,
+            data,
+        };
+        let json = serde_json::to_string_pretty(&versioned)?;
+        let Some(output) = JSON_OUTPUT.get() else {
+            println!("{}", json);
+            return Ok(());
+        };
+
+        let mut file = output
+            .file
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .take()
+            .ok_or_else(|| {
+                ProcError::InvalidInput(format!(
+                    "{} already holds this run's JSON result",
+                    output.path.display()
+                ))
+            })?;
+        writeln!(file, "{}", json).map_err(|e| {
+            ProcError::SystemError(format!("failed to write {}: {}", output.path.display(), e))
+        })
+    }
+
+    /// Print one compact JSON object on its own line, for streaming (NDJSON)
+    ///
+    /// Carries the same `schema_version` and `version` prefix as
+    /// [`Printer::print_json`] and is flushed at once. Appended to the
+    /// `--output` file when one is set, otherwise written to stdout. Fails when
+    /// stdout is closed, e.g. by the reader of a pipe exiting.
+    pub fn print_json_line<T: Serialize>(&self, data: &T) -> std::io::Result<()> {
+        let versioned = Versioned {
+            schema_version: JSON_SCHEMA_VERSION,
+            version: crate::VERSION,
+            data,
+        };
+        let json = serde_json::to_string(&versioned)?;
+        if let Some(output) = JSON_OUTPUT.get() {
+            let mut file = output.file.lock().unwrap_or_else(|e| e.into_inner());
+            if let Some(file) = file.as_mut() {
+                writeln!(file, "{}", json)?;
+                return file.flush();
+            }
+        }
+        let mut out = std::io::stdout().lock();
+        writeln!(out, "{}", json)?;
+        out.flush()
+    }
+
+    /// Explain, in human output, why zombie or dead processes were not signaled
+    pub fn print_defunct(&self, defunct: &[Process]) {
+        if self.format != OutputFormat::Human {
+            return;
+        }
+        for proc in defunct {
+            let state = match proc.status {
+                ProcessStatus::Zombie => "already a zombie",
+                _ => "already exiting",
+            };
+            let hint = match proc.parent_pid {
+                Some(ppid) => format!("signal its parent PID {} to reap it", ppid),
+                None => "only its parent can reap it".to_string(),
+            };
+            println!(
+                "{} {} [PID {}] is {}; {}",
+                "⚠".yellow().bold(),
+                proc.name.white(),
+                proc.pid.to_string().cyan(),
+                state.yellow(),
+                hint
+            );
+        }
+    }
+
+    /// Print the processes a kill would signal, without signaling them
+    pub fn print_kill_dry_run(&self, processes: &[Process]) -> Result<()> {
+        self.print_kill_dry_run_json(processes)?;
+        if self.format == OutputFormat::Json {
+            return Ok(());
+        }
+        self.warning(&format!(
+            "Dry run: would kill {} process{}",
+            processes.len(),
+            if processes.len() == 1 { "" } else { "es" }
+        ));
+        match self.format {
+            OutputFormat::Plain => self.print_processes_plain(processes),
+            _ => self.print_processes_human(processes, None),
+        }
+        Ok(())
+    }
+
+    fn print_kill_dry_run_json(&self, processes: &[Process]) -> Result<()> {
+        if self.wants_json() {
+            self.print_json(&KillOutput {
+                action: "kill",
                 success: true,
-                port: port_info,
-            }),
+                dry_run: true,
+                killed_count: 0,
+                confirmed_count: 0,
+                escalated_count: 0,
+                defunct_count: 0,
+                skipped_count: 0,
+                failed_count: 0,
+                killed: &[],
+                confirmed: &[],
+                escalated: &[],
+                defunct: &[],
+                skipped: &[],
+                failed: &[],
+                would_kill: Some(processes),
+            })?;
         }
+        Ok(())
     }
 
-    /// Print JSON output for any serializable type
-    pub fn print_json<T: Serialize>(&self, data: &T) {
-        match serde_json::to_string_pretty(data) {
-            Ok(json) => println!("{}", json),
-            Err(e) => eprintln!("Failed to serialize JSON: {}", e),
+    /// Print what `kill --tree --dry-run` would kill, indented as a tree
+    ///
+    /// `targets` are the matched processes whose subtrees were added; they
+    /// are highlighted. JSON and plain output are the same as
+    /// [`Printer::print_kill_dry_run`].
+    pub fn print_kill_dry_run_tree(
+        &self,
+        processes: &[Process],
+        targets: &HashSet<u32>,
+    ) -> Result<()> {
+        if self.format != OutputFormat::Human {
+            return self.print_kill_dry_run(processes);
         }
+        self.print_kill_dry_run_json(processes)?;
+        self.warning(&format!(
+            "Dry run: would kill {} process{}",
+            processes.len(),
+            if processes.len() == 1 { "" } else { "es" }
+        ));
+        Self::blank_line();
+
+        let children_map = children_map(processes);
+        let pids: HashSet<u32> = processes.iter().map(|p| p.pid).collect();
+        let roots: Vec<&Process> = processes
+            .iter()
+            .filter(|p| p.parent_pid.is_none_or(|ppid| !pids.contains(&ppid)))
+            .collect();
+        TreePrinter::new(&children_map)
+            .highlight(targets)
+            .print_roots(&roots);
+        Self::blank_line();
+        Ok(())
     }
 
     /// Print kill confirmation
-    pub fn print_kill_result(&self, killed: &[Process], failed: &[(Process, String)]) {
+    pub fn print_kill_result(
+        &self,
+        killed: &[Process],
+        confirmed: &[Process],
+        escalated: &[Process],
+        defunct: &[Process],
+        skipped: &[Process],
+        failed: &[(Process, String)],
+    ) -> Result<()> {
+        if self.wants_json() {
+            self.print_json(&KillOutput {
+                action: "kill",
+                success: failed.is_empty(),
+                dry_run: false,
+                killed_count: killed.len(),
+                confirmed_count: confirmed.len(),
+                escalated_count: escalated.len(),
+                defunct_count: defunct.len(),
+                skipped_count: skipped.len(),
+                failed_count: failed.len(),
+                killed,
+                confirmed,
+                escalated,
+                defunct,
+                skipped,
+                failed: &failed
+                    .iter()
+                    .map(|(p, e)| FailedKill {
+                        process: p,
+                        error: e,
+                    })
+                    .collect::<Vec<_>>(),
+                would_kill: None,
+            })?;
+        }
         match self.format {
-            OutputFormat::Human => {
-                if !killed.is_empty() {
+            OutputFormat::Human | OutputFormat::Plain => {
+                for (procs, suffix) in [
+                    (killed, ""),
+                    (confirmed, " (confirmed exited)"),
+                    (escalated, " with SIGKILL after they ignored SIGTERM"),
+                ] {
+                    if procs.is_empty() {
+                        continue;
+                    }
                     println!(
-                        "{} Killed {} process{}",
+                        "{} Killed {} process{}{}",
                         "✓".green().bold(),
-                        killed.len().to_string().cyan().bold(),
-                        if killed.len() == 1 { "" } else { "es" }
+                        procs.len().to_string().cyan().bold(),
+                        if procs.len() == 1 { "" } else { "es" },
+                        suffix
+                    );
+                    for proc in procs {
+                        println!(
+                            "  {} {} [PID {}]",
+                            "→".bright_black(),
+                            proc.name.white(),
+                            proc.pid.to_string().cyan()
+                        );
+                    }
+                }
+                self.print_defunct(defunct);
+                if !skipped.is_empty() {
+                    println!(
+                        "{} Skipped {} process{}",
+                        "-".bright_black().bold(),
+                        skipped.len().to_string().cyan().bold(),
+                        if skipped.len() == 1 { "" } else { "es" }
                     );
-                    for proc in killed {
+                    for proc in skipped {
                         println!(
                             "  {} {} [PID {}]",
                             "→".bright_black(),
@@ -346,43 +1486,78 @@ impl Printer {
                     }
                 }
             }
-            OutputFormat::Json => {
-                self.print_json(&KillOutput {
-                    action: "kill",
-                    success: failed.is_empty(),
-                    killed_count: killed.len(),
-                    failed_count: failed.len(),
-                    killed,
-                    failed: &failed
-                        .iter()
-                        .map(|(p, e)| FailedKill {
-                            process: p,
-                            error: e,
-                        })
-                        .collect::<Vec<_>>(),
-                });
+            OutputFormat::Json => {}
+        }
+        Ok(())
+    }
+}
+
+/// Short label for a delta's change, empty for processes present throughout
+fn change_label(change: DeltaChange) -> &'static str {
+    match change {
+        DeltaChange::Changed => "",
+        DeltaChange::New => "new",
+        DeltaChange::Gone => "gone",
+    }
+}
+
+/// Width of the terminal on stdout, or 80 when it can't be detected (e.g. piped)
+fn terminal_width() -> usize {
+    terminal_size::terminal_size()
+        .map(|(terminal_size::Width(w), _)| w as usize)
+        .unwrap_or(80)
+}
+
+/// Fit column widths to a table `total` characters wide
+///
+/// Fixed columns keep their default width. Flexible ones (path, name, args,
+/// command) start at their minimum and split whatever space is left in
+/// proportion to their defaults, so very narrow terminals still wrap.
+fn fit_widths(columns: &[Column], total: usize) -> Vec<usize> {
+    let separators = columns.len().saturating_sub(1);
+    let (mut used, mut weights) = (separators, 0);
+    for col in columns {
+        match col.min_width() {
+            Some(min) => {
+                used += min;
+                weights += col.width();
             }
+            None => used += col.width(),
         }
     }
+    let spare = total.saturating_sub(used);
+
+    columns
+        .iter()
+        .map(|c| match c.min_width() {
+            Some(min) => min + c.width() * spare / weights,
+            None => c.width(),
+        })
+        .collect()
 }
 
 /// Truncate a string to a maximum length
-fn truncate_string(s: &str, max_len: usize) -> String {
-    if s.len() <= max_len {
+///
+/// Counts characters, not bytes, so multi-byte text is never split mid-character.
+/// Only for display: JSON output always carries the full value.
+pub(crate) fn truncate_string(s: &str, max_len: usize) -> String {
+    if s.chars().count() <= max_len {
         s.to_string()
     } else {
-        format!("{}...", &s[..max_len.saturating_sub(3)])
+        let kept: String = s.chars().take(max_len.saturating_sub(3)).collect();
+        format!("{}...", kept)
     }
 }
 
 /// Truncate a path intelligently - show the end (most relevant part)
 fn truncate_path(path: &str, max_len: usize) -> String {
-    if path.len() <= max_len {
+    let len = path.chars().count();
+    if len <= max_len {
         path.to_string()
     } else {
         // Show ...ending of path
-        let start = path.len().saturating_sub(max_len.saturating_sub(3));
-        format!("...{}", &path[start..])
+        let skip = len.saturating_sub(max_len.saturating_sub(3));
+        format!("...{}", path.chars().skip(skip).collect::<String>())
     }
 }
 
@@ -397,17 +1572,72 @@ fn colorize_status(
         crate::core::ProcessStatus::Sleeping => status_str.blue(),
         crate::core::ProcessStatus::Stopped => status_str.yellow(),
         crate::core::ProcessStatus::Zombie => status_str.red(),
+        crate::core::ProcessStatus::DiskSleep => status_str.magenta(),
         _ => status_str.white(),
     }
 }
 
 // JSON output structures
+#[derive(Serialize)]
+struct Versioned<'a, T> {
+    schema_version: u32,
+    version: &'static str,
+    #[serde(flatten)]
+    data: &'a T,
+}
+
+#[derive(Serialize)]
+struct ErrorOutput {
+    success: bool,
+    error: String,
+}
+
 #[derive(Serialize)]
 struct ProcessListOutput<'a> {
     action: &'static str,
     success: bool,
     count: usize,
-    processes: &'a [Process],
+    #[serde(skip_serializing_if = "Option::is_none")]
+    system: Option<&'a SystemSummary>,
+    processes: Vec<ListedProcess<'a>>,
+}
+
+#[derive(Serialize)]
+struct ListedProcess<'a> {
+    #[serde(flatten)]
+    process: &'a Process,
+    #[serde(flatten)]
+    span: Option<&'a MatchSpan>,
+}
+
+#[derive(Serialize)]
+struct DeltaOutput<'a> {
+    action: &'static str,
+    success: bool,
+    interval_secs: u64,
+    count: usize,
+    processes: &'a [ProcessDelta],
+}
+
+#[derive(Serialize)]
+struct CountOutput {
+    action: &'static str,
+    success: bool,
+    count: usize,
+}
+
+#[derive(Serialize)]
+struct CountCheckOutput {
+    action: &'static str,
+    success: bool,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    expected: Option<usize>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    expected_min: Option<usize>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    expected_max: Option<usize>,
+    found: usize,
+    ok: bool,
 }
 
 #[derive(Serialize)]
@@ -429,10 +1659,21 @@ struct SinglePortOutput<'a> {
 struct KillOutput<'a> {
     action: &'static str,
     success: bool,
+    dry_run: bool,
     killed_count: usize,
+    confirmed_count: usize,
+    escalated_count: usize,
+    defunct_count: usize,
+    skipped_count: usize,
     failed_count: usize,
     killed: &'a [Process],
+    confirmed: &'a [Process],
+    escalated: &'a [Process],
+    defunct: &'a [Process],
+    skipped: &'a [Process],
     failed: &'a [FailedKill<'a>],
+    #[serde(skip_serializing_if = "Option::is_none")]
+    would_kill: Option<&'a [Process]>,
 }
 
 #[derive(Serialize)]
@@ -446,3 +1687,150 @@ impl Default for Printer {
         Self::new(OutputFormat::Human, false)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_columns() {
+        assert_eq!(
+            Column::parse_list("pid,name,cpu").unwrap(),
+            vec![Column::Pid, Column::Name, Column::Cpu]
+        );
+        assert_eq!(
+            Column::parse_list(" cmd , PPID ").unwrap(),
+            vec![Column::Cmd, Column::Ppid]
+        );
+        assert_eq!(
+            Column::parse_list("mem,vmem,swap").unwrap(),
+            vec![Column::Mem, Column::Vmem, Column::Swap]
+        );
+    }
+
+    #[test]
+    fn test_parse_columns_invalid() {
+        let err = Column::parse_list("pid,bogus").unwrap_err().to_string();
+        assert!(err.contains("bogus"));
+        assert!(err.contains("ppid"));
+        assert!(Column::parse_list(",").is_err());
+    }
+
+    #[test]
+    fn test_versioned_json_leads_with_schema_version() {
+        let output = CountOutput {
+            action: "list",
+            success: true,
+            count: 3,
+        };
+        let json = serde_json::to_string(&Versioned {
+            schema_version: JSON_SCHEMA_VERSION,
+            version: crate::VERSION,
+            data: &output,
+        })
+        .unwrap();
+        let expected = format!(
+            r#"{{"schema_version":{},"version":"{}","action":"list","success":true,"count":3}}"#,
+            JSON_SCHEMA_VERSION,
+            crate::VERSION
+        );
+        assert_eq!(json, expected);
+    }
+
+    #[test]
+    fn test_thresholds_validate() {
+        assert!(Thresholds::default().validate().is_ok());
+        let inverted = Thresholds {
+            cpu_warn: 90.0,
+            ..Thresholds::default()
+        };
+        assert!(inverted.validate().is_err());
+        let inverted = Thresholds {
+            mem_crit_mb: 512.0,
+            ..Thresholds::default()
+        };
+        assert!(inverted.validate().is_err());
+        let nan = Thresholds {
+            cpu_crit: f32::NAN,
+            ..Thresholds::default()
+        };
+        assert!(nan.validate().is_err());
+        let infinite = Thresholds {
+            mem_warn_mb: f64::INFINITY,
+            ..Thresholds::default()
+        };
+        assert!(infinite.validate().is_err());
+    }
+
+    #[test]
+    fn test_thresholds_from_levels() {
+        let default = Thresholds::default();
+        assert_eq!(
+            Thresholds::from_levels(None, None, None, None).unwrap(),
+            default
+        );
+
+        // A lone level past its default partner carries the partner along
+        let raised = Thresholds::from_levels(Some(90.0), None, None, None).unwrap();
+        assert_eq!((raised.cpu_warn, raised.cpu_crit), (90.0, 90.0));
+        let lowered = Thresholds::from_levels(None, None, None, Some(512.0)).unwrap();
+        assert_eq!((lowered.mem_warn_mb, lowered.mem_crit_mb), (512.0, 512.0));
+        let within = Thresholds::from_levels(None, Some(95.0), None, None).unwrap();
+        assert_eq!((within.cpu_warn, within.cpu_crit), (default.cpu_warn, 95.0));
+
+        // Both given and inverted is still an error
+        assert!(Thresholds::from_levels(Some(90.0), Some(60.0), None, None).is_err());
+        assert!(Thresholds::from_levels(None, None, Some(f64::NAN), None).is_err());
+        assert!(Thresholds::from_levels(Some(-1.0), None, None, None).is_err());
+    }
+
+    #[test]
+    fn test_truncate_counts_characters() {
+        assert_eq!(truncate_string("héllo wörld", 8), "héllo...");
+        assert_eq!(truncate_string("ééé", 3), "ééé");
+        assert_eq!(truncate_path("/srv/ünïcødé/bin", 8), "...é/bin");
+    }
+
+    #[test]
+    fn test_json_keeps_full_command() {
+        let command = format!("node server.js --data {}", "x".repeat(4096));
+        let proc = Process {
+            pid: 7,
+            name: "node".to_string(),
+            command: Some(command.clone()),
+            ..Process::default()
+        };
+        let output = ProcessListOutput {
+            action: "list",
+            success: true,
+            count: 1,
+            system: None,
+            processes: vec![ListedProcess {
+                process: &proc,
+                span: None,
+            }],
+        };
+        let json = serde_json::to_value(&output).unwrap();
+        assert_eq!(json["processes"][0]["command"], command.as_str());
+    }
+
+    #[test]
+    fn test_fit_widths() {
+        let table_width = |widths: &[usize]| widths.iter().sum::<usize>() + widths.len() - 1;
+
+        let narrow = fit_widths(Column::DEFAULT, 80);
+        assert!(table_width(&narrow) <= 80);
+
+        let wide = fit_widths(Column::DEFAULT, 200);
+        assert!(table_width(&wide) <= 200);
+        assert!(wide[2] > narrow[2], "name column should widen");
+        assert_eq!(wide[0], Column::Pid.width());
+
+        // Flexible columns bottom out at their minimums
+        assert_eq!(
+            fit_widths(&[Column::Pid, Column::Name, Column::Cmd], 10),
+            vec![7, 12, 15]
+        );
+        assert_eq!(fit_widths(&[Column::Pid, Column::Cpu], 10), vec![7, 5]);
+    }
+}
@@ -0,0 +1,123 @@
+//! Interactive process selection
+//!
+//! Used when a target matches several processes and a terminal is attached.
+//! Callers must check [`is_interactive`] first so scripts never block on a
+//! prompt.
+
+use crate::core::Process;
+use crate::error::{ProcError, Result};
+use dialoguer::{MultiSelect, Select};
+use std::io::IsTerminal;
+
+/// Whether both stdin and stdout are attached to a terminal
+pub fn is_interactive() -> bool {
+    std::io::stdin().is_terminal() && std::io::stdout().is_terminal()
+}
+
+/// Fail early when a confirmation prompt can't be shown
+///
+/// Without a terminal on stdin the prompt would error out with an opaque
+/// dialog error, so scripts get told to pass `--yes` instead. `action` is the
+/// verb being confirmed, e.g. "kill".
+pub fn require_confirmation_terminal(action: &str) -> Result<()> {
+    if std::io::stdin().is_terminal() {
+        return Ok(());
+    }
+    Err(ProcError::InvalidInput(format!(
+        "refusing to {} without confirmation in non-interactive mode; pass --yes",
+        action
+    )))
+}
+
+/// Answer to a per-process confirmation
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum StepChoice {
+    /// Act on this process
+    Yes,
+    /// Leave this process alone and move on
+    No,
+    /// Leave this and every remaining process alone
+    Quit,
+}
+
+/// Ask whether to act on a single process, showing its usage and command
+///
+/// `action` is the verb being confirmed, e.g. "Kill". Cancelling the prompt
+/// (Esc) counts as quitting.
+pub fn confirm_step(proc: &Process, action: &str) -> Result<StepChoice> {
+    let prompt = format!(
+        "{} {} [PID {}] - CPU: {:.1}%, MEM: {:.1}MB  {}",
+        action,
+        proc.name,
+        proc.pid,
+        proc.cpu_percent,
+        proc.memory_mb,
+        short_command(proc)
+    );
+    let choice = Select::new()
+        .with_prompt(prompt)
+        .items(["yes", "no", "quit (skip the rest)"])
+        .default(1)
+        .interact_opt()?;
+
+    Ok(match choice {
+        Some(0) => StepChoice::Yes,
+        Some(1) => StepChoice::No,
+        _ => StepChoice::Quit,
+    })
+}
+
+/// A process's command line, truncated to fit a picker row
+fn short_command(proc: &Process) -> String {
+    let command = proc.command.as_deref().unwrap_or("");
+    if command.chars().count() > 45 {
+        format!("{}...", command.chars().take(42).collect::<String>())
+    } else {
+        command.to_string()
+    }
+}
+
+/// One-line label for a process in a picker
+fn label(proc: &Process) -> String {
+    let command = short_command(proc);
+    format!(
+        "{:>7}  {:<16} {:>5.1}% {:>8.1}MB  {}",
+        proc.pid, proc.name, proc.cpu_percent, proc.memory_mb, command
+    )
+}
+
+/// Ask the user to choose one process, or all of them
+///
+/// Returns an empty list if the prompt was cancelled.
+pub fn pick_one(processes: &[Process], prompt: &str) -> Result<Vec<Process>> {
+    let mut items = vec![format!("All {} processes", processes.len())];
+    items.extend(processes.iter().map(label));
+
+    let choice = Select::new()
+        .with_prompt(prompt)
+        .items(&items)
+        .default(1)
+        .interact_opt()?;
+
+    Ok(match choice {
+        Some(0) => processes.to_vec(),
+        Some(i) => vec![processes[i - 1].clone()],
+        None => Vec::new(),
+    })
+}
+
+/// Ask the user to choose any subset of processes
+///
+/// Nothing starts checked, so pressing enter straight away selects nothing.
+/// Returns an empty list if nothing was selected or the prompt was cancelled.
+pub fn pick_many(processes: &[Process], prompt: &str) -> Result<Vec<Process>> {
+    let items: Vec<String> = processes.iter().map(label).collect();
+
+    let chosen = MultiSelect::new()
+        .with_prompt(format!("{} (space to select, enter to confirm)", prompt))
+        .items(&items)
+        .interact_opt()?
+        .unwrap_or_default();
+
+    Ok(chosen.into_iter().map(|i| processes[i].clone()).collect())
+}
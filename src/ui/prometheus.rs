@@ -0,0 +1,148 @@
+//! Prometheus text exposition format
+//!
+//! Renders process and port snapshots for node_exporter's textfile collector.
+
+use crate::core::{PortInfo, Process};
+use std::collections::HashSet;
+use std::fmt::Write;
+
+/// Bytes in a megabyte, as used by `Process::memory_mb`
+const BYTES_PER_MB: f64 = 1024.0 * 1024.0;
+
+/// A per-process metric: name, help text, type, and how to read the value
+type ProcessMetric = (
+    &'static str,
+    &'static str,
+    &'static str,
+    fn(&Process) -> f64,
+);
+
+const PROCESS_METRICS: &[ProcessMetric] = &[
+    ("proc_cpu_percent", "CPU usage percentage", "gauge", |p| {
+        p.cpu_percent as f64
+    }),
+    (
+        "proc_cpu_time_seconds",
+        "Total CPU time consumed",
+        "counter",
+        |p| p.cpu_time_secs as f64,
+    ),
+    (
+        "proc_resident_memory_bytes",
+        "Resident memory in bytes",
+        "gauge",
+        |p| (p.memory_mb * BYTES_PER_MB).round(),
+    ),
+    (
+        "proc_virtual_memory_bytes",
+        "Virtual memory in bytes",
+        "gauge",
+        |p| (p.virtual_memory_mb * BYTES_PER_MB).round(),
+    ),
+    (
+        "proc_uptime_seconds",
+        "Seconds since the process started",
+        "gauge",
+        |p| p.uptime_secs.unwrap_or(0) as f64,
+    ),
+];
+
+/// Render process and listening-port metrics in exposition format
+///
+/// A process listening on the same port on several addresses (say
+/// `0.0.0.0` and `::`) gets one `proc_listening_port` series, since
+/// duplicate series make the textfile collector reject the whole file.
+pub fn render(processes: &[Process], ports: &[PortInfo]) -> String {
+    let mut out = String::new();
+
+    for (metric, help, kind, value) in PROCESS_METRICS {
+        if processes.is_empty() {
+            break;
+        }
+        let _ = writeln!(out, "# HELP {} {}", metric, help);
+        let _ = writeln!(out, "# TYPE {} {}", metric, kind);
+        for proc in processes {
+            let _ = writeln!(
+                out,
+                "{}{{pid=\"{}\",name=\"{}\"}} {}",
+                metric,
+                proc.pid,
+                escape_label(&proc.name),
+                value(proc)
+            );
+        }
+    }
+
+    if !ports.is_empty() {
+        let _ = writeln!(
+            out,
+            "# HELP proc_listening_port Port a process is listening on"
+        );
+        let _ = writeln!(out, "# TYPE proc_listening_port gauge");
+        let mut seen = HashSet::new();
+        for port in ports {
+            if !seen.insert((port.port, port.protocol, port.pid)) {
+                continue;
+            }
+            let _ = writeln!(
+                out,
+                "proc_listening_port{{port=\"{}\",proto=\"{}\",pid=\"{}\",name=\"{}\"}} 1",
+                port.port,
+                format!("{:?}", port.protocol).to_lowercase(),
+                port.pid,
+                escape_label(&port.process_name)
+            );
+        }
+    }
+
+    out
+}
+
+/// Escape a label value: backslash, double quote, and newline
+fn escape_label(value: &str) -> String {
+    value
+        .replace('\\', "\\\\")
+        .replace('"', "\\\"")
+        .replace('\n', "\\n")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::Protocol;
+
+    #[test]
+    fn test_escape_label() {
+        assert_eq!(escape_label(r#"a"b\c"#), r#"a\"b\\c"#);
+        assert_eq!(escape_label("x\ny"), "x\\ny");
+    }
+
+    #[test]
+    fn test_render() {
+        let proc = Process {
+            pid: 42,
+            name: "node".to_string(),
+            memory_mb: 12.5,
+            ..Process::default()
+        };
+        let port = PortInfo {
+            port: 3000,
+            protocol: Protocol::Tcp,
+            pid: 42,
+            process_name: "node".to_string(),
+            address: None,
+        };
+        let dual_stack = PortInfo {
+            address: Some("::".to_string()),
+            ..port.clone()
+        };
+        let text = render(&[proc], &[port, dual_stack]);
+        assert!(text.contains("# TYPE proc_resident_memory_bytes gauge\n"));
+        assert!(text.contains("proc_resident_memory_bytes{pid=\"42\",name=\"node\"} 13107200\n"));
+        assert!(text.contains(
+            "proc_listening_port{port=\"3000\",proto=\"tcp\",pid=\"42\",name=\"node\"} 1\n"
+        ));
+        assert_eq!(text.matches("proc_listening_port{").count(), 1);
+        assert_eq!(render(&[], &[]), "");
+    }
+}
@@ -0,0 +1,193 @@
+//! Tree rendering for process hierarchies
+//!
+//! Shared by `proc tree` and `proc list --tree`.
+
+use crate::core::{Process, ProcessStatus};
+use colored::*;
+use std::collections::{HashMap, HashSet};
+
+/// Status indicator glyph used in tree views
+pub fn status_indicator(status: &ProcessStatus) -> ColoredString {
+    match status {
+        ProcessStatus::Running => "●".green(),
+        ProcessStatus::Sleeping => "○".blue(),
+        ProcessStatus::Stopped => "◐".yellow(),
+        ProcessStatus::Zombie => "✗".red(),
+        ProcessStatus::DiskSleep => "◆".magenta(),
+        _ => "?".white(),
+    }
+}
+
+/// Renders a process hierarchy with box-drawing connectors
+pub struct TreePrinter<'a> {
+    children_map: &'a HashMap<u32, Vec<&'a Process>>,
+    max_depth: usize,
+    compact: bool,
+    matches: Option<&'a HashSet<u32>>,
+    highlight: Option<&'a HashSet<u32>>,
+    threads: Option<&'a HashMap<u32, Vec<Process>>>,
+}
+
+impl<'a> TreePrinter<'a> {
+    /// Creates a tree printer over a parent -> children map
+    pub fn new(children_map: &'a HashMap<u32, Vec<&'a Process>>) -> Self {
+        Self {
+            children_map,
+            max_depth: usize::MAX,
+            compact: false,
+            matches: None,
+            highlight: None,
+            threads: None,
+        }
+    }
+
+    /// Stop descending below this depth
+    pub fn max_depth(mut self, depth: usize) -> Self {
+        self.max_depth = depth;
+        self
+    }
+
+    /// Show PIDs only
+    pub fn compact(mut self, compact: bool) -> Self {
+        self.compact = compact;
+        self
+    }
+
+    /// Only these PIDs are rendered normally; all others are dimmed as context
+    pub fn matches(mut self, pids: &'a HashSet<u32>) -> Self {
+        self.matches = Some(pids);
+        self
+    }
+
+    /// Emphasize these PIDs (e.g. the targets a subtree was taken from)
+    pub fn highlight(mut self, pids: &'a HashSet<u32>) -> Self {
+        self.highlight = Some(pids);
+        self
+    }
+
+    /// Render these threads as dimmed leaves under their owning process
+    pub fn threads(mut self, threads: &'a HashMap<u32, Vec<Process>>) -> Self {
+        self.threads = Some(threads);
+        self
+    }
+
+    /// Print a list of root processes and their subtrees
+    pub fn print_roots(&self, roots: &[&Process]) {
+        for (i, proc) in roots.iter().enumerate() {
+            let is_last = i == roots.len() - 1;
+            self.print(proc, "", is_last, 0);
+        }
+    }
+
+    /// Print a process and its subtree
+    pub fn print(&self, proc: &Process, prefix: &str, is_last: bool, depth: usize) {
+        if depth > self.max_depth {
+            return;
+        }
+
+        let connector = if is_last { "└── " } else { "├── " };
+        let is_context = self
+            .matches
+            .map(|pids| !pids.contains(&proc.pid))
+            .unwrap_or(false);
+
+        if self.compact {
+            let pid = if is_context {
+                proc.pid.to_string().bright_black()
+            } else {
+                proc.pid.to_string().cyan()
+            };
+            println!(
+                "{}{}{}",
+                prefix.bright_black(),
+                connector.bright_black(),
+                pid
+            );
+        } else if is_context {
+            println!(
+                "{}{}{}",
+                prefix.bright_black(),
+                connector.bright_black(),
+                format!(
+                    "{} [{}] {:.1}% {:.1}MB",
+                    proc.name, proc.pid, proc.cpu_percent, proc.memory_mb
+                )
+                .bright_black()
+            );
+        } else {
+            let highlighted = self.highlight.is_some_and(|pids| pids.contains(&proc.pid));
+            println!(
+                "{}{}{} {} [{}] {:.1}% {:.1}MB",
+                prefix.bright_black(),
+                connector.bright_black(),
+                status_indicator(&proc.status),
+                if highlighted {
+                    proc.name.red().bold()
+                } else {
+                    proc.name.white().bold()
+                },
+                proc.pid.to_string().cyan(),
+                proc.cpu_percent,
+                proc.memory_mb
+            );
+        }
+
+        let child_prefix = if is_last {
+            format!("{}    ", prefix)
+        } else {
+            format!("{}│   ", prefix)
+        };
+        self.print_children(proc, &child_prefix, depth);
+    }
+
+    /// Print a process's threads and child subtrees, but not the process itself
+    ///
+    /// `prefix` is the indentation of the children and `depth` the depth of
+    /// `proc`.
+    pub fn print_children(&self, proc: &Process, prefix: &str, depth: usize) {
+        let mut sorted_children: Vec<&Process> = self
+            .children_map
+            .get(&proc.pid)
+            .map(|kids| kids.to_vec())
+            .unwrap_or_default();
+        sorted_children.sort_by_key(|p| p.pid);
+
+        let mut threads: Vec<&Process> = self
+            .threads
+            .and_then(|map| map.get(&proc.pid))
+            .map(|tasks| tasks.iter().collect())
+            .unwrap_or_default();
+        threads.sort_by_key(|t| t.pid);
+
+        if depth < self.max_depth {
+            for (i, thread) in threads.iter().enumerate() {
+                let is_last = i == threads.len() - 1 && sorted_children.is_empty();
+                self.print_thread(thread, prefix, is_last);
+            }
+        }
+
+        for (i, child) in sorted_children.iter().enumerate() {
+            let child_is_last = i == sorted_children.len() - 1;
+            self.print(child, prefix, child_is_last, depth + 1);
+        }
+    }
+
+    /// Print a thread as a dimmed leaf
+    fn print_thread(&self, thread: &Process, prefix: &str, is_last: bool) {
+        let connector = if is_last { "└── " } else { "├── " };
+        let label = if self.compact {
+            format!("~{}", thread.pid)
+        } else {
+            format!(
+                "~ {} [tid {}] {:.1}%",
+                thread.name, thread.pid, thread.cpu_percent
+            )
+        };
+        println!(
+            "{}{}{}",
+            prefix.bright_black(),
+            connector.bright_black(),
+            label.bright_black()
+        );
+    }
+}